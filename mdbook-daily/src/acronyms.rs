@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::link::relative_link;
+use crate::locale::Strings;
+use crate::summary::{escape_title, SummaryEntry};
+
+/// Whether a word looks like an acronym: two or more characters, starting
+/// with an uppercase letter, made of uppercase letters and digits
+fn is_acronym(word: &str) -> bool {
+    word.chars().count() >= 2
+        && word.starts_with(|c: char| c.is_ascii_uppercase())
+        && word.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+/// Whether the initials of `words` spell the letters of `acronym`
+fn initials_match(acronym: &str, words: &[&str]) -> bool {
+    let letters: Vec<char> = acronym.chars().filter(|c| c.is_ascii_alphabetic()).collect();
+    letters.len() == words.len()
+        && letters.iter().zip(words).all(|(l, w)| {
+            w.chars().next().is_some_and(|c| c.eq_ignore_ascii_case(l))
+        })
+}
+
+fn trim_word(word: &str) -> &str {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+/// Acronym definitions on one line, written as `ABC (Alpha Beta Charlie)`
+/// or `Alpha Beta Charlie (ABC)`
+pub fn find_definitions(line: &str) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    let mut rest = line;
+    let mut offset = 0;
+    while let Some(open) = rest.find('(') {
+        let Some(close) = rest[open..].find(')') else { break };
+        let inside = &rest[open + 1..open + close];
+        let before: Vec<&str> = line[..offset + open].split_whitespace().collect();
+
+        let inner: Vec<&str> = inside.split_whitespace().collect();
+        if let Some(acronym) = before.last().map(|w| trim_word(w)) {
+            if is_acronym(acronym) && initials_match(acronym, &inner) {
+                found.push((acronym.to_string(), inner.join(" ")));
+            }
+        }
+        if inner.len() == 1 && is_acronym(inner[0]) {
+            let count = inner[0].chars().filter(|c| c.is_ascii_alphabetic()).count();
+            if before.len() >= count {
+                let words: Vec<&str> = before[before.len() - count..].iter().map(|w| trim_word(w)).collect();
+                if initials_match(inner[0], &words) {
+                    found.push((inner[0].to_string(), words.join(" ")));
+                }
+            }
+        }
+
+        offset += open + close + 1;
+        rest = &rest[open + close + 1..];
+    }
+    found
+}
+
+/// Write an index of acronyms with links to the note that first defines
+/// each one, visiting notes in SUMMARY order. `page` is relative to src.
+pub fn write_acronym_page(
+    src_path: &Path,
+    strings: &Strings,
+    entries: &[SummaryEntry],
+    page: &Path,
+    out: &mut dyn Write,
+) -> io::Result<usize> {
+    let page_dir = page.parent().unwrap_or(Path::new(""));
+    let mut definitions: BTreeMap<String, (String, String, String)> = BTreeMap::new();
+    for entry in entries {
+        let Some(link) = &entry.path else { continue };
+        let relative = Path::new(link.strip_prefix("./").unwrap_or(link));
+        if relative == page {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(src_path.join(relative)) else { continue };
+        let mut in_code = false;
+        for line in content.lines() {
+            if line.trim_start().starts_with("```") {
+                in_code = !in_code;
+                continue;
+            }
+            if in_code {
+                continue;
+            }
+            for (acronym, meaning) in find_definitions(line) {
+                definitions.entry(acronym).or_insert_with(|| {
+                    (meaning, entry.title.clone(), relative_link(page_dir, relative))
+                });
+            }
+        }
+    }
+
+    writeln!(out, "# {}", strings.acronyms)?;
+    writeln!(out)?;
+    writeln!(out, "| {} | {} | {} |", strings.acronym, strings.meaning, strings.defined_in)?;
+    writeln!(out, "|---------|---------|------------|")?;
+    for (acronym, (meaning, title, link)) in &definitions {
+        writeln!(out, "| {} | {} | [{}]({}) |", acronym, meaning, escape_title(title), link)?;
+    }
+    Ok(definitions.len())
+}
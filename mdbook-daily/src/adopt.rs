@@ -0,0 +1,200 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+use crate::date::{date_from_path, Date};
+use crate::frontmatter;
+use crate::interactive::{ask, confirm};
+use crate::links::markdown_files;
+use crate::summary::ScanStats;
+
+/// Section for notes that sit directly in the adopted folder
+const DEFAULT_SECTION: &str = "notes";
+
+/// How adopted notes get into src
+#[derive(Clone, Copy)]
+enum Mode {
+    Copy,
+    Move,
+    /// Symlink to the original, leaving the folder as it is
+    Link,
+}
+
+/// Where one note of the adopted folder goes
+struct Placement {
+    source: PathBuf,
+    section: String,
+    date: Option<Date>,
+    /// Destination relative to src
+    target: PathBuf,
+}
+
+/// Whether a directory name is a date part (`2024`, `06`) rather than a topic
+fn is_date_part(name: &str) -> bool {
+    matches!(name.len(), 2 | 4) && name.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Guess a note's section from its first non-date directory and its date
+/// from the file name or front matter
+fn guess(dir: &Path, source: &Path) -> (String, Option<Date>) {
+    let relative = source.strip_prefix(dir).unwrap_or(source);
+    let section = relative.parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .find(|name| !is_date_part(name))
+        .map_or_else(|| DEFAULT_SECTION.to_string(), |name| name.to_lowercase());
+    let date = date_from_path(&relative.to_string_lossy().replace('\\', "/"))
+        .or_else(|| fs::read_to_string(source).ok().and_then(|c| frontmatter::split(&c).0?.date));
+    (section, date)
+}
+
+/// Destination of a note: `SECTION/YYYY/MMDD.md` for dated notes, falling
+/// back to `SECTION/YYYY/YYYY-MM-DD-STEM.md` when two land on the same
+/// day, and `SECTION/STEM.md` for undated ones
+fn target(src_path: &Path, placement: &Placement, taken: &HashSet<PathBuf>) -> Option<PathBuf> {
+    let stem = placement.source.file_stem()?.to_string_lossy();
+    let section = Path::new(&placement.section);
+    let candidates = match placement.date {
+        Some(date) => {
+            let year = section.join(date.year.to_string());
+            let dated_stem = if date_from_path(&stem).is_some() { stem.into_owned() } else { format!("{}-{}", date, stem) };
+            vec![
+                year.join(format!("{:02}{:02}.md", date.month, date.day)),
+                year.join(format!("{}.md", dated_stem)),
+            ]
+        }
+        None => vec![section.join(format!("{}.md", stem))],
+    };
+    candidates.into_iter().find(|c| !taken.contains(c) && !src_path.join(c).exists())
+}
+
+/// Print the proposed layout, grouped by section
+fn print_plan(dir: &Path, placements: &[Placement]) {
+    let mut sections: BTreeMap<&str, Vec<&Placement>> = BTreeMap::new();
+    for placement in placements {
+        sections.entry(&placement.section).or_default().push(placement);
+    }
+    for (section, notes) in sections {
+        let dated = notes.iter().filter(|p| p.date.is_some()).count();
+        println!("{} ({} notes, {} dated)", section, notes.len(), dated);
+        for note in notes {
+            let from = note.source.strip_prefix(dir).unwrap_or(&note.source);
+            println!("  {} -> {}", from.display(), note.target.display());
+        }
+    }
+}
+
+/// Put one note at its destination
+fn place(mode: Mode, source: &Path, destination: &Path) -> io::Result<()> {
+    match mode {
+        Mode::Copy => fs::copy(source, destination).map(|_| ()),
+        Mode::Move => fs::rename(source, destination).or_else(|_| {
+            // Across filesystems a rename fails, so copy and remove
+            fs::copy(source, destination)?;
+            fs::remove_file(source)
+        }),
+        #[cfg(unix)]
+        Mode::Link => std::os::unix::fs::symlink(fs::canonicalize(source)?, destination),
+        #[cfg(windows)]
+        Mode::Link => std::os::windows::fs::symlink_file(fs::canonicalize(source)?, destination),
+    }
+}
+
+/// `mdbook-daily adopt DIR [--move|--link] [--yes]`: bring a folder of
+/// markdown notes into src, guessing each note's section from its
+/// directories and its date from the file name or front matter. The
+/// proposed layout is shown and each section can be renamed before
+/// anything is written; `--yes` accepts the proposal as is. Notes are
+/// copied by default, moved with `--move`, or symlinked with `--link` so
+/// the folder keeps working as before.
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut dir = None;
+    let mut mode = Mode::Copy;
+    let mut yes = false;
+    for arg in args {
+        match arg.as_str() {
+            "--move" => mode = Mode::Move,
+            "--link" => mode = Mode::Link,
+            "--yes" => yes = true,
+            _ if arg.starts_with("--") => return Err(crate::usage_error(&format!("unknown adopt argument {:?}", arg))),
+            _ => dir = Some(PathBuf::from(arg)),
+        }
+    }
+    let Some(dir) = dir else { return Err(crate::usage_error("adopt needs a directory")) };
+    if !dir.is_dir() {
+        return Err(crate::usage_error(&format!("{:?} is not a directory", dir)));
+    }
+
+    let mut placements: Vec<Placement> = markdown_files(&dir).into_iter()
+        .map(|source| {
+            let (section, date) = guess(&dir, &source);
+            Placement { source, section, date, target: PathBuf::new() }
+        })
+        .collect();
+    if placements.is_empty() {
+        println!("No markdown files found in {:?}", dir);
+        return Ok(());
+    }
+
+    if !yes {
+        let guessed: Vec<String> = placements.iter().map(|p| p.section.clone())
+            .collect::<BTreeSet<_>>().into_iter().collect();
+        for section in guessed {
+            let renamed = ask(&format!("Section for notes guessed as {:?}?", section), &section)?;
+            for placement in placements.iter_mut().filter(|p| p.section == section) {
+                placement.section = renamed.clone();
+            }
+        }
+    }
+
+    let mut taken = HashSet::new();
+    let mut conflicts = Vec::new();
+    placements.retain_mut(|placement| match target(src_path, placement, &taken) {
+        Some(target) => {
+            taken.insert(target.clone());
+            placement.target = target;
+            true
+        }
+        None => {
+            conflicts.push(placement.source.clone());
+            false
+        }
+    });
+
+    print_plan(&dir, &placements);
+    for source in &conflicts {
+        eprintln!("Warning: {} would overwrite an existing note, leaving it out", source.display());
+    }
+    if !yes && !confirm(&format!("Adopt {} notes into {:?}?", placements.len(), src_path))? {
+        return Ok(());
+    }
+    fs::create_dir_all(src_path)?;
+
+    for placement in &placements {
+        let destination = src_path.join(&placement.target);
+        let parent = destination.parent().unwrap_or(src_path);
+        fs::create_dir_all(parent)?;
+        // Without a README a year directory is only a draft chapter
+        let readme = parent.join("README.md");
+        if let (Some(date), false) = (placement.date, readme.exists()) {
+            fs::write(&readme, format!("# {}\n", date.year))?;
+        }
+        place(mode, &placement.source, &destination)?;
+    }
+
+    // A minimal book.toml is enough for `mdbook build` to pick up src
+    let book = Path::new("book.toml");
+    if !book.exists() {
+        let title = dir.canonicalize()?.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        fs::write(book, format!("[book]\ntitle = {:?}\nsrc = {:?}\n", title, src_path.to_string_lossy()))?;
+        println!("Wrote {:?}", book);
+    }
+    let mut stats = ScanStats::default();
+    crate::write_summary(src_path, &config::book_options(Path::new("."))?, &mut stats)?;
+    stats.print_warnings();
+    println!("Adopted {} notes, updated {:?}", placements.len(), src_path.join("SUMMARY.md"));
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::generated::write_if_changed;
+use crate::links::chapter_link;
+use crate::sources;
+use crate::summary::{Options, ScanStats};
+
+/// First line of every redirect stub, so stale ones can be told apart
+/// from authored HTML files
+const MARKER: &str = "<!-- mdbook-daily alias -->";
+
+fn escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// The stub (relative to src) an `aliases` value redirects from: `old/page`,
+/// `/old/page.html` and `old/page.md` all give `old/page.html`, and
+/// `old/dir/` gives `old/dir/index.html`. `None` for a value leaving src.
+pub fn stub_path(alias: &str) -> Option<PathBuf> {
+    let alias = alias.trim().trim_start_matches('/');
+    let alias = match alias.strip_suffix('/') {
+        Some(dir) => format!("{}/index", dir),
+        None => alias.trim_end_matches(".html").trim_end_matches(".md").to_string(),
+    };
+    let path = Path::new(&alias);
+    if alias.is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    Some(path.with_extension("html"))
+}
+
+/// A page sending browsers from `stub` to the chapter at `target`, both
+/// relative to src
+fn stub(stub: &Path, target: &Path) -> String {
+    let link = chapter_link(stub.parent().unwrap_or(Path::new("")), target);
+    let link = escape(&format!("{}.html", link.strip_suffix(".md").unwrap_or(&link)));
+    format!(
+        "{}\n<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Redirecting...</title>\n\
+         <meta http-equiv=\"refresh\" content=\"0; URL={}\">\n<link rel=\"canonical\" href=\"{}\">\n</head>\n\
+         <body>\n<p>Redirecting to <a href=\"{}\">{}</a>...</p>\n</body>\n</html>\n",
+        MARKER, link, link, link, link
+    )
+}
+
+/// Write a redirect stub at each old path listed in an entry's `aliases`
+/// front matter. mdBook copies the `.html` files under src into the built
+/// book as they are, so links to the old pages keep working after entries
+/// move. An alias clashing with a chapter or an authored HTML file is
+/// skipped with a warning, and stubs whose alias went away are removed.
+/// Returns the number of stubs.
+pub fn write_redirects(src_path: &Path, options: &Options, stats: &ScanStats) -> io::Result<usize> {
+    let excluded: HashSet<&str> = stats.excluded.iter().map(|(_, _, link)| link.as_str()).collect();
+    let mut stubs: BTreeMap<PathBuf, &str> = BTreeMap::new();
+    let mut links: Vec<_> = stats.front_matter.iter()
+        .filter(|(link, front)| !front.aliases.is_empty() && !excluded.contains(link.as_str()) && (!front.draft || options.include_drafts))
+        .collect();
+    links.sort_by_key(|(link, _)| link.as_str());
+    for (link, front) in links {
+        for alias in &front.aliases {
+            let Some(relative) = stub_path(alias) else {
+                eprintln!("Warning: {}: alias {:?} is outside the book", link, alias);
+                continue;
+            };
+            let path = src_path.join(&relative);
+            let chapter = path.with_extension("md");
+            let clash = if chapter.is_file() {
+                Some("a chapter")
+            } else if path.is_file() && !is_stub(&path) {
+                Some("an HTML file")
+            } else {
+                stubs.get(&relative).filter(|other| **other != link.as_str()).map(|_| "another entry's alias")
+            };
+            match clash {
+                Some(clash) => eprintln!("Warning: {}: alias {:?} is already {}", link, alias, clash),
+                None => {
+                    stubs.insert(relative, link);
+                }
+            }
+        }
+    }
+
+    let mut keep = HashSet::new();
+    for (relative, link) in &stubs {
+        let path = src_path.join(relative);
+        sources::check_write(options.read_only_sources, src_path, &options.generated_dir, &path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        write_if_changed(&path, stub(relative, Path::new(link.trim_start_matches("./"))).as_bytes(), options.line_ending)?;
+        keep.insert(path);
+    }
+    for path in sources::snapshot(src_path).into_keys() {
+        if path.extension().is_some_and(|e| e == "html") && !keep.contains(&path) && is_stub(&path) {
+            fs::remove_file(&path)?;
+            // Only succeeds while the directories are left empty
+            for dir in path.ancestors().skip(1).take_while(|dir| *dir != src_path) {
+                if fs::remove_dir(dir).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(stubs.len())
+}
+
+/// Whether the file at `path` is a redirect stub written by
+/// [`write_redirects`]
+fn is_stub(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| content.starts_with(MARKER))
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::assets::rewrite_links;
+use crate::config;
+use crate::date::Date;
+use crate::frontmatter;
+use crate::link::relative_link;
+use crate::links::{markdown_files, markdown_links, resolve_link};
+use crate::summary::{Options, ScanStats};
+
+/// Flat daily notes dated before `cutoff`, relative to src, mapped to
+/// their place in a `YYYY/MM/` directory next to them, or in an `MM/`
+/// one when they are in a `YYYY/` directory already. Only notes whose
+/// file name holds the full date move, so they keep their date.
+fn planned_moves(src_path: &Path, options: &Options, cutoff: Date) -> HashMap<PathBuf, PathBuf> {
+    let mut moves = HashMap::new();
+    for path in markdown_files(src_path) {
+        let relative = path.strip_prefix(src_path).unwrap_or(&path).to_path_buf();
+        if relative.starts_with(&options.generated_dir) {
+            continue;
+        }
+        let stem = relative.file_stem().unwrap_or_default().to_string_lossy();
+        let Some(date) = options.entry_date(&stem).filter(|date| *date < cutoff) else { continue };
+        let parent = relative.parent().unwrap_or(Path::new(""));
+        let year = format!("{:04}", date.year);
+        let month_dir = PathBuf::from(&year).join(format!("{:02}", date.month));
+        if parent.ends_with(&month_dir) {
+            continue;
+        }
+        // Notes already filed by year only gain the month
+        let target_dir = if parent.ends_with(&year) { parent.join(format!("{:02}", date.month)) } else { parent.join(month_dir) };
+        let target = target_dir.join(relative.file_name().unwrap_or_default());
+        if src_path.join(&target).exists() {
+            eprintln!("Warning: {} already exists, leaving {} in place", target.display(), relative.display());
+            continue;
+        }
+        moves.insert(relative, target);
+    }
+    moves
+}
+
+/// Notes tagged `tag` and with status `status` (either may be left
+/// out), relative to src, mapped to the same path under the `to`
+/// section. Notes already there, READMEs and generated pages stay.
+fn matching_moves(src_path: &Path, options: &Options, tag: Option<&str>, status: Option<&str>, to: &Path) -> HashMap<PathBuf, PathBuf> {
+    let mut moves = HashMap::new();
+    for path in markdown_files(src_path) {
+        let relative = path.strip_prefix(src_path).unwrap_or(&path).to_path_buf();
+        if relative.starts_with(&options.generated_dir) || relative.starts_with(to) || relative.ends_with("README.md") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+        let Some(front) = frontmatter::split(&content).0 else { continue };
+        if tag.is_some_and(|tag| !front.tags.iter().any(|t| t == tag)) || status.is_some_and(|s| front.status.as_deref() != Some(s)) {
+            continue;
+        }
+        let target = to.join(&relative);
+        if src_path.join(&target).exists() {
+            eprintln!("Warning: {} already exists, leaving {} in place", target.display(), relative.display());
+            continue;
+        }
+        moves.insert(relative, target);
+    }
+    moves
+}
+
+/// `content` of the note at `relative` with its local links pointing at
+/// the same files once the notes in `moves` have moved
+fn relink(content: &str, relative: &Path, moves: &HashMap<PathBuf, PathBuf>) -> String {
+    let moved_to = moves.get(relative).map_or(relative, PathBuf::as_path);
+    let mut updated = content.to_string();
+    for target in markdown_links(content) {
+        if target.starts_with('/') {
+            continue;
+        }
+        let resolved = resolve_link(relative, &target);
+        let linked = moves.get(&resolved).unwrap_or(&resolved);
+        if moved_to == relative && *linked == resolved {
+            continue;
+        }
+        let link = relative_link(moved_to.parent().unwrap_or(Path::new("")), linked);
+        // Keep links that did not start with `./` in that form
+        let link = if target.starts_with("./") { link } else { link.trim_start_matches("./").to_string() };
+        if link != target {
+            updated = rewrite_links(&updated, &target, &link);
+        }
+    }
+    updated
+}
+
+/// `mdbook-daily archive --older-than AGE | [--tag TAG] [--status
+/// STATUS] [--to DIR] [--dry-run]`: move flat daily notes older than AGE
+/// (`90d`, `6m`, ...) into `YYYY/MM/` directories, or the notes with a
+/// tag or status into the `DIR` section (`archive` unless given), fix the
+/// relative links in and to them, then regenerate SUMMARY.md
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut cutoff = None;
+    let mut tag = None;
+    let mut status = None;
+    let mut to = PathBuf::from("archive");
+    let mut dry_run = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--older-than" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--older-than needs an age"))?;
+                cutoff = Some(Date::today().minus_age(value)
+                    .ok_or_else(|| crate::usage_error(&format!("invalid age {:?}, expected e.g. 90d or 2y", value)))?);
+            }
+            "--tag" => tag = Some(args.next().ok_or_else(|| crate::usage_error("--tag needs a tag"))?.clone()),
+            "--status" => status = Some(args.next().ok_or_else(|| crate::usage_error("--status needs a status"))?.clone()),
+            "--to" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--to needs a directory"))?;
+                to = config::generated_dir(value).map_err(|_| {
+                    crate::usage_error(&format!("--to {:?} must be a directory inside src, such as \"archive\"", value))
+                })?;
+            }
+            "--dry-run" => dry_run = true,
+            _ => return Err(crate::usage_error(&format!("unknown archive argument {:?}", arg))),
+        }
+    }
+    let by_front_matter = tag.is_some() || status.is_some();
+    if by_front_matter == cutoff.is_some() {
+        return Err(crate::usage_error("archive needs --older-than, or --tag or --status, but not both"));
+    }
+    let options = config::book_options(Path::new("."))?;
+    if to.starts_with(&options.generated_dir) {
+        return Err(crate::usage_error("--to cannot be the generated pages directory"));
+    }
+
+    let moves = match cutoff {
+        Some(cutoff) => planned_moves(src_path, &options, cutoff),
+        None => matching_moves(src_path, &options, tag.as_deref(), status.as_deref(), &to),
+    };
+    let mut sorted: Vec<_> = moves.iter().collect();
+    sorted.sort();
+    for (from, to) in &sorted {
+        println!("{} -> {}", from.display(), to.display());
+    }
+    if dry_run || moves.is_empty() {
+        let verb = if dry_run { "Would move" } else { "Moved" };
+        println!("{} {} notes", verb, moves.len());
+        return Ok(());
+    }
+
+    let mut relinked = 0;
+    for path in markdown_files(src_path) {
+        let relative = path.strip_prefix(src_path).unwrap_or(&path).to_path_buf();
+        let content = fs::read_to_string(&path)?;
+        let updated = relink(&content, &relative, &moves);
+        if updated != content {
+            fs::write(&path, updated)?;
+            relinked += 1;
+        }
+    }
+    for (from, target) in sorted {
+        let target = src_path.join(target);
+        let target_dir = target.parent().unwrap_or(src_path);
+        fs::create_dir_all(target_dir)?;
+        // Without READMEs the new directories would only be draft chapters.
+        // Dated notes gain a year and month; the others keep their path
+        // under the archive section, whose own directory is a part.
+        let dirs: Vec<&Path> = match cutoff {
+            Some(_) => vec![target_dir.parent().unwrap_or(src_path), target_dir],
+            None => target_dir.ancestors().take_while(|dir| *dir != src_path.join(&to) && *dir != src_path).collect(),
+        };
+        for dir in dirs {
+            let readme = dir.join("README.md");
+            if !readme.exists() {
+                let name = dir.file_name().unwrap_or_default().to_string_lossy();
+                let title = options.title_for(&name, dir.strip_prefix(src_path).unwrap_or(dir));
+                fs::write(readme, format!("# {}\n", title))?;
+            }
+        }
+        fs::rename(src_path.join(from), target)?;
+    }
+
+    let mut stats = ScanStats::default();
+    crate::write_summary(src_path, &options, &mut stats)?;
+    stats.print_warnings();
+    println!("Moved {} notes and updated links in {}", moves.len(), relinked);
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::Hasher;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::link::relative_link;
+use crate::links::{markdown_files, markdown_links, resolve_link};
+use crate::summary::Options;
+
+/// Extensions of the attachments checked for duplicates
+const EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "gif", "webp", "svg", "avif"];
+
+/// Every attachment under src outside the generated directory, sorted
+fn attachments(src_path: &Path, options: &Options) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![src_path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                if path != src_path.join(&options.generated_dir) {
+                    pending.push(path);
+                }
+            } else if path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| EXTENSIONS.contains(&e.to_lowercase().as_str()))
+            {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// Byte-identical attachments; only the first keeps its content, for
+/// comparing the files that come after it
+type Group = Vec<(PathBuf, Vec<u8>)>;
+
+/// Groups of byte-identical attachments, each with its first path as the
+/// copy to keep. Files are bucketed by size and hash and then compared
+/// in full, so a hash collision cannot merge different images.
+fn duplicate_groups(files: &[PathBuf]) -> io::Result<Vec<Vec<PathBuf>>> {
+    let mut buckets: HashMap<(u64, u64), Vec<Group>> = HashMap::new();
+    for path in files {
+        let content = fs::read(path)?;
+        let mut hasher = DefaultHasher::new();
+        hasher.write(&content);
+        let groups = buckets.entry((content.len() as u64, hasher.finish())).or_default();
+        match groups.iter_mut().find(|group| group[0].1 == content) {
+            Some(group) => group.push((path.clone(), Vec::new())),
+            None => groups.push(vec![(path.clone(), content)]),
+        }
+    }
+    let mut duplicates: Vec<Vec<PathBuf>> = buckets.into_values()
+        .flatten()
+        .filter(|group| group.len() > 1)
+        .map(|group| group.into_iter().map(|(path, _)| path).collect())
+        .collect();
+    duplicates.sort();
+    Ok(duplicates)
+}
+
+/// Point every `](target` link in `content` whose target is `from` at `to`
+pub fn rewrite_links(content: &str, from: &str, to: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(pos) = rest.find("](") {
+        let (head, tail) = rest.split_at(pos + 2);
+        out.push_str(head);
+        let bracketed = tail.starts_with('<');
+        let target = if bracketed { &tail[1..] } else { tail };
+        let ends = target.strip_prefix(from)
+            .is_some_and(|after| after.starts_with([')', ' ', '#', '>']));
+        if ends {
+            if bracketed {
+                out.push('<');
+            }
+            out.push_str(to);
+            rest = &target[from.len()..];
+        } else {
+            rest = tail;
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `mdbook-daily dedupe [--dry-run]`: find identical attachments under
+/// src, keep one copy of each, point every markdown link at the kept copy
+/// and delete the rest
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let dry_run = match args {
+        [] => false,
+        [flag] if flag == "--dry-run" => true,
+        _ => return Err(crate::usage_error("dedupe only accepts --dry-run")),
+    };
+    let options = crate::config::book_options(Path::new("."))?;
+    let groups = duplicate_groups(&attachments(src_path, &options))?;
+
+    // Each duplicate, relative to src, mapped to the copy that is kept
+    let mut replaced: HashMap<PathBuf, PathBuf> = HashMap::new();
+    let mut saved = 0;
+    for group in &groups {
+        let keep = group[0].strip_prefix(src_path).unwrap_or(&group[0]);
+        println!("{}", keep.display());
+        for duplicate in &group[1..] {
+            saved += fs::metadata(duplicate)?.len();
+            let duplicate = duplicate.strip_prefix(src_path).unwrap_or(duplicate);
+            println!("  duplicate {}", duplicate.display());
+            replaced.insert(duplicate.to_path_buf(), keep.to_path_buf());
+        }
+    }
+
+    let mut rewritten = 0;
+    for file in markdown_files(src_path) {
+        let relative = file.strip_prefix(src_path).unwrap_or(&file).to_path_buf();
+        let content = fs::read_to_string(&file)?;
+        let mut updated = content.clone();
+        for target in markdown_links(&content) {
+            let Some(keep) = replaced.get(&resolve_link(&relative, &target)) else { continue };
+            let link = relative_link(relative.parent().unwrap_or(Path::new("")), keep);
+            // Keep links that did not start with `./` in that form
+            let link = if target.starts_with("./") { link } else { link.trim_start_matches("./").to_string() };
+            updated = rewrite_links(&updated, &target, &link);
+        }
+        if updated != content {
+            rewritten += 1;
+            if dry_run {
+                println!("Would rewrite links in {}", relative.display());
+            } else {
+                println!("Rewrote links in {}", relative.display());
+                fs::write(&file, updated)?;
+            }
+        }
+    }
+    if !dry_run {
+        for duplicate in replaced.keys() {
+            fs::remove_file(src_path.join(duplicate))?;
+        }
+    }
+
+    let (remove, rewrite) = if dry_run { ("Would remove", "rewrite") } else { ("Removed", "rewrote") };
+    println!("{} {} duplicate attachments ({} bytes) and {} {} notes", remove, replaced.len(), saved, rewrite, rewritten);
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::date::Date;
+use crate::links::{markdown_files, markdown_links, resolve_link};
+use crate::summary::{self, Options, ScanStats};
+use crate::timings::Timings;
+
+/// Section directories the synthetic entries are spread over
+const SECTIONS: [&str; 4] = ["essay", "techlog", "work", "notes"];
+
+/// Write `count` dated entries under `src`, laid out like a real journal
+/// (`section/YYYY/MMDD.md` with a README per year). Each entry links to
+/// the previous one in its section so the link check has work to do.
+fn write_synthetic_tree(src: &Path, count: usize) -> io::Result<()> {
+    let first_day = Date::new(2000, 1, 1).map_or(0, |d| d.days());
+    let mut previous: [Option<PathBuf>; SECTIONS.len()] = Default::default();
+    for i in 0..count {
+        let slot = i % SECTIONS.len();
+        let date = Date::from_days(first_day + (i / SECTIONS.len()) as i64);
+        let year_dir = src.join(SECTIONS[slot]).join(date.year.to_string());
+        if !year_dir.exists() {
+            fs::create_dir_all(&year_dir)?;
+            fs::write(year_dir.join("README.md"), format!("# {}\n", date.year))?;
+        }
+        let path = year_dir.join(format!("{:02}{:02}.md", date.month, date.day));
+        let mut content = format!("# {}\n\nSynthetic entry {} for benchmarking.\n", date, i);
+        if let Some(prev) = &previous[slot] {
+            let target = prev.strip_prefix(src.join(SECTIONS[slot])).unwrap_or(prev);
+            content.push_str(&format!("\nPrevious: [entry](../{})\n", target.to_string_lossy().replace('\\', "/")));
+        }
+        fs::write(&path, content)?;
+        previous[slot] = Some(path);
+    }
+    Ok(())
+}
+
+/// Resolve every local link of every file, returning how many were
+/// checked and how many point at missing files
+fn check_links(files: &[PathBuf]) -> (usize, usize) {
+    let mut checked = 0;
+    let mut broken = 0;
+    for file in files {
+        let content = fs::read_to_string(file).unwrap_or_default();
+        for target in markdown_links(&content) {
+            checked += 1;
+            if !resolve_link(file, &target).exists() {
+                broken += 1;
+            }
+        }
+    }
+    (checked, broken)
+}
+
+/// `mdbook-daily bench --synthetic N`: time traversal, SUMMARY generation
+/// and link checking on a generated tree of N entries in a temp directory
+pub fn run(args: &[String]) -> io::Result<()> {
+    let mut count = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--synthetic" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--synthetic needs an entry count"))?;
+                count = Some(value.parse::<usize>()
+                    .map_err(|_| crate::usage_error(&format!("invalid entry count {:?}", value)))?);
+            }
+            _ => return Err(crate::usage_error(&format!("unknown bench argument {:?}", arg))),
+        }
+    }
+    let Some(count) = count else { return Err(crate::usage_error("bench needs --synthetic N")) };
+
+    let root = std::env::temp_dir().join(format!("mdbook-daily-bench-{}", std::process::id()));
+    let src = root.join("src");
+    fs::create_dir_all(&src)?;
+    let result = measure(&src, count);
+    fs::remove_dir_all(&root)?;
+    result
+}
+
+fn measure(src: &Path, count: usize) -> io::Result<()> {
+    let mut timings = Timings::new();
+    write_synthetic_tree(src, count)?;
+    timings.phase("create tree");
+    let files = markdown_files(src);
+    timings.phase("traversal");
+    let mut stats = ScanStats::default();
+    let summary = summary::generate_summary(src, &Options::default(), &mut stats)?;
+    timings.phase("generation");
+    let (checked, broken) = check_links(&files);
+    timings.phase("link check");
+
+    eprintln!("Entries:             {}", count);
+    eprintln!("Summary lines:       {}", summary.lines().count());
+    eprintln!("Links checked:       {} ({} broken)", checked, broken);
+    timings.report(&stats);
+    Ok(())
+}
@@ -0,0 +1,275 @@
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::config;
+use crate::generated;
+use crate::summary::{self, Filter, Options, ScanStats, Sorter};
+use crate::tree::BookTree;
+
+/// Generates a book's SUMMARY.md without going through the command line
+pub struct SummaryBuilder {
+    src_path: PathBuf,
+    options: Options,
+    /// Directory written by [`SummaryBuilder::from_files`], removed on drop
+    scratch: Option<PathBuf>,
+    /// Notes [`SummaryBuilder::from_files`] could not write, reported as
+    /// skipped by every build
+    left_out: Vec<(PathBuf, String)>,
+}
+
+/// Scratch directories created by this process so far
+static SCRATCH_DIRS: AtomicUsize = AtomicUsize::new(0);
+
+impl SummaryBuilder {
+    /// Builder for the notes under `src_path` with the default options
+    pub fn new(src_path: impl Into<PathBuf>) -> SummaryBuilder {
+        SummaryBuilder { src_path: src_path.into(), options: Options::default(), scratch: None, left_out: Vec::new() }
+    }
+
+    /// Builder for the book in `root`, with its daily.toml or book.toml
+    /// configuration applied and the notes in `root/src`
+    pub fn for_book(root: &Path) -> io::Result<SummaryBuilder> {
+        Ok(SummaryBuilder {
+            src_path: root.join("src"),
+            options: config::book_options(root)?,
+            scratch: None,
+            left_out: Vec::new(),
+        })
+    }
+
+    /// Builder for notes given as paths (relative to src) and contents,
+    /// with the default options. The scan reads from disk, so the notes
+    /// are first written to a scratch directory that lives as long as the
+    /// builder. Any path and content is accepted: components other than
+    /// plain names, such as `..` or a root, are dropped, and notes the
+    /// system refuses to write are left out and listed in
+    /// [`ScanStats::skipped`].
+    pub fn from_files<P, C>(files: impl IntoIterator<Item = (P, C)>) -> io::Result<SummaryBuilder>
+    where
+        P: AsRef<Path>,
+        C: AsRef<[u8]>,
+    {
+        let number = SCRATCH_DIRS.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!("mdbook-daily-{}-{}", process::id(), number));
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        fs::create_dir_all(&dir)?;
+        let mut builder = SummaryBuilder {
+            src_path: dir.clone(),
+            options: Options::default(),
+            scratch: Some(dir.clone()),
+            left_out: Vec::new(),
+        };
+        for (path, content) in files {
+            let relative: PathBuf = path.as_ref().components()
+                .filter_map(|component| match component {
+                    Component::Normal(name) => Some(name),
+                    _ => None,
+                })
+                .collect();
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+            let path = dir.join(relative);
+            let written = path.parent().map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&path, content.as_ref()));
+            if let Err(e) = written {
+                builder.left_out.push((path, format!("cannot be written: {}", e)));
+            }
+        }
+        Ok(builder)
+    }
+
+    /// Order the chapters of `section` (a directory of src, or `""` for
+    /// every section) with `sorter`
+    pub fn sorter(mut self, section: &str, sorter: impl Sorter + 'static) -> SummaryBuilder {
+        self.options.sorters.insert(section.to_string(), Box::new(sorter));
+        self
+    }
+
+    /// Only list the notes and directories `filter` includes
+    pub fn filter(mut self, filter: impl Filter + 'static) -> SummaryBuilder {
+        self.options.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Replace the generation options
+    pub fn options(mut self, options: Options) -> SummaryBuilder {
+        self.options = options;
+        self
+    }
+
+    /// The generated summary and what the scan came across
+    pub fn build_with_stats(&self) -> io::Result<(String, ScanStats)> {
+        let mut stats = ScanStats { skipped: self.left_out.clone(), ..ScanStats::default() };
+        let summary = summary::generate_summary(&self.src_path, &self.options, &mut stats)?;
+        Ok((summary, stats))
+    }
+
+    /// The generated summary as a string
+    pub fn build(&self) -> io::Result<String> {
+        Ok(self.build_with_stats()?.0)
+    }
+
+    /// The generated summary as chapters and parts, with entry dates and
+    /// tags
+    pub fn tree(&self) -> io::Result<BookTree> {
+        let (summary, stats) = self.build_with_stats()?;
+        Ok(BookTree::parse(&summary).annotated(&stats))
+    }
+
+    /// Write the summary to `path`, only replacing the managed region of
+    /// a hand-maintained file. Returns whether the file changed.
+    pub fn write_to(&self, path: &Path) -> io::Result<bool> {
+        let existing = fs::read_to_string(path).ok();
+        let content = summary::merge_managed(existing.as_deref(), self.build()?);
+        generated::write_if_changed(path, content.as_bytes(), self.options.line_ending)
+    }
+}
+
+impl Drop for SummaryBuilder {
+    fn drop(&mut self) {
+        if let Some(dir) = &self.scratch {
+            let _ = fs::remove_dir_all(dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::Candidate;
+
+    /// Small xorshift generator, so failures replay from the seed
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: usize) -> usize {
+            (self.next() % n as u64) as usize
+        }
+    }
+
+    /// Name pieces for arbitrary names, with separators, markup and
+    /// names the generator treats specially
+    const ODD_PIECES: &[&str] = &[
+        "a", "z", "0", "7", "-", "_", " ", ".", "[", "]", "(", ")", "#", "%", "\\", "é", "日", "\n", "/", "..",
+        "2026-01-15", "0115", "README", "SUMMARY",
+    ];
+
+    /// Name pieces of ordinary note names
+    const PLAIN_PIECES: &[&str] = &["a", "z", "0", "7", "-", "_", "é", "日", "/", "2026-01-15", "0115", "README"];
+
+    const CONTENT_PIECES: &[&str] = &[
+        "# ", "Title", "---\n", "title: \"x\"\n", "date: 2026-02-31\n", "draft: true\n", "\n", "[link](", ")", "[[",
+        "]]", "\u{feff}",
+    ];
+
+    fn arbitrary_name(rng: &mut Rng, pieces: &[&str]) -> String {
+        let mut name: String = (0..1 + rng.below(6)).map(|_| pieces[rng.below(pieces.len())]).collect();
+        if rng.below(4) != 0 {
+            name.push_str(".md");
+        }
+        name
+    }
+
+    fn arbitrary_content(rng: &mut Rng) -> Vec<u8> {
+        let mut content = Vec::new();
+        for _ in 0..rng.below(12) {
+            match rng.below(4) {
+                0 => content.push(rng.next() as u8),
+                _ => content.extend_from_slice(CONTENT_PIECES[rng.below(CONTENT_PIECES.len())].as_bytes()),
+            }
+        }
+        content
+    }
+
+    /// Builders for 200 trees of up to ten files named from `pieces`
+    fn arbitrary_trees(pieces: &'static [&'static str]) -> impl Iterator<Item = (u64, SummaryBuilder)> {
+        (1..=200u64).map(move |seed| {
+            let mut rng = Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+            let files: Vec<(String, Vec<u8>)> =
+                (0..rng.below(10)).map(|_| (arbitrary_name(&mut rng, pieces), arbitrary_content(&mut rng))).collect();
+            (seed, SummaryBuilder::from_files(files).unwrap())
+        })
+    }
+
+    #[test]
+    fn arbitrary_trees_build() {
+        for (seed, builder) in arbitrary_trees(ODD_PIECES) {
+            let summary = builder.build().unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+            assert_eq!(builder.build().unwrap(), summary, "seed {}: not deterministic", seed);
+            BookTree::parse(&summary);
+        }
+    }
+
+    #[test]
+    fn plain_trees_link_existing_files() {
+        for (seed, builder) in arbitrary_trees(PLAIN_PIECES) {
+            let summary = builder.build().unwrap();
+            for chapter in BookTree::parse(&summary).chapters() {
+                let Some(path) = chapter.path.as_ref().filter(|path| !path.is_empty()) else { continue };
+                assert!(builder.src_path.join(path).is_file(), "seed {}: missing {} in\n{}", seed, path, summary);
+            }
+        }
+    }
+
+    #[test]
+    fn custom_sorter_and_filter() {
+        let builder = SummaryBuilder::from_files([
+            ("notes/a.md", "# Alpha"),
+            ("notes/b.md", "# Beta"),
+            ("notes/c.md", "---\ntags: [private]\n---\n# Gamma"),
+            ("notes/hidden/d.md", "# Delta"),
+        ])
+        .unwrap()
+        .sorter("notes", |a: &Candidate, b: &Candidate| b.relative.cmp(a.relative))
+        .filter(|candidate: &Candidate| {
+            !candidate.relative.ends_with("hidden")
+                && !candidate.front_matter.is_some_and(|front| front.tags.iter().any(|tag| tag == "private"))
+        });
+        let summary = builder.build().unwrap();
+        let beta = summary.find("[Beta]").unwrap();
+        let alpha = summary.find("[Alpha]").unwrap();
+        assert!(beta < alpha, "{}", summary);
+        assert!(!summary.contains("Gamma") && !summary.contains("Delta") && !summary.contains("Hidden"), "{}", summary);
+    }
+
+    #[test]
+    fn odd_names_stay_inside_the_scratch_directory() {
+        let builder = SummaryBuilder::from_files([
+            ("notes/../../escape.md", "# Escape"),
+            ("/notes/absolute.md", "# Absolute"),
+            ("notes/unbalanced.md", "# Heading with [unbalanced bracket"),
+            ("", "# Nameless"),
+        ])
+        .unwrap();
+        let (summary, stats) = builder.build_with_stats().unwrap();
+        assert!(stats.skipped.is_empty());
+        assert!(summary.contains("- [Heading with \\[unbalanced bracket](./notes/unbalanced.md)"), "{}", summary);
+        assert!(builder.src_path.join("notes/escape.md").is_file());
+        assert!(builder.src_path.join("notes/absolute.md").is_file());
+        let dir = builder.src_path.clone();
+        drop(builder);
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn unwritable_notes_are_skipped() {
+        let builder = SummaryBuilder::from_files([("notes", "# File"), ("notes/inner.md", "# Inner")]).unwrap();
+        let (_, stats) = builder.build_with_stats().unwrap();
+        assert_eq!(stats.skipped.len(), 1);
+        assert!(stats.skipped[0].0.ends_with("notes/inner.md"), "{:?}", stats.skipped);
+        assert!(stats.check_strict(true).is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::generated;
+use crate::summary::{head_summary, read_head, Options};
+
+/// Cache file, next to the src directory
+pub const FILE_NAME: &str = ".mdbook-daily-cache.json";
+
+/// Fewest notes a reading thread is started for
+const BATCH: usize = 16;
+
+/// What an earlier run read from one note
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    /// Modification time as seconds and nanoseconds since the epoch
+    modified: (u64, u32),
+    size: u64,
+    /// `head_size` the head was read with
+    #[serde(default)]
+    head_size: u64,
+    /// The note's front matter block and first heading
+    head: String,
+}
+
+/// The front matter and first heading of notes read by earlier runs, by
+/// path relative to src. An entry is only used while its file keeps the
+/// same modification time and size.
+#[derive(Default)]
+pub struct HeadCache {
+    enabled: bool,
+    entries: HashMap<PathBuf, Entry>,
+    changed: bool,
+    /// Notes whose head came from the cache this run
+    pub hits: usize,
+}
+
+/// Where the cache of the book with `src_path` is kept
+pub fn cache_path(src_path: &Path) -> PathBuf {
+    src_path.parent().unwrap_or(Path::new(".")).join(FILE_NAME)
+}
+
+fn stamp(metadata: &fs::Metadata) -> Option<(u64, u32)> {
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((modified.as_secs(), modified.subsec_nanos()))
+}
+
+impl HeadCache {
+    /// The cache saved at `path`, empty when there is none or it cannot
+    /// be read
+    pub fn load(path: &Path) -> HeadCache {
+        let entries = fs::read(path).ok()
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default();
+        HeadCache { enabled: true, entries, changed: false, hits: 0 }
+    }
+
+    /// Save the cache to `path` if this run added to it, dropping the
+    /// entries of notes under `src_path` that are gone
+    pub fn save(&mut self, src_path: &Path, path: &Path) -> io::Result<()> {
+        if !self.enabled || !self.changed {
+            return Ok(());
+        }
+        self.entries.retain(|relative, _| src_path.join(relative).is_file());
+        let content = serde_json::to_vec(&self.entries).map_err(io::Error::other)?;
+        generated::write_atomic(path, &content)?;
+        self.changed = false;
+        Ok(())
+    }
+
+    /// The cached head of the note at `relative`, if it is still current
+    fn get(&self, relative: &Path, metadata: &fs::Metadata, head_size: u64) -> Option<&str> {
+        let entry = self.entries.get(relative)?;
+        let current = entry.size == metadata.len() && Some(entry.modified) == stamp(metadata) && entry.head_size == head_size;
+        current.then_some(entry.head.as_str())
+    }
+}
+
+/// Spaces reads of notes out to at most a number per second, across all
+/// the threads sharing it, for books on slow or shared storage
+pub struct ReadThrottle {
+    interval: Option<Duration>,
+    next: Mutex<Instant>,
+}
+
+impl ReadThrottle {
+    /// Throttle allowing `reads_per_second` reads, or any number for `None`
+    pub fn new(reads_per_second: Option<u32>) -> ReadThrottle {
+        let interval = reads_per_second.map(|n| Duration::from_secs(1) / n.max(1));
+        ReadThrottle { interval, next: Mutex::new(Instant::now()) }
+    }
+
+    /// Block until the next read is due
+    pub fn wait(&self) {
+        let Some(interval) = self.interval else { return };
+        let due = {
+            let mut next = self.next.lock().unwrap_or_else(PoisonError::into_inner);
+            let due = (*next).max(Instant::now());
+            *next = due + interval;
+            due
+        };
+        thread::sleep(due.saturating_duration_since(Instant::now()));
+    }
+}
+
+/// A note's head, or why it is not listed, and the entry to cache for it
+type Read = (Result<Vec<u8>, String>, Option<Entry>);
+
+/// Read the head of one note, from the cache when it is current. Also
+/// returns the entry to cache when the note had to be read.
+fn read_one(src_path: &Path, path: &Path, options: &Options, cache: &HeadCache, throttle: &ReadThrottle) -> Read {
+    let metadata = cache.enabled.then(|| fs::metadata(path).ok()).flatten();
+    let relative = path.strip_prefix(src_path).unwrap_or(path);
+    if let Some(metadata) = &metadata {
+        if let Some(head) = cache.get(relative, metadata, options.head_size).filter(|_| metadata.len() <= options.max_file_size) {
+            return (Ok(head.as_bytes().to_vec()), None);
+        }
+    }
+    throttle.wait();
+    let head = read_head(path, options);
+    let entry = match (&head, metadata.as_ref().and_then(stamp)) {
+        (Ok(head), Some(modified)) => Some(Entry {
+            modified,
+            size: metadata.map_or(0, |m| m.len()),
+            head_size: options.head_size,
+            head: head_summary(head),
+        }),
+        _ => None,
+    };
+    (head, entry)
+}
+
+/// The heads of `paths` (notes under `src_path`) in order, as
+/// [`read_head`] returns them. Notes are read on up to `options.jobs`
+/// threads when there are enough of them, at `options.reads_per_second`,
+/// and unchanged ones come from `cache`.
+pub fn read_heads(src_path: &Path, paths: &[PathBuf], options: &Options, cache: &mut HeadCache) -> Vec<Result<Vec<u8>, String>> {
+    let jobs = options.jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let threads = jobs.min(paths.len() / BATCH).max(1);
+    let throttle = &ReadThrottle::new(options.reads_per_second);
+    let results: Vec<Read> = if threads == 1 {
+        paths.iter().map(|path| read_one(src_path, path, options, cache, throttle)).collect()
+    } else {
+        let shared = &*cache;
+        let chunk = paths.len().div_ceil(threads);
+        thread::scope(|scope| {
+            let workers: Vec<_> = paths.chunks(chunk)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(|path| read_one(src_path, path, options, shared, throttle)).collect::<Vec<_>>()))
+                .collect();
+            workers.into_iter().flat_map(|worker| worker.join().unwrap_or_else(|e| std::panic::resume_unwind(e))).collect()
+        })
+    };
+    let mut heads = Vec::with_capacity(results.len());
+    for (path, (head, entry)) in paths.iter().zip(results) {
+        match entry {
+            Some(entry) => {
+                cache.entries.insert(path.strip_prefix(src_path).unwrap_or(path).to_path_buf(), entry);
+                cache.changed = true;
+            }
+            None if cache.enabled && head.is_ok() => cache.hits += 1,
+            None => {}
+        }
+        heads.push(head);
+    }
+    heads
+}
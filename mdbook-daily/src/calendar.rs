@@ -0,0 +1,75 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::date::{days_from_civil, Date};
+use crate::generated::{remove_stale, write_if_changed};
+use crate::link::relative_link;
+use crate::locale::Strings;
+use crate::summary::{Options, ScanStats};
+
+/// Directory (relative to src) holding the monthly calendar pages
+pub fn calendar_dir(options: &Options) -> PathBuf {
+    options.generated_dir.join("calendar")
+}
+
+/// One month's calendar page
+pub struct Month {
+    /// Page path relative to src
+    pub page: PathBuf,
+    pub title: String,
+    /// Dates and links of the month's entries
+    pub notes: Vec<(Date, String)>,
+}
+
+/// Calendar pages for the dated entries of one year directory, by month
+pub fn months(options: &Options, year_dir: &Path, dated: &[(Date, String, String)]) -> Vec<Month> {
+    let mut months: BTreeMap<(i64, u32), Vec<(Date, String)>> = BTreeMap::new();
+    for (date, _, link) in dated {
+        months.entry((date.year, date.month)).or_default().push((*date, link.clone()));
+    }
+    months.into_iter()
+        .map(|((year, month), notes)| {
+            let page = calendar_dir(options).join(year_dir).join(format!("{:04}-{:02}.md", year, month));
+            Month { page, title: options.strings.month_title(notes[0].0), notes }
+        })
+        .collect()
+}
+
+/// A month as a Monday-first table, each day linking to its first entry.
+/// `page` is relative to src.
+fn month_page(strings: &Strings, title: &str, notes: &[(Date, String)], page: &Path) -> String {
+    let page_dir = page.parent().unwrap_or(Path::new(""));
+    let first = notes[0].0;
+    let start = days_from_civil(first.year, first.month, 1);
+    let (next_year, next_month) = if first.month == 12 { (first.year + 1, 1) } else { (first.year, first.month + 1) };
+    let len = days_from_civil(next_year, next_month, 1) - start;
+    let mut content = format!("# {}\n\n| {} |\n|{}\n", title, strings.weekdays.join(" | "), " --- |".repeat(7));
+    let mut cells: Vec<String> = vec![String::new(); (Date::from_days(start).weekday() as usize + 6) % 7];
+    for day in 1..=len as u32 {
+        let cell = match notes.iter().find(|(date, _)| date.day == day) {
+            Some((_, link)) => format!("[{}]({})", day, relative_link(page_dir, Path::new(link.trim_start_matches("./")))),
+            None => day.to_string(),
+        };
+        cells.push(cell);
+    }
+    for week in cells.chunks(7) {
+        let mut row: Vec<&str> = week.iter().map(String::as_str).collect();
+        row.resize(7, "");
+        content.push_str(&format!("| {} |\n", row.join(" | ")));
+    }
+    content
+}
+
+/// Write the calendar pages listed while scanning, removing pages for
+/// months that no longer have entries. Returns the number of pages.
+pub fn write_calendar_pages(src_path: &Path, options: &Options, stats: &ScanStats) -> io::Result<usize> {
+    let mut keep = HashSet::new();
+    for month in &stats.calendars {
+        let path = src_path.join(&month.page);
+        write_if_changed(&path, month_page(&options.strings, &month.title, &month.notes, &month.page).as_bytes(), options.line_ending)?;
+        keep.insert(path);
+    }
+    remove_stale(&src_path.join(calendar_dir(options)), &keep)?;
+    Ok(stats.calendars.len())
+}
@@ -0,0 +1,105 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::config::{self, Config};
+use crate::date::{Date, DateTime};
+use crate::new::{create_entry, pick_section, template};
+use crate::summary::ScanStats;
+
+/// Fill in a capture template's `{{text}}`, `{{time}}` (HH:MM, UTC),
+/// `{{date}}` and `{{weekday}}` placeholders
+fn render(template: &str, text: &str, now: &DateTime) -> String {
+    let today = Date { year: now.year, month: now.month, day: now.day };
+    template.replace("{{text}}", text)
+        .replace("{{time}}", &format!("{:02}:{:02}", now.hour, now.minute))
+        .replace("{{date}}", &today.to_string())
+        .replace("{{weekday}}", today.weekday_name())
+}
+
+/// Whether `line` is a plain `- HH:MM text` capture
+fn is_plain_capture(line: &str) -> bool {
+    let bytes = line.as_bytes();
+    line.starts_with("- ")
+        && bytes.len() > 8
+        && bytes[2..4].iter().chain(&bytes[5..7]).all(u8::is_ascii_digit)
+        && bytes[4] == b':'
+        && bytes[7] == b' '
+}
+
+/// `content` with `block` appended, set off by a blank line unless it is
+/// a plain capture following another one
+fn append(content: &str, block: &str, plain: bool) -> String {
+    let separate = !(plain && content.lines().last().is_some_and(is_plain_capture));
+    let mut updated = content.to_string();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    if separate && !updated.is_empty() && !updated.ends_with("\n\n") {
+        updated.push('\n');
+    }
+    updated.push_str(block.trim_end());
+    updated.push('\n');
+    updated
+}
+
+/// `mdbook-daily capture [--type NAME] [--section NAME] TEXT...`: append
+/// TEXT to today's note as `- HH:MM TEXT`, or as the block of a named
+/// `capture-templates` entry, creating the note if there is none yet
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut kind = None;
+    let mut section = None;
+    let mut words = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--type" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--type needs a template name"))?;
+                kind = Some(value.clone());
+            }
+            "--section" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--section needs a directory"))?;
+                section = Some(value.clone());
+            }
+            _ => words.push(arg.as_str()),
+        }
+    }
+    if words.is_empty() {
+        return Err(crate::usage_error("capture needs some text"));
+    }
+
+    let config = Config::load(Path::new("."))?;
+    let capture_template = match &kind {
+        Some(kind) => Some(config.capture_templates.get(kind).cloned().ok_or_else(|| {
+            let mut names: Vec<&str> = config.capture_templates.keys().map(String::as_str).collect();
+            names.sort();
+            crate::usage_error(&match names.as_slice() {
+                [] => format!("no capture template {:?}, add one under [capture-templates]", kind),
+                _ => format!("no capture template {:?}, expected one of {}", kind, names.join(", ")),
+            })
+        })?),
+        None => None,
+    };
+    let options = config::book_options(Path::new("."))?;
+    let section = pick_section(src_path, &options, section, "capture")?;
+
+    let now = DateTime::now();
+    let today = Date::today();
+    let path = src_path.join(&section).join(today.entry_path());
+    let created = !path.exists();
+    if created {
+        create_entry(&path, today, &template(&config, None, &section)?)?;
+    }
+    let text = words.join(" ");
+    let block = match &capture_template {
+        Some(capture_template) => render(capture_template, &text, &now),
+        None => render("- {{time}} {{text}}", &text, &now),
+    };
+    let content = fs::read_to_string(&path)?;
+    fs::write(&path, append(&content, &block, capture_template.is_none()))?;
+    if created {
+        crate::write_summary(src_path, &options, &mut ScanStats::default())?;
+    }
+    println!("{}", path.display());
+    Ok(())
+}
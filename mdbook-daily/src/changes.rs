@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config;
+use crate::git;
+use crate::link::relative_link;
+use crate::locale::Strings;
+use crate::summary::{escape_title, SummaryEntry};
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Added,
+    Modified,
+    Removed,
+}
+
+impl ChangeKind {
+    fn label(self) -> &'static str {
+        match self {
+            ChangeKind::Added => "Added",
+            ChangeKind::Modified => "Modified",
+            ChangeKind::Removed => "Removed",
+        }
+    }
+}
+
+/// A markdown file changed since a revision, relative to src
+pub struct FileChange {
+    pub kind: ChangeKind,
+    pub path: PathBuf,
+}
+
+/// Markdown files under src added, modified or removed since `rev`,
+/// including uncommitted and untracked files
+pub fn changes_since(src_path: &Path, rev: &str, generated_dir: &Path) -> io::Result<Vec<FileChange>> {
+    let mut changes = Vec::new();
+    let diff = git::run(src_path, &["diff", "--name-status", "-M", "--relative", rev, "--", "."])?;
+    for line in diff.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let (kind, path) = match (fields[0].chars().next(), fields.as_slice()) {
+            (Some('A'), [_, path]) => (ChangeKind::Added, *path),
+            (Some('D'), [_, path]) => (ChangeKind::Removed, *path),
+            (Some('R'), [_, from, to]) => {
+                changes.push(FileChange { kind: ChangeKind::Removed, path: PathBuf::from(from) });
+                (ChangeKind::Added, *to)
+            }
+            (Some(_), [_, path]) => (ChangeKind::Modified, *path),
+            _ => continue,
+        };
+        changes.push(FileChange { kind, path: PathBuf::from(path) });
+    }
+    for path in git::run(src_path, &["ls-files", "--others", "--exclude-standard"])?.lines() {
+        changes.push(FileChange { kind: ChangeKind::Added, path: PathBuf::from(path) });
+    }
+
+    changes.retain(|c| {
+        c.path.extension().is_some_and(|e| e == "md")
+            && c.path != Path::new("SUMMARY.md")
+            && !c.path.starts_with(generated_dir)
+    });
+    changes.sort_by(|a, b| (a.kind, &a.path).cmp(&(b.kind, &b.path)));
+    Ok(changes)
+}
+
+/// Print the changes grouped by kind
+pub fn print_report(rev: &str, changes: &[FileChange]) {
+    if changes.is_empty() {
+        println!("No entries changed since {}", rev);
+        return;
+    }
+    println!("Entries changed since {}:", rev);
+    for kind in [ChangeKind::Added, ChangeKind::Modified, ChangeKind::Removed] {
+        let paths: Vec<_> = changes.iter().filter(|c| c.kind == kind).collect();
+        if paths.is_empty() {
+            continue;
+        }
+        println!("{} ({}):", kind.label(), paths.len());
+        for change in paths {
+            println!("  {}", change.path.display());
+        }
+    }
+}
+
+/// Write a page listing the changes, linking entries still in the book
+/// and naming their authors. `page` is relative to src.
+pub fn write_changes_page(
+    src_path: &Path,
+    strings: &Strings,
+    rev: &str,
+    changes: &[FileChange],
+    entries: &[SummaryEntry],
+    page: &Path,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let titles: HashMap<PathBuf, &str> = entries.iter()
+        .filter_map(|e| {
+            let link = e.path.as_deref()?;
+            Some((PathBuf::from(link.strip_prefix("./").unwrap_or(link)), e.title.as_str()))
+        })
+        .collect();
+    let page_dir = page.parent().unwrap_or(Path::new(""));
+    let authors = git::authors(src_path);
+
+    writeln!(out, "# {}", strings.changes_since.replace("{rev}", rev))?;
+    for kind in [ChangeKind::Added, ChangeKind::Modified, ChangeKind::Removed] {
+        let group: Vec<_> = changes.iter().filter(|c| c.kind == kind).collect();
+        if group.is_empty() {
+            continue;
+        }
+        writeln!(out)?;
+        let heading = match kind {
+            ChangeKind::Added => &strings.added,
+            ChangeKind::Modified => &strings.modified,
+            ChangeKind::Removed => &strings.removed,
+        };
+        writeln!(out, "## {}", heading)?;
+        writeln!(out)?;
+        for change in group {
+            match titles.get(&change.path) {
+                Some(title) => write!(out, "- [{}]({})", escape_title(title), relative_link(page_dir, &change.path))?,
+                None => write!(out, "- `{}`", change.path.display())?,
+            }
+            match authors.get(&change.path) {
+                Some(author) => writeln!(out, " {}", strings.by_author.replace("{author}", author))?,
+                None => writeln!(out)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `mdbook-daily changes REV`: report entries changed since a revision
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let [rev] = args else {
+        return Err(crate::usage_error("changes needs exactly one git revision"));
+    };
+    let changes = changes_since(src_path, rev, &config::book_options(Path::new("."))?.generated_dir)?;
+    print_report(rev, &changes);
+    Ok(())
+}
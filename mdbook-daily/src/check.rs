@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::cache::ReadThrottle;
+use crate::config;
+use crate::links::{markdown_files, markdown_links, resolve_link};
+use crate::summary::parse_entries;
+
+/// One problem found by `check`
+#[derive(Serialize)]
+pub struct Problem {
+    /// `missing-entry`, `orphan` or `broken-link`
+    pub kind: &'static str,
+    /// File the problem is in, relative to the book root
+    pub file: PathBuf,
+    /// 1-based line, when the problem is on one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// The link target that does not resolve
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+impl Problem {
+    fn message(&self) -> String {
+        let target = self.target.as_deref().unwrap_or_default();
+        match self.kind {
+            "missing-entry" => format!("SUMMARY.md lists {}, which does not exist", target),
+            "orphan" => "not reachable from SUMMARY.md".to_string(),
+            _ => format!("link to {}, which does not exist", target),
+        }
+    }
+}
+
+/// Whether a src-relative link target exists. Links to a chapter's
+/// rendered `.html` page count if the `.md` source exists.
+fn exists(src_path: &Path, relative: &Path) -> bool {
+    src_path.join(relative).exists()
+        || (relative.extension().is_some_and(|e| e == "html") && src_path.join(relative.with_extension("md")).is_file())
+}
+
+/// Check the book in `src_path`: SUMMARY.md entries pointing at missing
+/// files, markdown files it does not reach (besides excluded ones) and
+/// relative links in its chapters to missing files
+pub fn check_book(src_path: &Path) -> io::Result<Vec<Problem>> {
+    let options = config::book_options(Path::new("."))?;
+    let summary_path = src_path.join("SUMMARY.md");
+    let summary = fs::read_to_string(&summary_path)?;
+    let mut problems = Vec::new();
+    let mut chapters = Vec::new();
+    for entry in parse_entries(&summary) {
+        let Some(link) = entry.path.as_deref().filter(|l| !l.is_empty()) else { continue };
+        let relative = resolve_link(Path::new("SUMMARY.md"), link);
+        if src_path.join(&relative).is_file() {
+            chapters.push(relative);
+        } else {
+            problems.push(Problem { kind: "missing-entry", file: summary_path.clone(), line: Some(entry.line + 1), target: Some(link.to_string()) });
+        }
+    }
+
+    let reachable: HashSet<&PathBuf> = chapters.iter().collect();
+    for path in markdown_files(src_path) {
+        let relative = path.strip_prefix(src_path).unwrap_or(&path).to_path_buf();
+        if !reachable.contains(&relative) && !options.is_excluded(&relative, false) {
+            problems.push(Problem { kind: "orphan", file: path, line: None, target: None });
+        }
+    }
+
+    let throttle = ReadThrottle::new(options.reads_per_second);
+    for relative in &chapters {
+        let path = src_path.join(relative);
+        throttle.wait();
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+        let mut in_fence = false;
+        for (i, line) in content.lines().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            if in_fence {
+                continue;
+            }
+            for target in markdown_links(line) {
+                // Site-absolute links depend on where the book is served
+                if target.starts_with('/') || exists(src_path, &resolve_link(relative, &target)) {
+                    continue;
+                }
+                problems.push(Problem { kind: "broken-link", file: path.clone(), line: Some(i + 1), target: Some(target) });
+            }
+        }
+    }
+    Ok(problems)
+}
+
+/// `mdbook-daily check [--format text|json]`: report broken SUMMARY.md
+/// entries, orphaned pages and broken relative links, exiting with 1 if
+/// there are any
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let json = match args {
+        [] => false,
+        [flag, format] if flag == "--format" => match format.as_str() {
+            "text" => false,
+            "json" => true,
+            _ => return Err(crate::usage_error(&format!("invalid format {:?}, expected text or json", format))),
+        },
+        _ => return Err(crate::usage_error("check only accepts --format text|json")),
+    };
+    let problems = check_book(src_path)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&problems).map_err(io::Error::other)?);
+    } else {
+        for problem in &problems {
+            let line = problem.line.map(|l| format!(":{}", l)).unwrap_or_default();
+            println!("{}{}: {}: {}", problem.file.display(), line, problem.kind, problem.message());
+        }
+        let count = |kind: &str| problems.iter().filter(|p| p.kind == kind).count();
+        println!("{} missing entries, {} orphaned pages, {} broken links",
+            count("missing-entry"), count("orphan"), count("broken-link"));
+    }
+    if !problems.is_empty() {
+        return Err(crate::reported_failure());
+    }
+    Ok(())
+}
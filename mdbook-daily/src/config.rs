@@ -0,0 +1,626 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use toml::value::{Table, Value};
+
+use crate::date::{Date, DatePattern};
+use crate::eol::LineEnding;
+use crate::ignore::{self, Ignore};
+use crate::locale::Strings;
+use crate::rollup::Period;
+use crate::titles::TitleTransform;
+use crate::related::RelatedBy;
+use crate::wikilinks::Unresolved;
+use crate::{new, plan};
+use crate::summary::{DateSource, Disambiguator, Options, SectionLayout, SortOrder, SymlinkPolicy};
+
+/// Standalone configuration file in the book root, read instead of
+/// book.toml when present
+pub const FILE_NAME: &str = "daily.toml";
+
+/// Layout settings read from daily.toml or the `[preprocessor.daily]`
+/// table of book.toml. Keys the layout does not use, such as the
+/// preprocessor's own flags, are ignored.
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    /// Chapters (relative to src) listed before the first part, such as
+    /// `["aboutMe.md", "now.md"]`. Missing ones are skipped with a warning.
+    pub pinned: Option<Vec<PathBuf>>,
+    /// Write `pinned` as unnumbered prefix chapters
+    pub pinned_unnumbered: Option<bool>,
+    /// Chapters (relative to src) listed last as unnumbered suffix
+    /// chapters, such as `["colophon.md"]`
+    pub suffix: Vec<PathBuf>,
+    /// `part`, `separator` and `numbered` settings by section directory
+    /// name
+    pub section_layout: HashMap<String, SectionLayout>,
+    /// Order of the chapters inside each part: `asc`, `desc`, `mtime` or
+    /// `frontmatter-date`
+    pub sort: Option<SortOrder>,
+    /// Per-section sort orders, by section directory name
+    pub section_sort: HashMap<String, SortOrder>,
+    /// How entries of a section that share a title are told apart:
+    /// `date`, `parent` or `none`
+    pub disambiguate: Option<Disambiguator>,
+    /// Per-section `disambiguate` settings, by section directory name
+    pub section_disambiguate: HashMap<String, Disambiguator>,
+    /// Gitignore-style patterns (relative to src) for files and
+    /// directories left out of the summary, e.g. `drafts/**`,
+    /// `*.draft.md` or `templates/`
+    pub exclude: Vec<String>,
+    /// Spaces per nesting level
+    pub indent: Option<usize>,
+    /// `follow` or `skip` symlinked files and directories
+    pub symlinks: Option<SymlinkPolicy>,
+    /// Directory levels listed below each section
+    pub max_depth: Option<usize>,
+    /// List notes deeper than `max-depth` at the limit instead of leaving
+    /// them out
+    pub flatten_deep: Option<bool>,
+    /// Part titles by section directory name
+    pub section_titles: HashMap<String, String>,
+    /// Markdown file (relative to the book root) that `new` fills in,
+    /// instead of `templates/daily.md`
+    pub template: Option<PathBuf>,
+    /// Named templates (`templates/NAME.md`) by section directory name
+    pub section_templates: HashMap<String, String>,
+    /// How titles are derived from names: `separators`, `case`
+    /// (`first`, `words` or `none`), `acronyms` and `dates`
+    pub title_transform: Option<TitleTransform>,
+    /// Language of the generated headings and pages: `en`, `de`, `ja`,
+    /// `zh` or the path of a TOML locale file
+    pub locale: Option<String>,
+    /// Blocks `capture --type NAME` appends to today's note, by name,
+    /// with `{{text}}`, `{{time}}`, `{{date}}` and `{{weekday}}`
+    /// placeholders
+    pub capture_templates: HashMap<String, String>,
+    /// strftime-like patterns for dates in entry file names, tried in
+    /// order before the built-in formats, e.g. `%d%b%Y` or `%Gw%Vd%u`
+    pub date_patterns: Vec<String>,
+    /// Date notes without a date in their name or front matter by the
+    /// commit that added them
+    pub git_dates: Option<bool>,
+    /// Where entry dates come from, in order of priority: `front-matter`,
+    /// `filename`, `git` and `mtime`
+    pub date_sources: Option<Vec<DateSource>>,
+    /// Per-section `date-sources`, by section directory name
+    pub section_date_sources: HashMap<String, Vec<DateSource>>,
+    /// Days an entry's date sources may disagree by before a warning
+    pub date_tolerance: Option<u32>,
+    /// Message of the `update --commit` commit, with `{date}` and
+    /// `{count}` placeholders
+    pub commit_message: Option<String>,
+    /// Cache the front matter and first heading of notes in
+    /// `.mdbook-daily-cache.json` next to src, keyed by modification time
+    pub cache: Option<bool>,
+    /// Nest dated entries under a generated page per `week` or `month`
+    pub rollup: Option<Period>,
+    /// Tasks `plan` schedules every week, by weekday (`mon` to `sun`) or
+    /// `weekly` for ones without a fixed day
+    pub recurring: HashMap<String, Vec<String>>,
+    /// Values of the `status` front matter key, in the order `promote`
+    /// advances a note through them
+    pub statuses: Option<Vec<String>>,
+    /// Statuses whose notes are left out of the summary, such as `seed`
+    pub hidden_statuses: Vec<String>,
+}
+
+impl Config {
+    /// Parse the `[preprocessor.daily]` table of book.toml
+    pub fn from_table(table: &Table) -> Result<Config, toml::de::Error> {
+        Value::Table(table.clone()).try_into()
+    }
+
+    /// Read daily.toml in `root` if there is one, otherwise the
+    /// `[preprocessor.daily]` table of `root/book.toml`, adding the
+    /// patterns of `root/.mdbookdailyignore` to `exclude`
+    pub fn load(root: &Path) -> io::Result<Config> {
+        let mut config = Config::load_settings(root)?;
+        config.exclude.extend(ignore::read_file(root)?);
+        Ok(config)
+    }
+
+    fn load_settings(root: &Path) -> io::Result<Config> {
+        let Some(source) = Source::read(root)? else { return Ok(Config::default()) };
+        let problems = check_table(root, &source.table, source.standalone);
+        if !problems.is_empty() {
+            let lines: Vec<String> = problems.iter().map(|p| format!("{}: {}", source.path.display(), p)).collect();
+            return Err(io::Error::new(io::ErrorKind::InvalidData, lines.join("\n")));
+        }
+        Config::from_table(&source.table)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", source.path.display(), e)))
+    }
+
+    /// These settings with every unset one at its default
+    fn effective(mut self) -> Config {
+        let defaults = Options::default();
+        self.pinned.get_or_insert(defaults.pinned);
+        self.pinned_unnumbered.get_or_insert(defaults.pinned_unnumbered);
+        self.sort.get_or_insert(defaults.sort);
+        self.disambiguate.get_or_insert(defaults.disambiguate);
+        self.indent.get_or_insert(defaults.indent_width);
+        self.symlinks.get_or_insert(defaults.symlinks);
+        self.flatten_deep.get_or_insert(defaults.flatten_deep);
+        self.git_dates.get_or_insert(defaults.git_dates);
+        self.date_sources.get_or_insert(defaults.date_sources);
+        if let Some(tolerance) = defaults.date_tolerance {
+            self.date_tolerance.get_or_insert(tolerance);
+        }
+        self.commit_message.get_or_insert(defaults.commit_message);
+        self.cache.get_or_insert(defaults.head_cache);
+        self.title_transform.get_or_insert(defaults.title_transform);
+        self.statuses.get_or_insert(defaults.statuses);
+        self.locale.get_or_insert_with(|| "en".to_string());
+        self
+    }
+
+    /// Override the defaults in `options` with the configured settings
+    pub fn apply(self, options: &mut Options) -> io::Result<()> {
+        if let Some(pinned) = self.pinned {
+            options.pinned = pinned;
+        }
+        if let Some(pinned_unnumbered) = self.pinned_unnumbered {
+            options.pinned_unnumbered = pinned_unnumbered;
+        }
+        options.suffix = self.suffix;
+        options.section_layout = self.section_layout;
+        if let Some(sort) = self.sort {
+            options.sort = sort;
+        }
+        if let Some(disambiguate) = self.disambiguate {
+            options.disambiguate = disambiguate;
+        }
+        if let Some(indent) = self.indent {
+            if indent == 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "indent must be at least 1"));
+            }
+            options.indent_width = indent;
+        }
+        if let Some(symlinks) = self.symlinks {
+            options.symlinks = symlinks;
+        }
+        options.max_depth = self.max_depth;
+        if let Some(flatten_deep) = self.flatten_deep {
+            options.flatten_deep = flatten_deep;
+        }
+        options.section_sort = self.section_sort;
+        options.section_disambiguate = self.section_disambiguate;
+        options.exclude = Ignore::new(&self.exclude);
+        options.section_titles = self.section_titles;
+        if let Some(title_transform) = self.title_transform {
+            options.title_transform = title_transform;
+        }
+        if let Some(locale) = &self.locale {
+            options.strings = Strings::load(locale)?;
+        }
+        for pattern in &self.date_patterns {
+            let pattern = DatePattern::new(pattern).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidInput, format!("date pattern {:?}: {}", pattern, e))
+            })?;
+            options.date_parsers.push(Box::new(pattern));
+        }
+        if let Some(git_dates) = self.git_dates {
+            options.git_dates = git_dates;
+        }
+        if let Some(sources) = self.date_sources {
+            options.date_sources = sources;
+        }
+        options.section_date_sources = self.section_date_sources;
+        if self.date_tolerance.is_some() {
+            options.date_tolerance = self.date_tolerance;
+        }
+        if let Some(message) = self.commit_message {
+            options.commit_message = message;
+        }
+        if let Some(cache) = self.cache {
+            options.head_cache = cache;
+        }
+        options.rollup = self.rollup;
+        if let Some(statuses) = self.statuses {
+            if statuses.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "statuses needs at least one value"));
+            }
+            options.statuses = statuses;
+        }
+        options.hidden_statuses = self.hidden_statuses;
+        Ok(())
+    }
+}
+
+/// The settings table that [`Config::load`] reads
+struct Source {
+    path: PathBuf,
+    table: Table,
+    /// daily.toml rather than book.toml's `[preprocessor.daily]`
+    standalone: bool,
+}
+
+impl Source {
+    fn read(root: &Path) -> io::Result<Option<Source>> {
+        let invalid = |path: &Path, e: toml::de::Error| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))
+        };
+        let path = root.join(FILE_NAME);
+        if path.exists() {
+            let table = toml::from_str(&fs::read_to_string(&path)?).map_err(|e| invalid(&path, e))?;
+            return Ok(Some(Source { path, table, standalone: true }));
+        }
+        let path = root.join("book.toml");
+        let Ok(content) = fs::read_to_string(&path) else { return Ok(None) };
+        let book: Table = toml::from_str(&content).map_err(|e| invalid(&path, e))?;
+        let table = book.get("preprocessor")
+            .and_then(|p| p.get("daily"))
+            .and_then(Value::as_table)
+            .cloned();
+        Ok(table.map(|table| Source { path, table, standalone: false }))
+    }
+}
+
+/// Keys read into [`Config`]
+const LAYOUT_KEYS: [&str; 30] = [
+    "pinned", "pinned-unnumbered", "suffix", "section-layout", "sort", "section-sort", "disambiguate", "section-disambiguate", "exclude", "indent",
+    "section-titles", "template", "section-templates", "locale", "recurring", "capture-templates", "title-transform",
+    "date-patterns", "symlinks", "max-depth", "flatten-deep", "git-dates", "commit-message", "cache", "rollup",
+    "date-sources", "section-date-sources", "date-tolerance", "statuses", "hidden-statuses",
+];
+
+/// Keys of `[preprocessor.daily]` that mdBook or the preprocessor itself
+/// reads. They have no effect in daily.toml.
+const PREPROCESSOR_KEYS: [&str; 40] = [
+    "command", "before", "after", "renderers", "optional",
+    "year-separators", "archive-page", "acronym-index", "team", "time-log", "tag-index", "tag-pages", "series-pages",
+    "calendar", "stats-page", "include-drafts", "section-markers", "missing-image-placeholders", "entry-navigation", "hydrate-placeholders",
+    "series-navigation",
+    "changes-since", "site-url", "line-endings", "generated-dir", "exclude-older-than", "max-file-size",
+    "plain-titles", "wiki-links", "recent-entries", "strict", "feed-entries", "feed-html", "feed-sections", "on-this-day",
+    "jobs", "reads-per-second", "head-size", "related-notes", "related-by",
+];
+
+/// `value` as the generated pages directory. Stale generated pages are
+/// removed from it, so only a relative path of plain names is accepted;
+/// `""`, `.`, `..` and absolute paths would reach the notes themselves.
+pub fn generated_dir(value: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(value);
+    if path.as_os_str().is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(format!("generated-dir {:?} must be a directory inside src, such as \"_generated\"", value));
+    }
+    Ok(path)
+}
+
+/// Number of single-character edits turning `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (diagonal + usize::from(ca != *cb)).min(row[j] + 1).min(above + 1);
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// The known key closest to a misspelled one, if any is close
+fn suggest<'a>(key: &str, known: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    known.map(|k| (edit_distance(key, k), k))
+        .filter(|(distance, k)| *distance <= (k.len() / 3).max(2))
+        .min()
+        .map(|(_, k)| k)
+}
+
+/// Problems with a settings table, each naming its key: unknown keys,
+/// wrong types and values the tool cannot use. `standalone` is true for
+/// daily.toml, which has no preprocessor keys. Paths resolve in `root`.
+pub fn check_table(root: &Path, table: &Table, standalone: bool) -> Vec<String> {
+    let mut problems = Vec::new();
+    let known = || LAYOUT_KEYS.iter().chain(if standalone { &[][..] } else { &PREPROCESSOR_KEYS[..] }).copied();
+    let mut keys: Vec<&String> = table.keys().collect();
+    keys.sort();
+    for key in keys {
+        if known().any(|k| k == key) {
+            continue;
+        }
+        if standalone && PREPROCESSOR_KEYS.contains(&key.as_str()) {
+            problems.push(format!("`{}` is only read from [preprocessor.daily] in book.toml", key));
+            continue;
+        }
+        problems.push(match suggest(key, known()) {
+            Some(similar) => format!("unknown key `{}`, did you mean `{}`?", key, similar),
+            None => format!("unknown key `{}`", key),
+        });
+    }
+
+    if !standalone {
+        check_preprocessor_keys(table, &mut problems);
+    }
+    let config = match Config::from_table(table) {
+        Ok(config) => config,
+        Err(e) => {
+            problems.push(e.to_string());
+            return problems;
+        }
+    };
+    for pattern in &config.exclude {
+        if let Some(problem) = ignore::pattern_problem(pattern) {
+            problems.push(format!("exclude pattern {:?}: {}", pattern, problem));
+        }
+    }
+    for pattern in &config.date_patterns {
+        if let Err(e) = DatePattern::new(pattern) {
+            problems.push(format!("date pattern {:?}: {}", pattern, e));
+        }
+    }
+    if config.date_sources.as_ref().is_some_and(Vec::is_empty) || config.section_date_sources.values().any(Vec::is_empty) {
+        problems.push("date sources must list at least one of front-matter, filename, git or mtime".to_string());
+    }
+    if config.indent == Some(0) {
+        problems.push("indent must be at least 1".to_string());
+    }
+    if let Some(locale) = &config.locale {
+        let path = root.join(locale);
+        let locale = if path.is_file() { path.to_string_lossy().into_owned() } else { locale.clone() };
+        if let Err(e) = Strings::load(&locale) {
+            problems.push(e.to_string());
+        }
+    }
+    if let Some(template) = config.template.as_ref().filter(|t| !root.join(t).is_file()) {
+        problems.push(format!("template {} does not exist", template.display()));
+    }
+    let mut section_templates: Vec<_> = config.section_templates.iter().collect();
+    section_templates.sort();
+    for (section, name) in section_templates {
+        let path = Path::new(new::TEMPLATES_DIR).join(format!("{}.md", name));
+        if !root.join(&path).is_file() {
+            problems.push(format!("section-templates.{}: {} does not exist", section, path.display()));
+        }
+    }
+    let mut days: Vec<&String> = config.recurring.keys().collect();
+    days.sort();
+    for day in days.into_iter().filter(|d| *d != "weekly" && !plan::WEEKDAYS.contains(&d.as_str())) {
+        problems.push(format!("unknown recurring day {:?}, expected weekly or one of {}", day, plan::WEEKDAYS.join(", ")));
+    }
+    problems
+}
+
+/// A size setting: a byte count, or a string such as `"1M"`. Negative
+/// counts are refused rather than wrapping around.
+fn byte_count(value: &Value) -> Option<u64> {
+    match value {
+        Value::Integer(n) => u64::try_from(*n).ok(),
+        _ => value.as_str().and_then(crate::parse_size),
+    }
+}
+
+/// Type and value checks for the keys only the preprocessor reads
+fn check_preprocessor_keys(table: &Table, problems: &mut Vec<String>) {
+    for (key, value) in table {
+        let expected = match key.as_str() {
+            "year-separators" | "archive-page" | "acronym-index" | "team" | "time-log" | "tag-index" | "tag-pages" | "series-pages"
+            | "calendar" | "stats-page" | "include-drafts" | "section-markers" | "missing-image-placeholders" | "entry-navigation"
+            | "series-navigation" | "hydrate-placeholders" | "strict" | "feed-html" | "feed-sections" | "on-this-day" | "optional" => (!value.is_bool()).then_some("true or false"),
+            "command" | "changes-since" | "site-url" => (!value.is_str()).then_some("a string"),
+            "generated-dir" => value.as_str()
+                .is_none_or(|dir| generated_dir(dir).is_err())
+                .then_some("a directory inside src, such as \"_generated\""),
+            "line-endings" => (!matches!(value.as_str(), Some("lf" | "crlf"))).then_some("\"lf\" or \"crlf\""),
+            "wiki-links" => value.as_str()
+                .and_then(Unresolved::parse)
+                .is_none()
+                .then_some("\"keep\", \"strip\" or \"warn\""),
+            "exclude-older-than" => value.as_str()
+                .and_then(|age| Date::today().minus_age(age))
+                .is_none()
+                .then_some("an age such as \"90d\" or \"2y\""),
+            "max-file-size" | "head-size" => byte_count(value)
+                .is_none()
+                .then_some("a byte count such as 1048576 or \"1M\""),
+            "recent-entries" | "feed-entries" | "jobs" | "reads-per-second" | "related-notes" => value.as_integer().is_none_or(|n| n <= 0).then_some("a positive number"),
+            "related-by" => value.as_array()
+                .is_none_or(|items| items.is_empty() || !items.iter().all(|item| item.as_str().and_then(RelatedBy::parse).is_some()))
+                .then_some("a list of \"tags\", \"links\" and \"titles\""),
+            "plain-titles" | "before" | "after" | "renderers" => value.as_array()
+                .is_none_or(|items| !items.iter().all(Value::is_str))
+                .then_some("a list of strings"),
+            _ => None,
+        };
+        if let Some(expected) = expected {
+            problems.push(format!("`{}` is {}, expected {}", key, value, expected));
+        }
+    }
+}
+
+/// `mdbook-daily config check | print [--effective]`: report problems in
+/// the settings, or print them as TOML. `--effective` fills in the
+/// defaults and the patterns of .mdbookdailyignore.
+pub fn run(args: &[String]) -> io::Result<()> {
+    let root = Path::new(".");
+    let source = Source::read(root)?;
+    match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["check"] => {
+            let Some(source) = source else {
+                println!("No {} or [preprocessor.daily] in book.toml, using the defaults", FILE_NAME);
+                return Ok(());
+            };
+            let problems = check_table(root, &source.table, source.standalone);
+            for problem in &problems {
+                println!("{}: {}", source.path.display(), problem);
+            }
+            if !problems.is_empty() {
+                return Err(crate::reported_failure());
+            }
+            println!("{}: no problems found", source.path.display());
+        }
+        ["print"] => {
+            let table = source.map(|s| s.table).unwrap_or_default();
+            print!("{}", toml::to_string(&Value::Table(table)).unwrap_or_default());
+        }
+        ["print", "--effective"] => {
+            let effective = Config::load(root)?.effective();
+            let mut table = match Value::try_from(effective) {
+                Ok(Value::Table(table)) => table,
+                _ => Table::new(),
+            };
+            // The preprocessor's own keys, as book.toml sets them
+            if let Some(source) = source.filter(|s| !s.standalone) {
+                table.extend(source.table.into_iter().filter(|(key, _)| PREPROCESSOR_KEYS.contains(&key.as_str())));
+            }
+            print!("{}", toml::to_string(&Value::Table(table)).unwrap_or_default());
+        }
+        _ => return Err(crate::usage_error("config needs check, print or print --effective")),
+    }
+    Ok(())
+}
+
+/// Default options with the configuration of the book in `root` applied,
+/// as read from its book.toml and daily.toml
+pub fn book_options(root: &Path) -> io::Result<Options> {
+    let path = root.join("book.toml");
+    let book: Table = match fs::read_to_string(&path) {
+        Ok(content) => toml::from_str(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))?,
+        Err(_) => Table::new(),
+    };
+    let table = book.get("preprocessor").and_then(|p| p.get("daily")).and_then(Value::as_table);
+    let src = book.get("book").and_then(|b| b.get("src")).and_then(Value::as_str).unwrap_or("src");
+    let site_url = book.get("output")
+        .and_then(|o| o.get("html"))
+        .and_then(|h| h.get("site-url"))
+        .and_then(Value::as_str);
+    options_for(root, &root.join(src), table, site_url)
+}
+
+/// Options for the book in `root` with notes in `src_path`: the layout
+/// settings of daily.toml, or of `table` (the book's
+/// `[preprocessor.daily]`) when there is none, then the preprocessor's
+/// own keys of `table`. `update` and the preprocessor both build their
+/// options here, so they generate the same summary. `site_url` is the
+/// book's `output.html.site-url`, used when `table` sets none.
+pub fn options_for(root: &Path, src_path: &Path, table: Option<&Table>, site_url: Option<&str>) -> io::Result<Options> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let mut options = Options::default();
+    let layout = if root.join(FILE_NAME).exists() {
+        Config::load(root)?
+    } else {
+        if let Some(table) = table {
+            let problems = check_table(root, table, false);
+            if !problems.is_empty() {
+                return Err(invalid(format!("book.toml [preprocessor.daily]: {}", problems.join("; "))));
+            }
+        }
+        let mut layout: Config = table.map(Config::from_table).transpose()
+            .map_err(|e| invalid(format!("book.toml [preprocessor.daily]: {}", e)))?
+            .unwrap_or_default();
+        layout.exclude.extend(ignore::read_file(root)?);
+        layout
+    };
+    layout.apply(&mut options)?;
+    options.read_git_dates(src_path);
+    options.line_ending = LineEnding::from_editorconfig(root).unwrap_or(LineEnding::Lf);
+    options.site_url = site_url.map(str::to_string);
+    let Some(table) = table else { return Ok(options) };
+    let flag = |key: &str| table.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+    let text = |key: &str| table.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let count = |key: &str| -> io::Result<Option<usize>> {
+        let Some(value) = table.get(key) else { return Ok(None) };
+        value.as_integer().filter(|n| *n > 0).map(|n| Some(n as usize))
+            .ok_or_else(|| invalid(format!("invalid {} {}, expected a positive number", key, value)))
+    };
+
+    options.year_separators = flag("year-separators");
+    options.archive_page = flag("archive-page");
+    options.acronym_index = flag("acronym-index");
+    options.team_digest = flag("team");
+    options.time_log = flag("time-log");
+    options.tag_index = flag("tag-index");
+    options.tag_pages = flag("tag-pages");
+    options.series_pages = flag("series-pages");
+    options.calendar_pages = flag("calendar");
+    options.stats_page = flag("stats-page");
+    options.include_drafts = flag("include-drafts");
+    options.section_markers = flag("section-markers");
+    options.missing_image_placeholders = flag("missing-image-placeholders");
+    options.entry_navigation = flag("entry-navigation");
+    options.series_navigation = flag("series-navigation");
+    if let Some(value) = text("wiki-links") {
+        options.wiki_links = Some(Unresolved::parse(&value)
+            .ok_or_else(|| invalid(format!("invalid wiki-links {:?}, expected keep, strip or warn", value)))?);
+    }
+    options.hydrate_placeholders = flag("hydrate-placeholders");
+    options.strict = flag("strict");
+    options.changes_since = text("changes-since");
+    if let Some(url) = text("site-url") {
+        options.site_url = Some(url);
+    }
+    if let Some(value) = text("line-endings") {
+        options.line_ending = LineEnding::parse(&value)
+            .ok_or_else(|| invalid(format!("invalid line-endings {:?}, expected lf or crlf", value)))?;
+    }
+    if let Some(dir) = text("generated-dir") {
+        options.generated_dir = generated_dir(&dir).map_err(invalid)?;
+    }
+    if let Some(age) = text("exclude-older-than") {
+        options.exclude_before = Some(Date::today().minus_age(&age)
+            .ok_or_else(|| invalid(format!("invalid exclude-older-than {:?}, expected e.g. 90d or 2y", age)))?);
+    }
+    options.recent_entries = count("recent-entries")?;
+    options.related_notes = count("related-notes")?;
+    if let Some(value) = table.get("related-by") {
+        let by: Option<Vec<RelatedBy>> = value.as_array()
+            .map(|items| items.iter().map(|item| item.as_str().and_then(RelatedBy::parse)).collect())
+            .and_then(|by: Option<Vec<RelatedBy>>| by.filter(|by| !by.is_empty()));
+        options.related_by = by.ok_or_else(|| invalid(format!("invalid related-by {}, expected a list of tags, links and titles", value)))?;
+    }
+    options.jobs = count("jobs")?;
+    options.reads_per_second = count("reads-per-second")?.map(|n| n.min(u32::MAX as usize) as u32);
+    options.feed_entries = count("feed-entries")?;
+    options.feed_html = flag("feed-html");
+    options.feed_sections = flag("feed-sections");
+    options.on_this_day = flag("on-this-day");
+    if let Some(size) = table.get("max-file-size") {
+        options.max_file_size = byte_count(size)
+            .ok_or_else(|| invalid(format!("invalid max-file-size {}", size)))?;
+    }
+    if let Some(size) = table.get("head-size") {
+        options.head_size = byte_count(size)
+            .ok_or_else(|| invalid(format!("invalid head-size {}", size)))?;
+    }
+    if let Some(dirs) = table.get("plain-titles").and_then(|v| v.as_array()) {
+        options.plain_title_dirs = dirs.iter().filter_map(|d| d.as_str()).map(PathBuf::from).collect();
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_dir_stays_inside_src() {
+        for value in ["", ".", "..", "/", "/tmp/pages", "../pages", "pages/..", "./pages", "a/../../b"] {
+            assert!(generated_dir(value).is_err(), "{:?} was accepted", value);
+            let table: Table = toml::from_str(&format!("generated-dir = {:?}", value)).unwrap();
+            assert!(options_for(Path::new("/nonexistent"), Path::new("/nonexistent/src"), Some(&table), None).is_err(), "{:?}", value);
+            assert!(!check_table(Path::new("/nonexistent"), &table, false).is_empty(), "{:?}", value);
+        }
+        assert_eq!(generated_dir("_generated"), Ok(PathBuf::from("_generated")));
+        assert_eq!(generated_dir("pages/generated"), Ok(PathBuf::from("pages/generated")));
+    }
+
+    #[test]
+    fn negative_sizes_are_refused() {
+        for key in ["max-file-size", "head-size"] {
+            let table: Table = toml::from_str(&format!("{} = -1", key)).unwrap();
+            assert!(!check_table(Path::new("/nonexistent"), &table, false).is_empty(), "{}", key);
+            assert!(options_for(Path::new("/nonexistent"), Path::new("/nonexistent/src"), Some(&table), None).is_err(), "{}", key);
+        }
+        assert_eq!(byte_count(&Value::Integer(4096)), Some(4096));
+        assert_eq!(byte_count(&Value::String("1M".to_string())), Some(1 << 20));
+    }
+}
@@ -0,0 +1,405 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::config;
+use crate::generated;
+use crate::head;
+use crate::date::DateTime;
+use crate::sources::snapshot;
+use crate::summary::ScanStats;
+
+/// Options for `mdbook-daily daemon`
+struct DaemonOptions {
+    schedule: Option<Schedule>,
+    poll: Duration,
+    status_addr: Option<String>,
+    health_log: Option<PathBuf>,
+    install: bool,
+}
+
+impl DaemonOptions {
+    fn parse(args: &[String]) -> Result<DaemonOptions, String> {
+        let mut options = DaemonOptions {
+            schedule: None,
+            poll: Duration::from_secs(2),
+            status_addr: None,
+            health_log: None,
+            install: false,
+        };
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{} needs a value", arg));
+            match arg.as_str() {
+                "--schedule" => options.schedule = Some(Schedule::parse(value()?)?),
+                "--poll" => {
+                    let secs: u64 = value()?.parse()
+                        .map_err(|_| "--poll expects a number of seconds".to_string())?;
+                    options.poll = Duration::from_secs(secs.max(1));
+                }
+                "--status-addr" => options.status_addr = Some(value()?.clone()),
+                "--health-log" => options.health_log = Some(PathBuf::from(value()?)),
+                "--install" => options.install = true,
+                _ => return Err(format!("unknown daemon argument {:?}", arg)),
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Cron-like schedule with the usual five fields
+/// (minute hour day-of-month month day-of-week), evaluated in UTC
+pub struct Schedule {
+    minutes: Vec<u32>,
+    hours: Vec<u32>,
+    days: Vec<u32>,
+    months: Vec<u32>,
+    weekdays: Vec<u32>,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Schedule {
+    pub fn parse(expr: &str) -> Result<Schedule, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!("schedule {:?} must have five fields", expr));
+        }
+        let weekdays = parse_field(fields[4], 0, 7)?
+            .into_iter()
+            .map(|d| d % 7)
+            .collect();
+        Ok(Schedule {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            weekdays,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+
+    pub fn matches(&self, t: &DateTime) -> bool {
+        let day = self.days.contains(&t.day);
+        let weekday = self.weekdays.contains(&t.weekday());
+        // As in cron, a restricted day-of-month and day-of-week match either
+        let day_matches = match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        };
+        self.minutes.contains(&t.minute)
+            && self.hours.contains(&t.hour)
+            && self.months.contains(&t.month)
+            && day_matches
+    }
+}
+
+/// Parse one cron field: `*`, `N`, `A-B`, with optional `/STEP`, comma separated
+fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>, String> {
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse()
+                    .map_err(|_| format!("invalid step in {:?}", part))?;
+                (range, step.max(1))
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (parse_number(a, min, max)?, parse_number(b, min, max)?)
+        } else {
+            let n = parse_number(range, min, max)?;
+            (n, n)
+        };
+        values.extend((start..=end).step_by(step as usize));
+    }
+    Ok(values)
+}
+
+fn parse_number(s: &str, min: u32, max: u32) -> Result<u32, String> {
+    match s.parse::<u32>() {
+        Ok(n) if (min..=max).contains(&n) => Ok(n),
+        _ => Err(format!("{:?} is not in {}-{}", s, min, max)),
+    }
+}
+
+/// State reported by the status endpoint
+struct Status {
+    started: DateTime,
+    runs: u64,
+    last_run: Option<DateTime>,
+    last_trigger: String,
+    last_error: Option<String>,
+}
+
+impl Status {
+    fn to_json(&self) -> Value {
+        json!({
+            "status": if self.last_error.is_some() { "error" } else { "ok" },
+            "started": self.started.to_string(),
+            "runs": self.runs,
+            "last_run": self.last_run.map(|t| t.to_string()),
+            "last_trigger": self.last_trigger,
+            "last_error": self.last_error,
+        })
+    }
+}
+
+/// Serve the daemon status as JSON on every request
+fn serve_status(addr: &str, status: Arc<Mutex<Status>>) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    println!("Status endpoint listening on http://{}", addr);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let body = match status.lock() {
+                Ok(status) => status.to_json().to_string(),
+                Err(_) => "{\"status\":\"error\"}".to_string(),
+            };
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(), body
+            );
+        }
+    });
+    Ok(())
+}
+
+/// Regenerate SUMMARY.md and the generated pages as `update` does,
+/// recording the outcome in the status and health log
+fn regenerate(src_path: &Path, trigger: &str, status: &Mutex<Status>, health_log: Option<&Path>) {
+    let now = DateTime::now();
+    let mut stats = ScanStats::default();
+    let result = config::book_options(Path::new(".")).and_then(|options| {
+        let changed = crate::write_summary(src_path, &options, &mut stats)?;
+        for line in generated::write_pages(src_path, &options, &stats)? {
+            println!("[{}] {}", now, line);
+        }
+        head::write_head(Path::new("theme"), &stats, options.line_ending)?;
+        Ok(changed)
+    });
+    stats.print_warnings();
+    let summary_path = src_path.join("SUMMARY.md");
+    let outcome = match &result {
+        Ok(true) => format!("updated {}", summary_path.display()),
+        Ok(false) => format!("no changes to {}", summary_path.display()),
+        Err(e) => format!("error: {}", e),
+    };
+    println!("[{}] {}: {}", now, trigger, outcome);
+
+    if let Ok(mut status) = status.lock() {
+        status.runs += 1;
+        status.last_run = Some(now);
+        status.last_trigger = trigger.to_string();
+        status.last_error = result.err().map(|e| e.to_string());
+    }
+
+    if let Some(log_path) = health_log {
+        let logged = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_path)
+            .and_then(|mut log| writeln!(log, "{} {} {}", now, trigger, outcome));
+        if let Err(e) = logged {
+            eprintln!("Warning: could not write health log {:?}: {}", log_path, e);
+        }
+    }
+}
+
+const SERVICE_NAME: &str = "mdbook-daily";
+const LAUNCHD_LABEL: &str = "io.github.lcyou.mdbook-daily";
+
+/// Quote an argument for a systemd `ExecStart=` line
+fn systemd_quote(arg: &str) -> String {
+    let escaped = arg.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%");
+    if escaped.is_empty() || escaped.contains(char::is_whitespace) || escaped != arg {
+        format!("\"{}\"", escaped)
+    } else {
+        escaped
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn systemd_unit(exe: &str, book_dir: &str, args: &[String]) -> String {
+    let mut exec = vec![systemd_quote(exe), "daemon".to_string()];
+    exec.extend(args.iter().map(|a| systemd_quote(a)));
+    format!(
+        "[Unit]\n\
+         Description=Regenerate SUMMARY.md for {book_dir}\n\
+         \n\
+         [Service]\n\
+         WorkingDirectory={}\n\
+         ExecStart={}\n\
+         Restart=on-failure\n\
+         \n\
+         [Install]\n\
+         WantedBy=default.target\n",
+        book_dir, exec.join(" "), book_dir = book_dir
+    )
+}
+
+fn launchd_plist(exe: &str, book_dir: &str, args: &[String]) -> String {
+    let mut program = format!("        <string>{}</string>\n        <string>daemon</string>\n", escape_xml(exe));
+    for arg in args {
+        program.push_str(&format!("        <string>{}</string>\n", escape_xml(arg)));
+    }
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n\
+         <dict>\n\
+         \x20   <key>Label</key>\n\
+         \x20   <string>{}</string>\n\
+         \x20   <key>ProgramArguments</key>\n\
+         \x20   <array>\n\
+         {}\
+         \x20   </array>\n\
+         \x20   <key>WorkingDirectory</key>\n\
+         \x20   <string>{}</string>\n\
+         \x20   <key>RunAtLoad</key>\n\
+         \x20   <true/>\n\
+         \x20   <key>KeepAlive</key>\n\
+         \x20   <true/>\n\
+         </dict>\n\
+         </plist>\n",
+        LAUNCHD_LABEL, program, escape_xml(book_dir)
+    )
+}
+
+/// Write a user service file that runs the daemon for the current book
+/// with the given options, then print how to enable it
+fn install_service(args: &[String]) -> io::Result<()> {
+    let exe = std::env::current_exe()?.to_string_lossy().into_owned();
+    let book_dir = std::env::current_dir()?.to_string_lossy().into_owned();
+    let home = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME is not set"))?;
+
+    let (path, content, enable) = if cfg!(target_os = "macos") {
+        let path = home.join("Library/LaunchAgents").join(format!("{}.plist", LAUNCHD_LABEL));
+        let enable = format!("launchctl load -w {}", path.display());
+        (path, launchd_plist(&exe, &book_dir, args), enable)
+    } else {
+        let path = home.join(".config/systemd/user").join(format!("{}.service", SERVICE_NAME));
+        let enable = format!("systemctl --user daemon-reload && systemctl --user enable --now {}", SERVICE_NAME);
+        (path, systemd_unit(&exe, &book_dir, args), enable)
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, content)?;
+    println!("Wrote {:?}", path);
+    println!("Enable it with: {}", enable);
+    Ok(())
+}
+
+/// Run until killed, regenerating on file changes and on the schedule
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let options = DaemonOptions::parse(args).map_err(|e| crate::usage_error(&e))?;
+    if options.install {
+        let daemon_args: Vec<String> = args.iter().filter(|a| *a != "--install").cloned().collect();
+        return install_service(&daemon_args);
+    }
+
+    let status = Arc::new(Mutex::new(Status {
+        started: DateTime::now(),
+        runs: 0,
+        last_run: None,
+        last_trigger: String::new(),
+        last_error: None,
+    }));
+    if let Some(addr) = &options.status_addr {
+        serve_status(addr, Arc::clone(&status))?;
+    }
+    let health_log = options.health_log.as_deref();
+
+    regenerate(src_path, "startup", &status, health_log);
+    let mut files = snapshot(src_path);
+    let mut last_scheduled = None;
+
+    loop {
+        thread::sleep(options.poll);
+
+        let now = DateTime::now();
+        let minute = (now.year, now.month, now.day, now.hour, now.minute);
+        let mut trigger = None;
+        if let Some(schedule) = &options.schedule {
+            if schedule.matches(&now) && last_scheduled != Some(minute) {
+                last_scheduled = Some(minute);
+                trigger = Some("schedule");
+            }
+        }
+
+        let current = snapshot(src_path);
+        if current != files {
+            files = current;
+            trigger = Some("change");
+        }
+
+        if let Some(trigger) = trigger {
+            regenerate(src_path, trigger, &status, health_log);
+            // The pages just written are not a change to react to
+            files = snapshot(src_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime {
+        DateTime { year: 2026, month: 10, day, hour, minute, second: 0 }
+    }
+
+    #[test]
+    fn restricted_day_and_weekday_match_either() {
+        // The 13th, or any Friday
+        let schedule = Schedule::parse("0 9 13 * 5").unwrap();
+        assert!(schedule.matches(&at(13, 9, 0)), "Tuesday the 13th");
+        assert!(schedule.matches(&at(16, 9, 0)), "Friday the 16th");
+        assert!(!schedule.matches(&at(14, 9, 0)), "Wednesday the 14th");
+        assert!(!schedule.matches(&at(16, 10, 0)), "Friday at the wrong hour");
+    }
+
+    #[test]
+    fn an_unrestricted_day_field_leaves_the_other_to_decide() {
+        let fridays = Schedule::parse("30 6 * * 5").unwrap();
+        assert!(fridays.matches(&at(16, 6, 30)));
+        assert!(!fridays.matches(&at(13, 6, 30)));
+        let thirteenths = Schedule::parse("30 6 13 * *").unwrap();
+        assert!(thirteenths.matches(&at(13, 6, 30)));
+        assert!(!thirteenths.matches(&at(16, 6, 30)));
+        // 7 is Sunday as well as 0
+        let sundays = Schedule::parse("0 0 * * 7").unwrap();
+        assert!(sundays.matches(&at(18, 0, 0)));
+    }
+
+    #[test]
+    fn ranges_steps_and_bad_fields() {
+        let schedule = Schedule::parse("*/15 9-17 * 10,12 1-5").unwrap();
+        assert!(schedule.matches(&at(14, 17, 45)));
+        assert!(!schedule.matches(&at(14, 17, 50)));
+        assert!(!schedule.matches(&at(18, 9, 0)), "Sunday");
+        assert!(Schedule::parse("0 9 * *").is_err());
+        assert!(Schedule::parse("60 9 * * *").is_err());
+        assert!(Schedule::parse("0 9 32 * *").is_err());
+    }
+}
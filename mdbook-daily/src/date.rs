@@ -0,0 +1,499 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+
+/// A UTC calendar timestamp with minute precision and a weekday
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl DateTime {
+    /// Current time in UTC
+    pub fn now() -> DateTime {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        DateTime::from_unix(secs)
+    }
+
+    pub fn from_unix(secs: i64) -> DateTime {
+        let days = secs.div_euclid(86_400);
+        let rem = secs.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (rem / 3600) as u32,
+            minute: (rem % 3600 / 60) as u32,
+            second: (rem % 60) as u32,
+        }
+    }
+
+    /// Day of week, 0 = Sunday
+    pub fn weekday(&self) -> u32 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        (days + 4).rem_euclid(7) as u32
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second)
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Proleptic Gregorian date for a number of days since 1970-01-01
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Number of days in `month` of `year`
+pub fn month_len(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Longest age [`Date::minus_age`] goes back or forward, in days
+const MAX_AGE_DAYS: i64 = 10_000 * 366;
+
+pub const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+pub const WEEKDAY_NAMES: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+
+/// A calendar date, serialized as `YYYY-MM-DD`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// The date, if `day` exists in that month of that year
+    pub fn new(year: i64, month: u32, day: u32) -> Option<Date> {
+        if !(1..=12).contains(&month) || day < 1 || day > month_len(year, month) {
+            return None;
+        }
+        Some(Date { year, month, day })
+    }
+
+    pub fn month_name(&self) -> &'static str {
+        MONTH_NAMES[self.month as usize - 1]
+    }
+
+    /// Today's date in UTC
+    pub fn today() -> Date {
+        let now = DateTime::now();
+        Date { year: now.year, month: now.month, day: now.day }
+    }
+
+    pub fn from_days(days: i64) -> Date {
+        let (year, month, day) = civil_from_days(days);
+        Date { year, month, day }
+    }
+
+    /// Days since 1970-01-01
+    pub fn days(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day)
+    }
+
+    /// Day of week, 0 = Sunday
+    pub fn weekday(&self) -> u32 {
+        (self.days() + 4).rem_euclid(7) as u32
+    }
+
+    pub fn weekday_name(&self) -> &'static str {
+        WEEKDAY_NAMES[self.weekday() as usize]
+    }
+
+    /// ISO 8601 week-numbering year and week: the week belongs to the
+    /// year its Thursday falls in
+    pub fn iso_week(&self) -> (i64, u32) {
+        let thursday = self.monday().days() + 3;
+        let year = Date::from_days(thursday).year;
+        (year, ((thursday - days_from_civil(year, 1, 1)) / 7 + 1) as u32)
+    }
+
+    /// The Monday starting this date's week
+    pub fn monday(&self) -> Date {
+        Date::from_days(self.days() - (self.weekday() as i64 + 6) % 7)
+    }
+
+    /// `YYYY/MMDD.md`, where `new` puts the day's entry inside a section
+    pub fn entry_path(&self) -> PathBuf {
+        PathBuf::from(self.year.to_string()).join(format!("{:02}{:02}.md", self.month, self.day))
+    }
+
+    /// The date `age` before this one, where `age` is a count with a
+    /// `d`, `w`, `m` or `y` suffix (`90d`, `6w`, `18m`, `2y`)
+    pub fn minus_age(&self, age: &str) -> Option<Date> {
+        let unit = age.chars().last()?;
+        let n: i64 = age[..age.len() - unit.len_utf8()].parse().ok()?;
+        // Ages too large for a calendar give no date rather than overflow
+        let months = match unit {
+            'd' | 'w' => {
+                let days = n.checked_mul(if unit == 'w' { 7 } else { 1 })?;
+                return (days.unsigned_abs() <= MAX_AGE_DAYS as u64).then(|| Date::from_days(self.days() - days));
+            }
+            'm' => n,
+            'y' => n.checked_mul(12)?,
+            _ => return None,
+        };
+        if months.unsigned_abs() > (MAX_AGE_DAYS / 366 * 12) as u64 {
+            return None;
+        }
+        let total = self.year * 12 + (self.month as i64 - 1) - months;
+        let (year, month) = (total.div_euclid(12), total.rem_euclid(12) as u32 + 1);
+        // Clamp to the end of shorter months
+        Some(Date { year, month, day: self.day.min(month_len(year, month)) })
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Date, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Some(&text).filter(|text| text.len() == 10)
+            .and_then(|text| date_from_path(text))
+            .ok_or_else(|| de::Error::custom(format!("invalid date {:?}, expected YYYY-MM-DD", text)))
+    }
+}
+
+fn parse_digits(s: &str, len: usize) -> Option<u32> {
+    if s.len() == len && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Date encoded in an entry path: `YYYY-MM-DD.md`, `YYYYMMDD.md`,
+/// `YYYY/MMDD.md` or `YYYY/MM/DD.md`
+pub fn date_from_path(path: &str) -> Option<Date> {
+    let named = named_date(path)?;
+    Date::new(named.year, named.month, named.day)
+}
+
+/// The date an entry path is named for in one of the formats of
+/// [`date_from_path`], even one that does not exist such as February
+/// 31st. Such entries are undated but keep their place among the dated.
+pub fn named_date(path: &str) -> Option<Date> {
+    let path = path.strip_suffix(".md").unwrap_or(path);
+    let parts: Vec<&str> = path.split('/').collect();
+    let stem = *parts.last()?;
+    let parent = parts.len().checked_sub(2).map(|i| parts[i]);
+    let grandparent = parts.len().checked_sub(3).map(|i| parts[i]);
+
+    if stem.len() >= 10 && stem.is_char_boundary(10) {
+        let head = &stem[..10];
+        if head.as_bytes()[4] == b'-' && head.as_bytes()[7] == b'-' {
+            if let (Some(y), Some(m), Some(d)) = (
+                parse_digits(&head[..4], 4),
+                parse_digits(&head[5..7], 2),
+                parse_digits(&head[8..], 2),
+            ) {
+                return named(y as i64, m, d);
+            }
+        }
+    }
+    if let Some(n) = parse_digits(stem, 8) {
+        return named((n / 10_000) as i64, n / 100 % 100, n % 100);
+    }
+    if let (Some(n), Some(year)) = (parse_digits(stem, 4), parent.and_then(|p| parse_digits(p, 4))) {
+        return named(year as i64, n / 100, n % 100);
+    }
+    if let (Some(day), Some(month), Some(year)) = (
+        parse_digits(stem, 2),
+        parent.and_then(|p| parse_digits(p, 2)),
+        grandparent.and_then(|p| parse_digits(p, 4)),
+    ) {
+        return named(year as i64, month, day);
+    }
+    None
+}
+
+/// Fields of a date that may not exist, within the ranges of a month and
+/// a day
+fn named(year: i64, month: u32, day: u32) -> Option<Date> {
+    ((1..=12).contains(&month) && (1..=31).contains(&day)).then_some(Date { year, month, day })
+}
+
+/// Reads entry dates from file names in a book's own convention, for
+/// library users whose names no `date-patterns` entry describes. Parsers
+/// in [`Options::date_parsers`](crate::summary::Options::date_parsers)
+/// are tried before the built-in formats. Parsers are shared by the
+/// threads that read notes.
+pub trait DateParser: Send + Sync {
+    /// Date of the entry at `path`, `/`-separated and without `.md`
+    fn parse(&self, path: &str) -> Option<Date>;
+}
+
+/// One conversion of a [`DatePattern`]
+enum Field {
+    Literal(char),
+    /// `%Y`, four digits
+    Year,
+    /// `%y`, two digits in 2000-2099
+    ShortYear,
+    /// `%m`, two digits
+    Month,
+    /// `%b`, `Jan` to `Dec`, any case
+    MonthAbbr,
+    /// `%B`, `January` to `December`, any case
+    MonthName,
+    /// `%d`, two digits
+    Day,
+    /// `%j`, three digits
+    DayOfYear,
+    /// `%G`, four digits
+    IsoYear,
+    /// `%V`, two digits
+    IsoWeek,
+    /// `%u`, 1 (Monday) to 7 (Sunday)
+    IsoWeekday,
+}
+
+/// A strftime-like file name pattern such as `%d%b%Y` for `01Jan2024.md`
+/// or `%Gw%Vd%u` for `2024w05d3.md`. A `/` in the pattern matches
+/// directories, e.g. `%Y/%b/%d`. The pattern must match the end of the
+/// path, and may be followed in the file name by `-`, `_` or a space and
+/// any other text.
+pub struct DatePattern {
+    fields: Vec<Field>,
+}
+
+impl DatePattern {
+    /// Parse a pattern, failing on unknown conversions and on patterns
+    /// that do not give a full date
+    pub fn new(pattern: &str) -> Result<DatePattern, String> {
+        let mut fields = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                fields.push(Field::Literal(c));
+                continue;
+            }
+            fields.push(match chars.next() {
+                Some('Y') => Field::Year,
+                Some('y') => Field::ShortYear,
+                Some('m') => Field::Month,
+                Some('b') => Field::MonthAbbr,
+                Some('B') => Field::MonthName,
+                Some('d') => Field::Day,
+                Some('j') => Field::DayOfYear,
+                Some('G') => Field::IsoYear,
+                Some('V') => Field::IsoWeek,
+                Some('u') => Field::IsoWeekday,
+                Some('%') => Field::Literal('%'),
+                Some(other) => return Err(format!("unknown conversion %{}", other)),
+                None => return Err("ends in a lone %".to_string()),
+            });
+        }
+        let has = |wanted: fn(&Field) -> bool| fields.iter().any(wanted);
+        let year = has(|f| matches!(f, Field::Year | Field::ShortYear));
+        let month = has(|f| matches!(f, Field::Month | Field::MonthAbbr | Field::MonthName));
+        let full = (year && month && has(|f| matches!(f, Field::Day)))
+            || (year && has(|f| matches!(f, Field::DayOfYear)))
+            || (has(|f| matches!(f, Field::IsoYear)) && has(|f| matches!(f, Field::IsoWeek)));
+        if !full {
+            return Err("needs a year, month and day, a year and %j, or %G and %V".to_string());
+        }
+        Ok(DatePattern { fields })
+    }
+}
+
+impl DateParser for DatePattern {
+    fn parse(&self, path: &str) -> Option<Date> {
+        // Match as many trailing path components as the pattern has
+        let components = 1 + self.fields.iter().filter(|f| matches!(f, Field::Literal('/'))).count();
+        let start = path.rmatch_indices('/').nth(components - 1).map_or(0, |(i, _)| i + 1);
+        let mut rest = &path[start..];
+
+        let (mut year, mut month, mut day, mut day_of_year) = (None, None, None, None);
+        let (mut iso_year, mut iso_week, mut iso_weekday) = (None, None, None);
+        let digits = |rest: &mut &str, len: usize| {
+            let value = parse_digits(rest.get(..len)?, len)?;
+            *rest = &rest[len..];
+            Some(value)
+        };
+        for field in &self.fields {
+            match field {
+                Field::Literal(c) => {
+                    let next = rest.chars().next()?;
+                    if !next.eq_ignore_ascii_case(c) {
+                        return None;
+                    }
+                    rest = &rest[next.len_utf8()..];
+                }
+                Field::Year => year = Some(digits(&mut rest, 4)? as i64),
+                Field::ShortYear => year = Some(2000 + digits(&mut rest, 2)? as i64),
+                Field::Month => month = Some(digits(&mut rest, 2)?),
+                Field::MonthAbbr | Field::MonthName => {
+                    let (i, len) = MONTH_NAMES.iter().enumerate().find_map(|(i, name)| {
+                        let name = if matches!(field, Field::MonthAbbr) { &name[..3] } else { name };
+                        rest.get(..name.len()).filter(|r| r.eq_ignore_ascii_case(name)).map(|_| (i, name.len()))
+                    })?;
+                    month = Some(i as u32 + 1);
+                    rest = &rest[len..];
+                }
+                Field::Day => day = Some(digits(&mut rest, 2)?),
+                Field::DayOfYear => day_of_year = Some(digits(&mut rest, 3)?),
+                Field::IsoYear => iso_year = Some(digits(&mut rest, 4)? as i64),
+                Field::IsoWeek => iso_week = Some(digits(&mut rest, 2)?),
+                Field::IsoWeekday => iso_weekday = Some(digits(&mut rest, 1)?),
+            }
+        }
+        if !(rest.is_empty() || rest.starts_with(['-', '_', ' '])) {
+            return None;
+        }
+
+        match (year, month, day, day_of_year, iso_year, iso_week) {
+            // Days past the end of the month are rejected
+            (Some(year), Some(month), Some(day), ..) => Date::new(year, month, day),
+            (Some(year), _, _, Some(day_of_year), ..) if day_of_year >= 1 => {
+                let date = Date::from_days(days_from_civil(year, 1, 1) + day_of_year as i64 - 1);
+                (date.year == year).then_some(date)
+            }
+            (.., Some(iso_year), Some(iso_week)) => {
+                let weekday = iso_weekday.unwrap_or(1);
+                if !(1..=53).contains(&iso_week) || !(1..=7).contains(&weekday) {
+                    return None;
+                }
+                // Week 1 is the one holding January 4th
+                let first_monday = Date { year: iso_year, month: 1, day: 4 }.monday();
+                let date = Date::from_days(first_monday.days() + (iso_week as i64 - 1) * 7 + weekday as i64 - 1);
+                (date.iso_week() == (iso_year, iso_week)).then_some(date)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(pattern: &str, path: &str) -> Option<Date> {
+        DatePattern::new(pattern).unwrap().parse(path)
+    }
+
+    #[test]
+    fn patterns_round_trip() {
+        let start = Date::new(2023, 12, 20).unwrap().days();
+        for days in start..start + 800 {
+            let date = Date::from_days(days);
+            let (iso_year, iso_week) = date.iso_week();
+            let iso_weekday = (date.weekday() + 6) % 7 + 1;
+            let names = [
+                ("%Y-%m-%d", format!("notes/{}", date)),
+                ("%d%b%Y", format!("{:02}{}{}", date.day, &date.month_name()[..3], date.year)),
+                ("%Y/%B/%d", format!("journal/{}/{}/{:02}-standup", date.year, date.month_name().to_lowercase(), date.day)),
+                ("%y%j", format!("{:02}{:03}", date.year % 100, days - days_from_civil(date.year, 1, 1) + 1)),
+                ("%Gw%Vd%u", format!("{}w{:02}d{}", iso_year, iso_week, iso_weekday)),
+            ];
+            for (pattern, name) in names {
+                assert_eq!(parse(pattern, &name), Some(date), "{} {}", pattern, name);
+            }
+        }
+    }
+
+    #[test]
+    fn days_past_the_end_of_the_month_are_rejected() {
+        assert_eq!(Date::new(2026, 2, 31), None);
+        assert_eq!(Date::new(2023, 2, 29), None);
+        assert_eq!(Date::new(2024, 4, 31), None);
+        assert!(Date::new(2024, 2, 29).is_some());
+        assert_eq!(parse("%Y%m%d", "20260231"), None);
+        assert_eq!(parse("%Y/%m%d", "2026/0431"), None);
+        assert_eq!(parse("%Y%j", "2023366"), None);
+        assert_eq!(parse("%Y%j", "2024366"), Date::new(2024, 12, 31));
+    }
+
+    #[test]
+    fn iso_weeks_belong_to_the_year_of_their_thursday() {
+        assert_eq!(Date::new(2024, 12, 30).unwrap().iso_week(), (2025, 1));
+        assert_eq!(Date::new(2021, 1, 3).unwrap().iso_week(), (2020, 53));
+        assert_eq!(Date::new(2026, 12, 31).unwrap().iso_week(), (2026, 53));
+        assert_eq!(parse("%Gw%Vd%u", "2025w01d1"), Date::new(2024, 12, 30));
+        assert_eq!(parse("%Gw%Vd%u", "2020w53d7"), Date::new(2021, 1, 3));
+        assert_eq!(parse("%Gw%Vd%u", "2021w53d1"), None);
+        assert_eq!(parse("%Gw%V", "2026w01"), Date::new(2025, 12, 29));
+    }
+
+    #[test]
+    fn huge_ages_give_no_date() {
+        let today = Date::new(2026, 10, 14).unwrap();
+        for age in ["99999999999999999m", "99999999999999999y", "9223372036854775807w", "-9223372036854775808d", "20000y"] {
+            assert_eq!(today.minus_age(age), None, "{}", age);
+        }
+        assert_eq!(today.minus_age("1m"), Date::new(2026, 9, 14));
+        assert_eq!(Date::new(2024, 3, 31).unwrap().minus_age("1m"), Date::new(2024, 2, 29));
+        assert_eq!(today.minus_age("2w"), Date::new(2026, 9, 30));
+    }
+
+    #[test]
+    fn impossible_names_keep_their_date() {
+        assert_eq!(date_from_path("essay/2026/0231.md"), None);
+        let named = named_date("essay/2026/0231.md").unwrap();
+        assert!(Date::new(2026, 2, 25).unwrap() < named && named < Date::new(2026, 3, 1).unwrap());
+        assert_eq!(named_date("essay/2026/1340.md"), None);
+        assert_eq!(named_date("2026-02-29-leap.md").map(|d| d.to_string()), Some("2026-02-29".into()));
+    }
+
+    #[test]
+    fn incomplete_patterns_are_refused() {
+        assert!(DatePattern::new("%Y-%m").is_err());
+        assert!(DatePattern::new("%V%u").is_err());
+        assert!(DatePattern::new("%Y%q%d").is_err());
+    }
+}
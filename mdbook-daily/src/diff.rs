@@ -0,0 +1,103 @@
+/// Lines of unchanged context around each hunk
+const CONTEXT: usize = 3;
+
+/// Above this many line pairs the changed middle is shown as replaced
+/// wholesale instead of matched line by line
+const MAX_TABLE: usize = 4_000_000;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Keep,
+    Remove,
+    Add,
+}
+
+/// Line-level edit script from `old` to `new`, as each op with the line it
+/// applies to. Common leading and trailing lines are matched first, which
+/// leaves a small middle for the longest-common-subsequence table in the
+/// usual case of a few added or retitled entries.
+fn edits<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Op, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let (old_mid, new_mid) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+
+    let mut ops: Vec<(Op, &str)> = old[..prefix].iter().map(|line| (Op::Keep, *line)).collect();
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_TABLE {
+        ops.extend(old_mid.iter().map(|line| (Op::Remove, *line)));
+        ops.extend(new_mid.iter().map(|line| (Op::Add, *line)));
+    } else {
+        // lcs[i][j]: longest common subsequence of old_mid[i..] and new_mid[j..]
+        let width = new_mid.len() + 1;
+        let mut lcs = vec![0u32; (old_mid.len() + 1) * width];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() || j < new_mid.len() {
+            if i < old_mid.len() && j < new_mid.len() && old_mid[i] == new_mid[j] {
+                ops.push((Op::Keep, old_mid[i]));
+                i += 1;
+                j += 1;
+            } else if j == new_mid.len() || (i < old_mid.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+                ops.push((Op::Remove, old_mid[i]));
+                i += 1;
+            } else {
+                ops.push((Op::Add, new_mid[j]));
+                j += 1;
+            }
+        }
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|line| (Op::Keep, *line)));
+    ops
+}
+
+/// Unified diff of `new` against `old`, labelled with their names, or
+/// `None` when they have the same lines
+pub fn unified(old: &str, new: &str, old_name: &str, new_name: &str) -> Option<String> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = edits(&old_lines, &new_lines);
+    let changed: Vec<usize> = (0..ops.len()).filter(|&i| ops[i].0 != Op::Keep).collect();
+    if changed.is_empty() {
+        return None;
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+    // Group changes whose context would overlap into one hunk
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        match groups.last_mut() {
+            Some((_, end)) if i <= *end + 2 * CONTEXT + 1 => *end = i,
+            _ => groups.push((i, i)),
+        }
+    }
+    for (first, last) in groups {
+        let start = first.saturating_sub(CONTEXT);
+        let end = (last + CONTEXT + 1).min(ops.len());
+        // Line numbers where the hunk starts in each file
+        let old_start = ops[..start].iter().filter(|(op, _)| *op != Op::Add).count();
+        let new_start = ops[..start].iter().filter(|(op, _)| *op != Op::Remove).count();
+        let hunk = &ops[start..end];
+        let old_len = hunk.iter().filter(|(op, _)| *op != Op::Add).count();
+        let new_len = hunk.iter().filter(|(op, _)| *op != Op::Remove).count();
+        // An empty side is numbered by the line before it, as diff(1) does
+        let number = |start: usize, len: usize| if len == 0 { start } else { start + 1 };
+        out.push_str(&format!("@@ -{},{} +{},{} @@\n",
+            number(old_start, old_len), old_len, number(new_start, new_len), new_len));
+        for (op, line) in hunk {
+            let sign = match op {
+                Op::Keep => ' ',
+                Op::Remove => '-',
+                Op::Add => '+',
+            };
+            out.push_str(&format!("{}{}\n", sign, line));
+        }
+    }
+    Some(out)
+}
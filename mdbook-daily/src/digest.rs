@@ -0,0 +1,91 @@
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::config;
+use crate::date::{date_from_path, Date};
+use crate::summary::{self, escape_title, section_title, Options, ScanStats};
+use crate::url::page_url;
+
+/// Push ATX headings down by `levels` so entries nest under the digest's
+/// own headings. Fenced code is left alone.
+fn demote_headings(content: &str, levels: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut in_fence = false;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && line.starts_with('#') {
+            let hashes = line.len() - line.trim_start_matches('#').len();
+            writeln!(out, "{}{}", "#".repeat((hashes + levels).min(6)), &line[hashes..])?;
+        } else {
+            writeln!(out, "{}", line)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the full text of every entry dated `date`, grouped by section
+pub fn write_digest(src_path: &Path, date: Date, stats: &ScanStats, options: &Options, out: &mut dyn Write) -> io::Result<usize> {
+    let mut day: Vec<_> = stats.dated.iter().filter(|(d, _, _)| *d == date).collect();
+    day.sort_by(|a, b| a.2.cmp(&b.2));
+
+    writeln!(out, "# {}", options.strings.daily_digest.replace("{date}", &date.to_string()))?;
+    if day.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "{}", options.strings.no_entries)?;
+    }
+    let mut section = None;
+    for (_, title, link) in &day {
+        let path = Path::new(link.trim_start_matches("./"));
+        let dir = path.components().next().map(|c| c.as_os_str().to_string_lossy().into_owned());
+        if dir != section {
+            writeln!(out)?;
+            writeln!(out, "## {}", section_title(options, dir.as_deref().unwrap_or_default()))?;
+            section = dir;
+        }
+        writeln!(out)?;
+        match &options.site_url {
+            Some(site) => writeln!(out, "### [{}]({})", escape_title(title), page_url(site, link))?,
+            None => writeln!(out, "### {} (`{}`)", title, path.display())?,
+        }
+        writeln!(out)?;
+        demote_headings(&summary::read_text(&src_path.join(path), options.max_file_size)?, 3, out)?;
+    }
+    Ok(day.len())
+}
+
+/// `mdbook-daily digest [--date today|yesterday|YYYY-MM-DD] [--site-url URL]`:
+/// print one day's entries as a single markdown document, linking each
+/// entry to its published page when a site URL is given
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut date = Date::today();
+    let mut options = config::book_options(Path::new("."))?;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--date" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--date needs a date"))?;
+                date = match value.as_str() {
+                    "today" => Date::today(),
+                    "yesterday" => Date::from_days(Date::today().days() - 1),
+                    _ => date_from_path(value)
+                        .ok_or_else(|| crate::usage_error(&format!("invalid date {:?}, expected YYYY-MM-DD", value)))?,
+                };
+            }
+            "--site-url" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--site-url needs a URL"))?;
+                options.site_url = Some(value.clone());
+            }
+            _ => return Err(crate::usage_error(&format!("unknown digest argument {:?}", arg))),
+        }
+    }
+
+    let mut stats = ScanStats::default();
+    summary::write_summary_to(src_path, &options, &mut stats, &mut io::sink())?;
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    write_digest(src_path, date, &stats, &options, &mut out)?;
+    out.flush()
+}
@@ -0,0 +1,239 @@
+use std::path::{Path, PathBuf};
+
+use mdbook::book::{Book, BookItem};
+
+use crate::assets::rewrite_links;
+use crate::date::Date;
+use crate::frontmatter;
+use crate::link::relative_link;
+use crate::lint::headings;
+use crate::links::{chapter_link, markdown_links, resolve_link};
+use crate::tags::slug;
+use crate::summary::{escape_title, read_text, Options, ScanStats};
+
+const OPEN: &str = "{{#daily ";
+const CLOSE: &str = "}}";
+
+/// Entries `recent` lists without a count
+const RECENT_COUNT: usize = 5;
+
+/// What directives expand from: the dated entries and a way to read a
+/// note by its path relative to src
+struct Context<'a> {
+    /// Dated entries, newest first
+    dated: Vec<&'a (Date, String, String)>,
+    today: Date,
+    read: &'a dyn Fn(&Path) -> Option<String>,
+}
+
+/// `content` of the chapter at `source` (relative to src) with each
+/// `{{#daily ...}}` directive outside fenced code replaced by what it
+/// stands for. Unknown directives stay as written, with a warning.
+/// `including` holds the notes whose includes are being expanded, ending
+/// with `source`.
+fn expand(content: &str, source: &Path, cx: &Context, including: &mut Vec<PathBuf>) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut in_fence = false;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if in_fence || !line.contains(OPEN) {
+            out.push_str(line);
+            continue;
+        }
+        let mut rest = line;
+        while let Some(start) = rest.find(OPEN) {
+            let Some(len) = rest[start..].find(CLOSE) else { break };
+            out.push_str(&rest[..start]);
+            let directive = &rest[start..start + len + CLOSE.len()];
+            let args: Vec<&str> = directive[OPEN.len()..len].split_whitespace().collect();
+            match expansion(&args, source, cx, including) {
+                Some(text) => out.push_str(&text),
+                None => {
+                    eprintln!("Warning: {}: unknown directive {}", source.display(), directive);
+                    out.push_str(directive);
+                }
+            }
+            rest = &rest[start + len + CLOSE.len()..];
+        }
+        out.push_str(rest);
+    }
+    out
+}
+
+/// What one directive, split into words, expands to in the chapter at
+/// `source`; `None` for one that is not known
+fn expansion(args: &[&str], source: &Path, cx: &Context, including: &mut Vec<PathBuf>) -> Option<String> {
+    let dir = source.parent().unwrap_or(Path::new(""));
+    let link = |title: &str, link: &str| {
+        format!("[{}]({})", escape_title(title), chapter_link(dir, Path::new(link.trim_start_matches("./"))))
+    };
+    match args {
+        ["latest"] => Some(cx.dated.first().map(|(_, title, path)| link(title, path)).unwrap_or_default()),
+        ["recent"] | ["recent", _] => {
+            let count = match args.get(1) {
+                Some(count) => count.parse().ok().filter(|n| *n > 0)?,
+                None => RECENT_COUNT,
+            };
+            let items: Vec<String> = cx.dated.iter().take(count)
+                .map(|(date, title, path)| format!("- {} ({})", link(title, path), date))
+                .collect();
+            Some(items.join("\n"))
+        }
+        ["today"] => {
+            let today = cx.dated.iter().find(|(date, _, _)| *date == cx.today);
+            let body = today.and_then(|(_, _, path)| (cx.read)(Path::new(path.trim_start_matches("./"))));
+            Some(body.map(|body| frontmatter::split(&body).1.trim().to_string()).unwrap_or_default())
+        }
+        ["include", target] => Some(include(target, source, cx, including).unwrap_or_else(|problem| {
+            eprintln!("Warning: {}: {{{{#daily include {}}}}} {}", source.display(), target, problem);
+            String::new()
+        })),
+        _ => None,
+    }
+}
+
+/// The body of the note `target` (relative to `source`), or with a
+/// `#heading` only that heading and what follows it up to the next
+/// heading of the same or a higher level. Its own directives are
+/// expanded and its links rebased onto `source`; a note that, through its
+/// includes, includes itself is a cycle and is left out.
+fn include(target: &str, source: &Path, cx: &Context, including: &mut Vec<PathBuf>) -> Result<String, String> {
+    let (file, heading) = match target.split_once('#') {
+        Some((file, heading)) => (file, Some(heading)),
+        None => (target, None),
+    };
+    let path = if file.is_empty() { source.to_path_buf() } else { resolve_link(source, file) };
+    if let Some(start) = including.iter().position(|seen| *seen == path) {
+        let chain: Vec<String> = including[start..].iter().chain([&path]).map(|p| p.display().to_string()).collect();
+        return Err(format!("is a cycle: {}", chain.join(" -> ")));
+    }
+    let content = (cx.read)(&path).ok_or_else(|| format!("has no note {}", path.display()))?;
+    let body = frontmatter::split(&content).1;
+    let fragment = match heading {
+        Some(heading) => section(body, heading).ok_or_else(|| format!("has no heading {:?} in {}", heading, path.display()))?,
+        None => body.trim().to_string(),
+    };
+    including.push(path.clone());
+    let expanded = expand(&fragment, &path, cx, including);
+    including.pop();
+    Ok(rebase(&expanded, &path, source))
+}
+
+/// The lines of `body` from the heading whose slug is that of `heading`
+/// to the next heading of the same or a higher level
+fn section(body: &str, heading: &str) -> Option<String> {
+    let lines: Vec<&str> = body.lines().collect();
+    let found = headings(&lines, 0);
+    let at = found.iter().position(|h| slug(&h.text) == slug(heading))?;
+    let end = found[at + 1..].iter().find(|h| h.level <= found[at].level).map_or(lines.len(), |h| h.line);
+    Some(lines[found[at].line..end].join("\n").trim().to_string())
+}
+
+/// `text` from the note at `from` with its relative links pointing at
+/// the same files from the note at `to`
+fn rebase(text: &str, from: &Path, to: &Path) -> String {
+    if from.parent() == to.parent() {
+        return text.to_string();
+    }
+    let dir = to.parent().unwrap_or(Path::new(""));
+    let mut targets = markdown_links(text);
+    targets.sort();
+    targets.dedup();
+    targets.into_iter()
+        .filter(|target| !target.starts_with('/'))
+        .fold(text.to_string(), |text, target| {
+            let rebased = relative_link(dir, &resolve_link(from, &target));
+            rewrite_links(&text, &target, &rebased)
+        })
+}
+
+/// Expand the `{{#daily ...}}` directives of every chapter:
+/// `{{#daily latest}}` links to the newest entry, `{{#daily recent N}}`
+/// lists the N newest (5 without a count) and `{{#daily today}}` is the
+/// body of today's entry, or nothing before it is written.
+/// `{{#daily include PATH#HEADING}}` transcludes a section of another
+/// note, see [`include`].
+pub fn expand_directives(book: &mut Book, src_path: &Path, stats: &ScanStats, options: &Options) {
+    let mut dated: Vec<_> = stats.dated.iter().collect();
+    dated.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.cmp(&b.2)));
+    let read = |relative: &Path| read_text(&src_path.join(relative), options.max_file_size).ok();
+    let cx = Context { dated, today: Date::today(), read: &read };
+    book.for_each_mut(|item| {
+        let BookItem::Chapter(chapter) = item else { return };
+        let Some(source) = &chapter.source_path else { return };
+        if chapter.content.contains(OPEN) {
+            chapter.content = expand(&chapter.content, source, &cx, &mut vec![source.clone()]);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<(Date, String, String)> {
+        [(14, "Wednesday"), (12, "Monday"), (13, "Tuesday")]
+            .map(|(day, title)| (Date::new(2026, 10, day).unwrap(), title.to_string(), format!("./journal/2026/10{}.md", day)))
+            .to_vec()
+    }
+
+    fn context<'a>(entries: &'a [(Date, String, String)], read: &'a dyn Fn(&Path) -> Option<String>) -> Context<'a> {
+        let mut dated: Vec<_> = entries.iter().collect();
+        dated.sort_by_key(|entry| std::cmp::Reverse(entry.0));
+        Context { dated, today: Date::new(2026, 10, 14).unwrap(), read }
+    }
+
+    #[test]
+    fn latest_recent_and_today_expand() {
+        let entries = entries();
+        let read = |path: &Path| (path == Path::new("journal/2026/1014.md")).then(|| "---\ntags: [x]\n---\n# Wednesday\n\nBody\n".to_string());
+        let cx = context(&entries, &read);
+        let page = "Latest: {{#daily latest}}\n\n{{#daily recent 2}}\n\n{{#daily today}}\n";
+        assert_eq!(
+            expand(page, Path::new("README.md"), &cx, &mut Vec::new()),
+            "Latest: [Wednesday](./journal/2026/1014.md)\n\n\
+             - [Wednesday](./journal/2026/1014.md) (2026-10-14)\n- [Tuesday](./journal/2026/1013.md) (2026-10-13)\n\n\
+             # Wednesday\n\nBody\n",
+        );
+        // Links are relative to the page holding the directive
+        assert_eq!(expand("{{#daily latest}}", Path::new("notes/home.md"), &cx, &mut Vec::new()), "[Wednesday](../journal/2026/1014.md)");
+    }
+
+    #[test]
+    fn unknown_directives_and_code_stay() {
+        let entries = entries();
+        let read = |_: &Path| None;
+        let cx = context(&entries, &read);
+        let page = "{{#daily nonsense}}\n```\n{{#daily latest}}\n```\n{{#daily recent 0}}\n";
+        assert_eq!(expand(page, Path::new("README.md"), &cx, &mut Vec::new()), page);
+    }
+
+    #[test]
+    fn includes_take_a_section_and_rebase_its_links() {
+        let entries = entries();
+        let read = |path: &Path| (path == Path::new("journal/2026/1014.md")).then(|| {
+            "# Wednesday\n\n## Highlights\n\nSaw ![a heron](heron.jpg)\n\n### Aside\n\nStill here\n\n## Tasks\n\n- [ ] left out\n".to_string()
+        });
+        let cx = context(&entries, &read);
+        assert_eq!(
+            expand("{{#daily include ../journal/2026/1014.md#highlights}}\n", Path::new("reviews/week-42.md"), &cx, &mut Vec::new()),
+            "## Highlights\n\nSaw ![a heron](../journal/2026/heron.jpg)\n\n### Aside\n\nStill here\n",
+        );
+    }
+
+    #[test]
+    fn include_cycles_are_left_out() {
+        let entries = entries();
+        let read = |path: &Path| match path.to_str() {
+            Some("a.md") => Some("A {{#daily include b.md}}".to_string()),
+            Some("b.md") => Some("B {{#daily include a.md}}".to_string()),
+            _ => None,
+        };
+        let cx = context(&entries, &read);
+        assert_eq!(expand("{{#daily include b.md}}", Path::new("a.md"), &cx, &mut vec![PathBuf::from("a.md")]), "B ");
+        assert_eq!(expand("{{#daily include missing.md}}", Path::new("a.md"), &cx, &mut Vec::new()), "");
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Line ending used for generated files
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn parse(value: &str) -> Option<LineEnding> {
+        match value.to_ascii_lowercase().as_str() {
+            "lf" => Some(LineEnding::Lf),
+            "crlf" => Some(LineEnding::Crlf),
+            _ => None,
+        }
+    }
+
+    fn as_bytes(self) -> &'static [u8] {
+        match self {
+            LineEnding::Lf => b"\n",
+            LineEnding::Crlf => b"\r\n",
+        }
+    }
+
+    /// The `end_of_line` that `.editorconfig` in `dir` sets for markdown
+    /// files. Only `*`, `*.md` style and brace patterns naming `md` are
+    /// understood; later sections win, as in EditorConfig.
+    pub fn from_editorconfig(dir: &Path) -> Option<LineEnding> {
+        let config = fs::read_to_string(dir.join(".editorconfig")).ok()?;
+        let mut applies = false;
+        let mut ending = None;
+        for line in config.lines().map(str::trim) {
+            if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                applies = glob == "*" || glob == "**" || glob.ends_with(".md") || glob.contains("md,") || glob.contains(",md");
+            } else if let Some((key, value)) = line.split_once('=') {
+                if applies && key.trim().eq_ignore_ascii_case("end_of_line") {
+                    ending = LineEnding::parse(value.trim()).or(ending);
+                }
+            }
+        }
+        ending
+    }
+}
+
+/// Strip trailing whitespace from one line (without its terminator) and
+/// append `ending`
+fn push_line(out: &mut Vec<u8>, line: &[u8], ending: LineEnding) {
+    let end = line.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(0, |i| i + 1);
+    out.extend_from_slice(&line[..end]);
+    out.extend_from_slice(ending.as_bytes());
+}
+
+/// Text with trailing whitespace removed and every line ended by `ending`
+pub fn normalize(text: &[u8], ending: LineEnding) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len() + text.len() / 32);
+    let mut lines = text.split(|&b| b == b'\n').peekable();
+    while let Some(line) = lines.next() {
+        // The piece after the final newline is empty for terminated text
+        if lines.peek().is_none() && line.is_empty() {
+            break;
+        }
+        push_line(&mut out, line, ending);
+    }
+    out
+}
+
+/// Writer that normalizes line endings and trailing whitespace one line
+/// at a time, so streamed output stays streamed
+pub struct Normalize<W: Write> {
+    inner: W,
+    line: Vec<u8>,
+    ending: LineEnding,
+}
+
+impl<W: Write> Normalize<W> {
+    pub fn new(inner: W, ending: LineEnding) -> Normalize<W> {
+        Normalize { inner, line: Vec::new(), ending }
+    }
+}
+
+impl<W: Write> Write for Normalize<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rest = buf;
+        while let Some(pos) = rest.iter().position(|&b| b == b'\n') {
+            self.line.extend_from_slice(&rest[..pos]);
+            let mut out = Vec::with_capacity(self.line.len() + 2);
+            push_line(&mut out, &self.line, self.ending);
+            self.inner.write_all(&out)?;
+            self.line.clear();
+            rest = &rest[pos + 1..];
+        }
+        self.line.extend_from_slice(rest);
+        Ok(buf.len())
+    }
+
+    /// Also terminates a final line that has no newline yet
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.line.is_empty() {
+            let mut out = Vec::new();
+            push_line(&mut out, &self.line, self.ending);
+            self.inner.write_all(&out)?;
+            self.line.clear();
+        }
+        self.inner.flush()
+    }
+}
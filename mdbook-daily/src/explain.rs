@@ -0,0 +1,129 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::config;
+use crate::date::date_from_path;
+use crate::git;
+use crate::links::{markdown_files, markdown_links, resolve_link};
+use crate::summary;
+
+/// `mdbook-daily explain FILE`: show how the generator sees one file
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let [file] = args else {
+        return Err(crate::usage_error("explain needs exactly one file"));
+    };
+    let file = Path::new(file);
+    let relative = file.strip_prefix(src_path).unwrap_or(file);
+    let path = src_path.join(relative);
+    if !path.is_file() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("{:?} is not a file", path)));
+    }
+    let link = format!("./{}", relative.to_string_lossy().replace('\\', "/"));
+    let file_name = relative.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let options = config::book_options(Path::new("."))?;
+
+    println!("File:     {}", path.display());
+    // Shared content reached through a symlinked directory
+    if let (Ok(canonical), Ok(src)) = (fs::canonicalize(&path), fs::canonicalize(src_path)) {
+        if canonical != src.join(relative) {
+            println!("Canonical: {} (through a symlink)", canonical.display());
+        }
+    }
+
+    // Title and the rule that produced it
+    let name = if file_name == "README.md" {
+        relative.parent().and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    } else {
+        file_name.strip_suffix(".md").unwrap_or(&file_name).to_string()
+    };
+    let (heading, front) = if file_name == "README.md" {
+        (None, None)
+    } else {
+        summary::read_head(&path, &options).map_or((None, None), |head| summary::head_metadata(&head))
+    };
+    match (front.as_ref().and_then(|f| f.title.as_ref()), heading) {
+        (Some(title), _) => println!("Title:    {} (front matter title)", title),
+        (None, Some(heading)) => println!("Title:    {} (first H1 heading)", heading),
+        (None, None) => {
+            let title = options.title_for(&name, relative);
+            let source = if file_name == "README.md" { "directory name" } else { "file name without .md" };
+            let rule = if options.plain_titles(relative) {
+                format!("{}, used verbatim", source)
+            } else {
+                format!("{}, through the title transform", source)
+            };
+            println!("Title:    {} ({})", title, rule);
+        }
+    }
+    println!("Sort key: {} (by name within its directory)", file_name);
+
+    let mut components = relative.components();
+    match (components.next(), components.next()) {
+        (Some(section), Some(_)) => {
+            println!("Section:  {}", summary::section_title(&options, &section.as_os_str().to_string_lossy()));
+        }
+        _ => println!("Section:  none (top level)"),
+    }
+    match options.entry_date(&link) {
+        Some(date) if options.created.get(relative) == Some(&date) && date_from_path(&link).is_none() => {
+            println!("Date:     {} (from the commit that added it)", date)
+        }
+        Some(date) => println!("Date:     {} (from path)", date),
+        None => println!("Date:     none"),
+    }
+    match git::authors(src_path).get(relative) {
+        Some(author) => println!("Author:   {} (git)", author),
+        None => println!("Author:   unknown (not committed)"),
+    }
+
+    // Inclusion decision
+    let listed = summary::book_entries(src_path, &options)?
+        .into_iter()
+        .find(|e| e.path.as_deref() == Some(link.as_str()));
+    let reason = if relative.extension().is_none_or(|e| e != "md") {
+        Some("not a .md file".to_string())
+    } else if file_name == "SUMMARY.md" {
+        Some("SUMMARY.md itself is never listed".to_string())
+    } else if options.is_excluded(relative, false) {
+        Some("matches an `exclude` pattern or .mdbookdailyignore".to_string())
+    } else if let Some(expires) = front.as_ref().filter(|f| f.is_expired()).and_then(|f| f.expires) {
+        Some(format!("expired after {} (front matter expires)", expires))
+    } else {
+        summary::check_markdown_file(&path, &options)
+    };
+    match (&listed, reason) {
+        (Some(entry), _) => println!("Included: yes, line {} of the generated SUMMARY.md", entry.line + 1),
+        (None, Some(reason)) => println!("Included: no, {}", reason),
+        (None, None) => println!("Included: no, only pinned chapters and files in section directories are listed"),
+    }
+
+    // Outbound links, resolved relative to the file
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let outbound = markdown_links(&content);
+    println!("Outbound links: {}", outbound.len());
+    for target in &outbound {
+        let resolved = resolve_link(&path, target);
+        let state = if resolved.exists() { "ok" } else { "missing" };
+        println!("  {} -> {} ({})", target, resolved.display(), state);
+    }
+
+    // Inbound links from every other note
+    let mut inbound = Vec::new();
+    for other in markdown_files(src_path) {
+        if other == path {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&other) else { continue };
+        if markdown_links(&content).iter().any(|t| resolve_link(&other, t) == path) {
+            inbound.push(other);
+        }
+    }
+    println!("Inbound links: {}", inbound.len());
+    for other in &inbound {
+        println!("  {}", other.display());
+    }
+    Ok(())
+}
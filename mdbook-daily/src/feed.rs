@@ -0,0 +1,213 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use mdbook::config::Config as BookConfig;
+use mdbook::utils::render_markdown;
+
+use crate::date::Date;
+use crate::frontmatter;
+use crate::generated::{self, remove_stale};
+use crate::recent::excerpt;
+use crate::sources;
+use crate::summary::{read_text, Options, ScanStats};
+use crate::tags::slug;
+use crate::url::page_url;
+
+/// Feed file (relative to src). mdBook copies it to the root of the
+/// built site like any other file in src.
+pub const FEED_FILE: &str = "feed.xml";
+
+/// Directory of the per-section feeds, relative to src
+pub fn feeds_dir(options: &Options) -> PathBuf {
+    options.generated_dir.join("feeds")
+}
+
+/// Longest entry summary, in characters, when the feed has no HTML content
+const SUMMARY_LEN: usize = 300;
+
+/// `text` with the characters XML gives a meaning escaped
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The `count` newest of `entries`, newest first
+fn newest<'a>(entries: impl IntoIterator<Item = &'a (Date, String, String)>, count: usize) -> Vec<&'a (Date, String, String)> {
+    let mut dated: Vec<_> = entries.into_iter().collect();
+    dated.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.cmp(&b.2)));
+    dated.truncate(count);
+    dated
+}
+
+/// The Atom feed of the dated `entries`, newest first, credited to the
+/// book's `authors` and served from `feed_path` (relative to src). Each
+/// entry links to
+/// its page under `site_url` and carries an excerpt, or its body rendered
+/// to HTML when `options.feed_html` is set. The feed is as new as its newest entry, so
+/// it only changes when the entries do.
+pub fn atom_feed(
+    src_path: &Path,
+    title: &str,
+    authors: &[String],
+    site_url: &str,
+    feed_path: &str,
+    options: &Options,
+    dated: &[&(Date, String, String)],
+) -> String {
+    let site = site_url.trim_end_matches('/');
+    let updated = dated.first().map_or_else(|| "1970-01-01".to_string(), |(date, _, _)| date.to_string());
+
+    let mut feed = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    feed.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    feed.push_str(&format!("  <title>{}</title>\n", escape(title)));
+    feed.push_str(&format!("  <link href=\"{}/\"/>\n", escape(site)));
+    feed.push_str(&format!("  <link rel=\"self\" href=\"{}/{}\"/>\n", escape(site), escape(feed_path)));
+    feed.push_str(&format!("  <id>{}/</id>\n", escape(site)));
+    feed.push_str(&format!("  <updated>{}T00:00:00Z</updated>\n", updated));
+    // Atom wants an author for every entry, which the feed's own covers
+    for author in authors {
+        feed.push_str(&format!("  <author><name>{}</name></author>\n", escape(author)));
+    }
+    for (date, entry_title, link) in dated.iter().copied() {
+        let url = escape(&page_url(site, link));
+        feed.push_str("  <entry>\n");
+        feed.push_str(&format!("    <title>{}</title>\n", escape(entry_title)));
+        feed.push_str(&format!("    <link href=\"{}\"/>\n", url));
+        feed.push_str(&format!("    <id>{}</id>\n", url));
+        feed.push_str(&format!("    <updated>{}T00:00:00Z</updated>\n", date));
+        let path = src_path.join(link.trim_start_matches("./"));
+        if options.feed_html {
+            let content = read_text(&path, options.max_file_size).unwrap_or_default();
+            // Relative links and images resolve against the entry's page
+            let body = render_markdown(frontmatter::split(&content).1, false);
+            feed.push_str(&format!("    <content type=\"html\" xml:base=\"{}\">{}</content>\n", url, escape(&body)));
+        } else if let Some(summary) = excerpt(&read_text(&path, options.head_size).unwrap_or_default(), SUMMARY_LEN) {
+            feed.push_str(&format!("    <summary>{}</summary>\n", escape(&summary)));
+        }
+        feed.push_str("  </entry>\n");
+    }
+    feed.push_str("</feed>\n");
+    feed
+}
+
+/// The absolute site URL feeds link to, and the book's title and authors
+fn feed_settings(src_path: &Path, options: &Options) -> io::Result<(String, String, Vec<String>)> {
+    let Some(site_url) = options.site_url.as_deref().filter(|url| url.contains("://")) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a feed needs an absolute site URL, set site-url or pass --site-url",
+        ));
+    };
+    let book_toml = src_path.parent().unwrap_or(Path::new(".")).join("book.toml");
+    let book = BookConfig::from_disk(&book_toml).ok().map(|config| config.book);
+    let authors = book.as_ref().map(|book| book.authors.clone()).unwrap_or_default();
+    let title = book.and_then(|book| book.title)
+        .unwrap_or_else(|| options.strings.recent_entries.clone());
+    Ok((site_url.to_string(), title, authors))
+}
+
+/// Write `feed.xml` into src with the `count` newest entries, titled
+/// after the book. Needs an absolute site URL to link the entries to.
+/// Returns the number of entries in the feed.
+pub fn write_feed(src_path: &Path, options: &Options, stats: &ScanStats, count: usize) -> io::Result<usize> {
+    let (site_url, title, authors) = feed_settings(src_path, options)?;
+    let feed = atom_feed(src_path, &title, &authors, &site_url, FEED_FILE, options, &newest(&stats.dated, count));
+    let path = src_path.join(FEED_FILE);
+    sources::check_write(options.read_only_sources, src_path, &options.generated_dir, &path)?;
+    generated::write_if_changed(&path, feed.as_bytes(), options.line_ending)?;
+    Ok(stats.dated.len().min(count))
+}
+
+/// Dated entries by the top-level section holding them. Entries directly
+/// in src belong to no section.
+fn section_entries(stats: &ScanStats) -> BTreeMap<&str, Vec<&(Date, String, String)>> {
+    let mut sections: BTreeMap<&str, Vec<_>> = BTreeMap::new();
+    for entry in &stats.dated {
+        if let Some((section, _)) = entry.2.trim_start_matches("./").split_once('/') {
+            sections.entry(section).or_default().push(entry);
+        }
+    }
+    sections
+}
+
+/// Write a feed per top-level section to `feeds/SECTION.xml` in the
+/// generated pages directory, each with the section's `count` newest
+/// entries, removing the feeds of sections that are gone. Returns the
+/// number of feeds.
+pub fn write_section_feeds(src_path: &Path, options: &Options, stats: &ScanStats, count: usize) -> io::Result<usize> {
+    let (site_url, title, authors) = feed_settings(src_path, options)?;
+    let dir = src_path.join(feeds_dir(options));
+    fs::create_dir_all(&dir)?;
+    let sections = section_entries(stats);
+    let mut keep = HashSet::new();
+    for (section, entries) in &sections {
+        let relative = feeds_dir(options).join(format!("{}.xml", slug(section)));
+        let feed_path = relative.to_string_lossy().replace('\\', "/");
+        let feed_title = format!("{} ({})", title, section);
+        let feed = atom_feed(src_path, &feed_title, &authors, &site_url, &feed_path, options, &newest(entries.iter().copied(), count));
+        let path = src_path.join(&relative);
+        generated::write_if_changed(&path, feed.as_bytes(), options.line_ending)?;
+        keep.insert(path);
+    }
+    remove_stale(&dir, &keep)?;
+    Ok(sections.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::Date;
+
+    fn stats() -> ScanStats {
+        ScanStats {
+            dated: vec![(Date::new(2026, 10, 14).unwrap(), "Fish & <chips>".into(), "./2026/10/14.md".into())],
+            ..ScanStats::default()
+        }
+    }
+
+    #[test]
+    fn feed_names_the_book_authors() {
+        let stats = stats();
+        let entries: Vec<_> = stats.dated.iter().collect();
+        let authors = vec!["Ann".to_string(), "Bo & Co".to_string()];
+        let feed = atom_feed(Path::new("/nonexistent"), "Log", &authors, "https://x.test/", FEED_FILE, &Options::default(), &entries);
+        assert!(feed.contains("  <author><name>Ann</name></author>\n  <author><name>Bo &amp; Co</name></author>\n"));
+        let feed = atom_feed(Path::new("/nonexistent"), "Log", &[], "https://x.test/", FEED_FILE, &Options::default(), &entries);
+        assert!(!feed.contains("<author>"));
+    }
+
+    #[test]
+    fn titles_are_escaped() {
+        let stats = stats();
+        let entries: Vec<_> = stats.dated.iter().collect();
+        let feed = atom_feed(Path::new("/nonexistent"), "\"Q\" & A", &[], "https://x.test", FEED_FILE, &Options::default(), &entries);
+        assert!(feed.contains("  <title>&quot;Q&quot; &amp; A</title>\n"));
+        assert!(feed.contains("    <title>Fish &amp; &lt;chips&gt;</title>\n"));
+        assert!(feed.contains("<link href=\"https://x.test/2026/10/14.html\"/>"));
+    }
+
+    #[test]
+    fn section_feeds_split_entries_by_top_level_directory() {
+        let stats = ScanStats {
+            dated: [("./journal/2026/1014.md", 14), ("./til/1013.md", 13), ("./journal/2026/1012.md", 12), ("./1011.md", 11)]
+                .map(|(link, day)| (Date::new(2026, 10, day).unwrap(), "Entry".to_string(), link.to_string()))
+                .to_vec(),
+            ..ScanStats::default()
+        };
+        let sections = section_entries(&stats);
+        let links: Vec<(&str, Vec<&str>)> = sections.iter()
+            .map(|(section, entries)| (*section, entries.iter().map(|entry| entry.2.as_str()).collect()))
+            .collect();
+        assert_eq!(links, [
+            ("journal", vec!["./journal/2026/1014.md", "./journal/2026/1012.md"]),
+            ("til", vec!["./til/1013.md"]),
+        ]);
+        let feed = atom_feed(Path::new("/nonexistent"), "Log (til)", &[], "https://x.test", "daily/feeds/til.xml", &Options::default(), &sections["til"]);
+        assert!(feed.contains("<link rel=\"self\" href=\"https://x.test/daily/feeds/til.xml\"/>"));
+        assert_eq!(feed.matches("<entry>").count(), 1);
+    }
+}
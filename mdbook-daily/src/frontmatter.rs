@@ -0,0 +1,228 @@
+use toml::value::{Table, Value};
+
+use crate::date::{date_from_path, Date};
+
+/// Metadata a note declares in a leading `---` (YAML) or `+++` (TOML)
+/// block: `title`, `date`, `tags`, `draft`, `order`, `canonical`,
+/// `description`, `expires`, `week`, `series`, `part`, `aliases` and
+/// `status`
+#[derive(Clone, Default)]
+pub struct FrontMatter {
+    /// Link title, preferred over the first H1
+    pub title: Option<String>,
+    /// Entry date, preferred over a date in the path
+    pub date: Option<Date>,
+    pub tags: Vec<String>,
+    /// Left out of the summary unless drafts are included
+    pub draft: bool,
+    /// Position among its siblings; ordered notes come first, lowest first
+    pub order: Option<i64>,
+    /// URL of the original when the entry is cross-posted
+    pub canonical: Option<String>,
+    /// Page description for search engines and link previews
+    pub description: Option<String>,
+    /// Last day the entry is listed, for time-limited content
+    pub expires: Option<Date>,
+    /// Monday of the week a planning page covers
+    pub week: Option<Date>,
+    /// Name of the multi-day thread the entry is part of
+    pub series: Option<String>,
+    /// Number of the entry in its series, ordering it before its date does
+    pub part: Option<i64>,
+    /// Old paths of the entry, each given a page redirecting to it
+    pub aliases: Vec<String>,
+    /// Where the note is in its workflow, one of the configured `statuses`
+    pub status: Option<String>,
+}
+
+impl FrontMatter {
+    /// Whether the `expires` date has passed
+    pub fn is_expired(&self) -> bool {
+        self.expires.is_some_and(|expires| expires < Date::today())
+    }
+}
+
+/// Split a leading front matter block off `content`, returning the parsed
+/// metadata, if there was a block, and the body after it. A block without
+/// its closing fence, or one that does not parse, is treated as body:
+/// `---` also opens and closes thematic breaks around plain text.
+pub fn split(content: &str) -> (Option<FrontMatter>, &str) {
+    let text = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let fence = match text.lines().next().map(str::trim_end) {
+        Some("---") => "---",
+        Some("+++") => "+++",
+        _ => return (None, content),
+    };
+    let block_start = text.find('\n').map_or(text.len(), |i| i + 1);
+    let mut offset = block_start;
+    for line in text[block_start..].split_inclusive('\n') {
+        if line.trim_end() == fence {
+            let block = &text[block_start..offset];
+            let table = if fence == "+++" { toml::from_str(block).ok() } else { yaml_table(block) };
+            return match table {
+                Some(table) => (Some(from_table(&table)), &text[offset + line.len()..]),
+                None => (None, content),
+            };
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+/// Content with `keys` set in its front matter block, replacing earlier
+/// values of the same keys and adding a YAML block if there is none
+pub fn set_keys(content: &str, keys: &[(&str, Value)]) -> String {
+    let bom = if content.starts_with('\u{feff}') { "\u{feff}" } else { "" };
+    let text = &content[bom.len()..];
+    let fence = match (split(text).0, text.lines().next().map(str::trim_end)) {
+        (Some(_), Some("+++")) => "+++",
+        (Some(_), _) => "---",
+        (None, _) => {
+            let block: String = keys.iter().map(|(key, value)| format!("{}: {}\n", key, yaml_value(value))).collect();
+            return format!("{}---\n{}---\n{}", bom, block, text);
+        }
+    };
+    let body = split(text).1;
+    let mut lines: Vec<String> = text[..text.len() - body.len()].lines().map(str::to_string).collect();
+    let mut close = lines.len() - 1;
+    let separator = if fence == "+++" { '=' } else { ':' };
+    for (key, value) in keys {
+        let line = if fence == "+++" { format!("{} = {}", key, value) } else { format!("{}: {}", key, yaml_value(value)) };
+        let existing = lines[1..close].iter()
+            .position(|l| l.split_once(separator).is_some_and(|(k, _)| k.trim() == *key));
+        match existing {
+            Some(i) => lines[i + 1] = line,
+            None => {
+                lines.insert(close, line);
+                close += 1;
+            }
+        }
+    }
+    format!("{}{}\n{}", bom, lines.join("\n"), body)
+}
+
+/// A value as a YAML scalar. Strings are double-quoted with JSON escapes,
+/// which YAML reads the same way.
+fn yaml_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => serde_json::to_string(s).expect("strings serialize"),
+        other => other.to_string(),
+    }
+}
+
+/// Read the known keys out of a parsed block, ignoring any others
+fn from_table(table: &Table) -> FrontMatter {
+    let text = |key: &str| match table.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Datetime(d) => Some(d.to_string()),
+        _ => None,
+    };
+    let list = |key: &str| match table.get(key) {
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+        Some(Value::String(s)) => s.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect(),
+        _ => Vec::new(),
+    };
+    FrontMatter {
+        title: text("title").filter(|t| !t.trim().is_empty()),
+        date: text("date").and_then(|d| date_from_path(d.get(..10).unwrap_or(&d))),
+        tags: list("tags"),
+        draft: table.get("draft").and_then(Value::as_bool).unwrap_or(false),
+        order: table.get("order").and_then(Value::as_integer),
+        canonical: text("canonical").filter(|c| !c.trim().is_empty()),
+        description: text("description").filter(|d| !d.trim().is_empty()),
+        expires: text("expires").and_then(|d| date_from_path(d.get(..10).unwrap_or(&d))),
+        week: text("week").and_then(|d| date_from_path(d.get(..10).unwrap_or(&d))),
+        series: text("series").filter(|s| !s.trim().is_empty()),
+        part: table.get("part").and_then(Value::as_integer),
+        aliases: list("aliases"),
+        status: text("status").map(|s| s.trim().to_string()).filter(|s| !s.is_empty()),
+    }
+}
+
+/// Parse the flat YAML front matter notes use: `key: value` pairs whose
+/// values are scalars, `[a, b]` lists or `- item` lines. Nested mappings
+/// are skipped.
+fn yaml_table(block: &str) -> Option<Table> {
+    let mut table = Table::new();
+    let mut list_key: Option<String> = None;
+    for line in block.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if let (Some(key), Some(item)) = (&list_key, trimmed.strip_prefix("- ")) {
+            if let Some(Value::Array(items)) = table.get_mut(key) {
+                items.push(yaml_scalar(item));
+            }
+            continue;
+        }
+        list_key = None;
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        // Anything but `key: value` means the block is not front matter
+        let (key, value) = line.split_once(':')?;
+        let key = key.trim().trim_matches(['"', '\'']).to_string();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return None;
+        }
+        let value = value.trim();
+        if value.is_empty() {
+            table.insert(key.clone(), Value::Array(Vec::new()));
+            list_key = Some(key);
+        } else if let Some(items) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            let items = items.split(',').map(str::trim).filter(|i| !i.is_empty()).map(yaml_scalar).collect();
+            table.insert(key, Value::Array(items));
+        } else {
+            table.insert(key, yaml_scalar(value));
+        }
+    }
+    Some(table)
+}
+
+/// A YAML scalar as a boolean, integer or string
+fn yaml_scalar(value: &str) -> Value {
+    let quoted = |q: char| value.len() >= 2 && value.starts_with(q) && value.ends_with(q);
+    if quoted('"') {
+        // Escapes YAML has beyond JSON's are rare enough to keep as written
+        let unescaped = serde_json::from_str(value).unwrap_or_else(|_| value[1..value.len() - 1].to_string());
+        return Value::String(unescaped);
+    }
+    if quoted('\'') {
+        return Value::String(value[1..value.len() - 1].replace("''", "'"));
+    }
+    // Drop a trailing comment
+    let value = value.split(" #").next().unwrap_or(value).trim();
+    match value {
+        "true" | "yes" => Value::Boolean(true),
+        "false" | "no" => Value::Boolean(false),
+        _ => value.parse().map(Value::Integer).unwrap_or_else(|_| Value::String(value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoted_strings_round_trip() {
+        for title in ["It's \"quoted\"", "back\\slash", "tab\tand 'single'", "plain"] {
+            let content = set_keys("# Body\n", &[("title", Value::String(title.to_string()))]);
+            assert_eq!(split(&content).0.and_then(|front| front.title).as_deref(), Some(title), "{}", content);
+        }
+        let (front, _) = split("---\ntitle: 'it''s'\n---\n");
+        assert_eq!(front.and_then(|front| front.title).as_deref(), Some("it's"));
+    }
+
+    #[test]
+    fn thematic_breaks_are_not_front_matter() {
+        let content = "---\nSome text between two rules\n---\n\nBody\n";
+        let (front, body) = split(content);
+        assert!(front.is_none());
+        assert_eq!(body, content);
+        let (front, body) = split("---\ntitle: Kept\ntags:\n  - a\n---\nBody\n");
+        assert_eq!(front.map(|front| (front.title, front.tags)), Some((Some("Kept".to_string()), vec!["a".to_string()])));
+        assert_eq!(body, "Body\n");
+        assert!(split("+++\nnot = [toml\n+++\nBody\n").0.is_none());
+    }
+}
@@ -0,0 +1,189 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::eol::{self, LineEnding};
+use crate::date::Date;
+use crate::stats::{self, WritingStats};
+use crate::{acronyms, aliases, calendar, changes, config, feed, git, onthisday, recent, rollup, series, tags, team, worklog};
+use crate::summary::{self, GeneratedPage, Options, ScanStats};
+
+/// Write `content` with normalized line endings to `path` unless it
+/// already holds exactly that, so `mdbook serve` does not see a change
+/// and rebuild again
+pub fn write_if_changed(path: &Path, content: &[u8], ending: LineEnding) -> io::Result<bool> {
+    let content = &eol::normalize(content, ending)[..];
+    if fs::read(path).is_ok_and(|existing| existing == content) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(path, content)?;
+    Ok(true)
+}
+
+/// Replace `path` with `content` through a temporary file in the same
+/// directory, so a killed process never leaves a half-written file
+pub fn write_atomic(path: &Path, content: &[u8]) -> io::Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp = path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()));
+    fs::write(&temp, content)?;
+    fs::rename(&temp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp);
+    })
+}
+
+/// Remove files under `dir` that are not in `keep`, so entries that went
+/// away do not leave stale pages behind
+pub fn remove_stale(dir: &Path, keep: &HashSet<PathBuf>) -> io::Result<()> {
+    let Ok(entries) = fs::read_dir(dir) else { return Ok(()) };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_stale(&path, keep)?;
+            // Only succeeds once the directory is empty
+            let _ = fs::remove_dir(&path);
+        } else if !keep.contains(&path) {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write every enabled generated page into the generated directory and
+/// return a line describing each. Expects SUMMARY.md to have been
+/// written already.
+pub fn write_pages(src_path: &Path, options: &Options, stats: &ScanStats) -> io::Result<Vec<String>> {
+    let mut report = Vec::new();
+    let pages = options.generated_pages();
+    if let Some(count) = options.recent_entries {
+        let listed = recent::write_recent_page(src_path, options, stats, count)?;
+        report.push(format!("Listed {} recent entries in {:?}", listed, src_path.join(recent::RECENT_PAGE)));
+    }
+    if options.on_this_day {
+        let listed = onthisday::write_on_this_day_page(src_path, options, stats)?;
+        report.push(format!("Listed {} entries from earlier years in {:?}", listed, src_path.join(onthisday::ON_THIS_DAY_PAGE)));
+    }
+    if options.rollup.is_some() {
+        let count = rollup::write_rollup_pages(src_path, options, stats)?;
+        report.push(format!("Wrote {} rollup pages", count));
+    }
+    let aliases = aliases::write_redirects(src_path, options, stats)?;
+    if aliases > 0 {
+        report.push(format!("Wrote {} alias redirects", aliases));
+    }
+    if let Some(count) = options.feed_entries {
+        let listed = feed::write_feed(src_path, options, stats, count)?;
+        report.push(format!("Wrote {} entries to {:?}", listed, src_path.join(feed::FEED_FILE)));
+        if options.feed_sections {
+            let feeds = feed::write_section_feeds(src_path, options, stats, count)?;
+            report.push(format!("Wrote {} section feeds to {:?}", feeds, src_path.join(feed::feeds_dir(options))));
+        }
+    }
+    if pages.is_empty() && !options.team_digest && !options.include_drafts && !options.calendar_pages && !options.series_pages {
+        return Ok(report);
+    }
+    fs::create_dir_all(src_path.join(&options.generated_dir))?;
+    if options.include_drafts {
+        write_draft_pages(src_path, options, stats)?;
+        report.push(format!("Wrote {} draft placeholder pages to {:?}", stats.drafts.len(), src_path.join(options.drafts_dir())));
+    }
+    if options.calendar_pages {
+        let count = calendar::write_calendar_pages(src_path, options, stats)?;
+        report.push(format!("Wrote {} calendar pages to {:?}", count, src_path.join(calendar::calendar_dir(options))));
+    }
+    if options.series_pages {
+        let count = series::write_series_pages(src_path, options, stats)?;
+        report.push(format!("Wrote {} series pages to {:?}", count, src_path.join(series::series_dir(options))));
+    }
+    if options.team_digest {
+        let count = team::write_digest_pages(src_path, options, stats)?;
+        report.push(format!("Wrote {} daily digest pages to {:?}", count, src_path.join(team::digest_dir(options))));
+    }
+    let entries = summary::parse_entries(&fs::read_to_string(src_path.join("SUMMARY.md"))?);
+
+    for page in pages {
+        let relative = options.generated_path(page);
+        let page_path = src_path.join(&relative);
+        let mut content = Vec::new();
+        match page {
+            GeneratedPage::Archive => {
+                summary::write_archive_page(&options.strings, stats, &git::authors(src_path), &mut content)?;
+                report.push(format!("Listed {} archived entries in {:?}", stats.excluded.len(), page_path));
+            }
+            GeneratedPage::Changes => {
+                let rev = options.changes_since.as_deref().unwrap_or("HEAD");
+                let changes = changes::changes_since(src_path, rev, &options.generated_dir)?;
+                changes::write_changes_page(src_path, &options.strings, rev, &changes, &entries, &relative, &mut content)?;
+                report.push(format!("Listed {} changed entries in {:?}", changes.len(), page_path));
+            }
+            GeneratedPage::Acronyms => {
+                let count = acronyms::write_acronym_page(src_path, &options.strings, &entries, &relative, &mut content)?;
+                report.push(format!("Indexed {} acronyms in {:?}", count, page_path));
+            }
+            GeneratedPage::Tags => {
+                let count = tags::write_tag_index(options, stats, &entries, &relative, &mut content)?;
+                report.push(format!("Indexed {} tags in {:?}", count, page_path));
+                if options.tag_pages {
+                    let count = tags::write_tag_pages(src_path, options, stats, &entries)?;
+                    report.push(format!("Wrote {} tag pages to {:?}", count, src_path.join(tags::tags_dir(options))));
+                }
+            }
+            GeneratedPage::Stats => {
+                let writing = WritingStats::collect(src_path, options, stats, Date::today());
+                stats::write_stats_page(&options.strings, &writing, &mut content)?;
+                report.push(format!("Counted {} words in {} entries in {:?}", writing.words, writing.entries, page_path));
+            }
+            GeneratedPage::TimeLog => {
+                let count = worklog::write_time_log_page(src_path, &options.strings, stats, &relative, &mut content)?;
+                report.push(format!("Totalled {} sessions in {:?}", count, page_path));
+            }
+        }
+        write_if_changed(&page_path, &content, options.line_ending)?;
+    }
+    Ok(report)
+}
+
+/// Write a placeholder page with a draft banner for every draft chapter
+fn write_draft_pages(src_path: &Path, options: &Options, stats: &ScanStats) -> io::Result<()> {
+    let mut keep = HashSet::new();
+    for (title, page) in &stats.drafts {
+        let path = src_path.join(page);
+        let content = format!("# {}\n\n> {}\n", title, options.strings.draft_notice);
+        write_if_changed(&path, content.as_bytes(), options.line_ending)?;
+        keep.insert(path);
+    }
+    remove_stale(&src_path.join(options.drafts_dir()), &keep)
+}
+
+/// `mdbook-daily clean`: remove the generated pages directory, the
+/// book's `generated-dir` unless `--generated-dir` names another
+pub fn clean(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut dir = config::book_options(Path::new("."))?.generated_dir;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--generated-dir" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--generated-dir needs a directory"))?;
+                dir = config::generated_dir(value).map_err(|e| crate::usage_error(&e))?;
+            }
+            _ => return Err(crate::usage_error(&format!("unknown clean argument {:?}", arg))),
+        }
+    }
+    let path = src_path.join(dir);
+    // A symlinked directory could still lead out of src
+    if let (Ok(resolved), Ok(src)) = (fs::canonicalize(&path), fs::canonicalize(src_path)) {
+        if resolved == src || !resolved.starts_with(&src) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} is not inside {}", path.display(), src_path.display())));
+        }
+    }
+    if path.exists() {
+        fs::remove_dir_all(&path)?;
+        println!("Removed {:?}", path);
+    } else {
+        println!("Nothing to clean at {:?}", path);
+    }
+    Ok(())
+}
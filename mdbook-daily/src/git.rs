@@ -0,0 +1,87 @@
+use std::collections::{BTreeSet, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cache;
+use crate::date::{date_from_path, Date};
+
+/// Run git inside `dir` and return its stdout
+pub fn run(dir: &Path, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(io::Error::other(format!("git {}: {}", args.join(" "), message)));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The author who added each file under src, keyed by path relative to
+/// src. Files git does not know about are missing from the map; outside
+/// a git checkout the map is empty.
+pub fn authors(src_path: &Path) -> HashMap<PathBuf, String> {
+    let mut authors = HashMap::new();
+    let Ok(log) = run(src_path, &["log", "--diff-filter=A", "--format=%x00%an", "--name-only", "--relative", "--", "."]) else {
+        return authors;
+    };
+    // Commits are newest first, so the first add of a path is the current file's
+    for commit in log.split('\0').skip(1) {
+        let mut lines = commit.lines();
+        let Some(author) = lines.next() else { continue };
+        for path in lines.filter(|l| !l.is_empty()) {
+            authors.entry(PathBuf::from(path)).or_insert_with(|| author.to_string());
+        }
+    }
+    authors
+}
+
+/// The date of the commit that added each file under src, keyed by path
+/// relative to src. Like [`authors`], the map is empty outside a git
+/// checkout.
+pub fn creation_dates(src_path: &Path) -> HashMap<PathBuf, Date> {
+    let mut dates = HashMap::new();
+    let Ok(log) = run(src_path, &["log", "--diff-filter=A", "--format=%x00%ad", "--date=short", "--name-only", "--relative", "--", "."]) else {
+        return dates;
+    };
+    for commit in log.split('\0').skip(1) {
+        let mut lines = commit.lines();
+        let Some(date) = lines.next().and_then(date_from_path) else { continue };
+        for path in lines.filter(|l| !l.is_empty()) {
+            dates.entry(PathBuf::from(path)).or_insert(date);
+        }
+    }
+    dates
+}
+
+/// Paths with uncommitted changes in the checkout holding `dir`,
+/// untracked files included and the head cache left out, relative to
+/// the top of the checkout
+pub fn changed_paths(dir: &Path) -> io::Result<BTreeSet<String>> {
+    let status = run(dir, &["status", "--porcelain", "-z", "--untracked-files=all"])?;
+    let mut paths = BTreeSet::new();
+    let mut entries = status.split('\0');
+    while let Some(entry) = entries.next() {
+        let Some(path) = entry.get(3..).filter(|p| !p.is_empty()) else { continue };
+        // A rename or copy is followed by the path it came from
+        if entry.starts_with(['R', 'C']) {
+            entries.next();
+        }
+        // The head cache is rewritten on every run and is not content
+        if Path::new(path).file_name().is_some_and(|name| name == cache::FILE_NAME) {
+            continue;
+        }
+        paths.insert(path.to_string());
+    }
+    Ok(paths)
+}
+
+/// Stage `paths` (relative to the top of the checkout holding `dir`) and
+/// commit only them, leaving anything else that is staged alone
+pub fn commit(dir: &Path, paths: &[String], message: &str) -> io::Result<()> {
+    let top = run(dir, &["rev-parse", "--show-toplevel"])?;
+    let top = Path::new(top.trim());
+    let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+    run(top, &[&["add", "--"], paths.as_slice()].concat())?;
+    run(top, &[&["commit", "--quiet", "-m", message, "--"], paths.as_slice()].concat())?;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::eol::LineEnding;
+use crate::generated;
+use crate::summary::ScanStats;
+
+/// Handlebars comments delimiting the generated block in theme/head.hbs,
+/// so hand-written head content around it is kept
+const START: &str = "{{!-- mdbook-daily:start --}}";
+const END: &str = "{{!-- mdbook-daily:end --}}";
+
+/// Escape a value for a double-quoted HTML attribute in a Handlebars
+/// template
+fn escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace("{{", "\\{{")
+}
+
+/// One `{{#if (eq path ...)}}` block per chapter whose front matter sets
+/// `canonical` or `description`. mdBook renders head.hbs for every page
+/// with the chapter's source path as `path`.
+fn head_block(stats: &ScanStats) -> String {
+    let mut chapters: Vec<_> = stats.front_matter.iter()
+        .filter(|(_, front)| front.canonical.is_some() || front.description.is_some())
+        .collect();
+    chapters.sort_by(|a, b| a.0.cmp(b.0));
+    let mut block = String::new();
+    for (link, front) in chapters {
+        block.push_str(&format!("{{{{#if (eq path \"{}\")}}}}\n", escape(link.trim_start_matches("./"))));
+        if let Some(canonical) = &front.canonical {
+            block.push_str(&format!("<link rel=\"canonical\" href=\"{}\">\n", escape(canonical)));
+        }
+        if let Some(description) = &front.description {
+            block.push_str(&format!("<meta name=\"description\" content=\"{}\">\n", escape(description)));
+        }
+        block.push_str("{{/if}}\n");
+    }
+    block
+}
+
+/// Write the per-chapter `<link rel="canonical">` and `<meta
+/// name="description">` tags into `theme_dir/head.hbs`, replacing an
+/// earlier generated block and keeping the rest of the file. Returns a
+/// line describing the change, if one was made.
+pub fn write_head(theme_dir: &Path, stats: &ScanStats, ending: LineEnding) -> io::Result<Option<String>> {
+    let path = theme_dir.join("head.hbs");
+    let block = head_block(stats);
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    if block.is_empty() && !existing.contains(START) {
+        return Ok(None);
+    }
+    let region = format!("{}\n{}{}", START, block, END);
+    let content = match (existing.find(START), existing.find(END)) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}{}", &existing[..start], region, &existing[end + END.len()..])
+        }
+        _ if existing.is_empty() => format!("{}\n", region),
+        _ => format!("{}\n{}\n", existing.trim_end(), region),
+    };
+    let changed = generated::write_if_changed(&path, content.as_bytes(), ending)?;
+    let count = block.matches("{{/if}}").count();
+    Ok(changed.then(|| format!("Wrote head tags for {} chapters to {:?}", count, path)))
+}
@@ -0,0 +1,171 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Ignore file in the book root, one pattern per line
+pub const FILE_NAME: &str = ".mdbookdailyignore";
+
+/// One gitignore-style pattern, matched against src-relative paths
+struct Pattern {
+    /// `/`-separated components; `**` matches any number of them
+    components: Vec<String>,
+    /// A trailing `/` only matches directories
+    dir_only: bool,
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Option<Pattern> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() || pattern.starts_with('#') {
+            return None;
+        }
+        let dir_only = pattern.ends_with('/');
+        let trimmed = pattern.trim_end_matches('/');
+        // Like gitignore, a pattern without a slash matches at any depth
+        let anchored = trimmed.contains('/');
+        let mut components: Vec<String> = trimmed.trim_start_matches('/')
+            .split('/')
+            .filter(|c| !c.is_empty())
+            .map(str::to_string)
+            .collect();
+        if components.is_empty() {
+            return None;
+        }
+        if !anchored {
+            components.insert(0, "**".to_string());
+        }
+        Some(Pattern { components, dir_only })
+    }
+
+    fn matches(&self, path: &[String], is_dir: bool) -> bool {
+        (is_dir || !self.dir_only) && match_components(&self.components, path)
+    }
+}
+
+fn match_components(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((first, rest)) if first == "**" => (0..=path.len()).any(|i| match_components(rest, &path[i..])),
+        Some((first, rest)) => {
+            path.first().is_some_and(|name| match_wildcard(first, name)) && match_components(rest, &path[1..])
+        }
+    }
+}
+
+/// `*` and `?` wildcards within one path component
+fn match_wildcard(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            // Let the last `*` swallow one more character
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Why `pattern` would not match what it looks like it should, if it
+/// uses gitignore syntax the matcher does not support
+pub fn pattern_problem(pattern: &str) -> Option<String> {
+    let pattern = pattern.trim();
+    if pattern.starts_with('!') {
+        return Some("negated patterns are not supported".to_string());
+    }
+    if pattern.contains(['[', ']']) {
+        return Some("character classes are not supported, use `*` or `?`".to_string());
+    }
+    pattern.split('/')
+        .any(|c| c.contains("**") && c != "**")
+        .then(|| "`**` only works as a whole path component, as in `a/**/b`".to_string())
+}
+
+/// Files and directories left out of the summary: the configured
+/// `exclude` patterns plus those in .mdbookdailyignore
+#[derive(Default)]
+pub struct Ignore {
+    patterns: Vec<Pattern>,
+}
+
+impl Ignore {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Ignore {
+        Ignore { patterns: patterns.iter().filter_map(|p| Pattern::parse(p.as_ref())).collect() }
+    }
+
+    /// Whether a src-relative path, or any directory above it, matches
+    pub fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let components: Vec<String> = relative.components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        (1..=components.len()).any(|len| {
+            let dir = len < components.len() || is_dir;
+            self.patterns.iter().any(|p| p.matches(&components[..len], dir))
+        })
+    }
+}
+
+/// Patterns from the ignore file in `root`, if there is one
+pub fn read_file(root: &Path) -> io::Result<Vec<String>> {
+    match fs::read_to_string(root.join(FILE_NAME)) {
+        Ok(content) => Ok(content.lines().map(str::to_string).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ignored(patterns: &[&str], path: &str, is_dir: bool) -> bool {
+        Ignore::new(patterns).is_ignored(Path::new(path), is_dir)
+    }
+
+    #[test]
+    fn wildcards_stay_within_a_component() {
+        assert!(match_wildcard("*.md", "notes.md"));
+        assert!(match_wildcard("*", ""));
+        assert!(match_wildcard("2024-??-*", "2024-01-standup"));
+        assert!(match_wildcard("a*b*c", "aXbYbZc"));
+        assert!(!match_wildcard("a*b*c", "aXbYbZ"));
+        assert!(!match_wildcard("?", ""));
+        assert!(!ignored(&["essay/*.md"], "essay/2024/0101.md", false));
+        assert!(ignored(&["essay/*.md"], "essay/0101.md", false));
+    }
+
+    #[test]
+    fn double_stars_match_any_number_of_components() {
+        assert!(ignored(&["essay/**/draft-*"], "essay/draft-1.md", false));
+        assert!(ignored(&["essay/**/draft-*"], "essay/2024/01/draft-1.md", false));
+        assert!(!ignored(&["essay/**/draft-*"], "techlog/draft-1.md", false));
+        assert!(ignored(&["**/private"], "private/a.md", false));
+        assert!(ignored(&["essay/**"], "essay/2024/0101.md", false));
+    }
+
+    #[test]
+    fn unanchored_and_directory_patterns() {
+        // Without a slash a pattern matches at any depth
+        assert!(ignored(&["*.tmp.md"], "essay/2024/x.tmp.md", false));
+        // Anything under an ignored directory is ignored
+        assert!(ignored(&["scratch/"], "essay/scratch/a.md", false));
+        assert!(!ignored(&["scratch/"], "essay/scratch", false));
+        assert!(ignored(&["/essay"], "essay/a.md", false));
+        assert!(!ignored(&["/essay"], "notes/essay/a.md", false));
+        assert!(!ignored(&["# comment", ""], "# comment", false));
+    }
+}
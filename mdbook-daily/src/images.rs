@@ -0,0 +1,89 @@
+use std::path::Path;
+
+/// Decode `%XX` escapes in a link target, leaving malformed ones as is
+fn percent_decode(target: &str) -> String {
+    let bytes = target.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%')
+            .then(|| target.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Whether an image target names a local file (not a URL or data URI)
+fn is_local(target: &str) -> bool {
+    !target.is_empty() && !target.contains("://") && !target.starts_with("data:") && !target.starts_with('/')
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Visible stand-in for an image whose file is missing
+fn placeholder(alt: &str, target: &str) -> String {
+    format!(
+        "<span style=\"border: 1px dashed #c33; color: #c33; padding: 0 .3em\">missing image: {}{}</span>",
+        escape_html(target),
+        if alt.is_empty() { String::new() } else { format!(" ({})", escape_html(alt)) }
+    )
+}
+
+/// Replace `![alt](target)` images whose local file does not exist,
+/// resolved against `chapter_dir`, with a placeholder. Images in fenced
+/// or inline code are left alone. Returns the new content and the
+/// missing targets.
+pub fn mark_missing_images(content: &str, chapter_dir: &Path) -> (String, Vec<String>) {
+    let mut out = String::with_capacity(content.len());
+    let mut missing = Vec::new();
+    let mut in_fence = false;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if in_fence || !line.contains("![") {
+            out.push_str(line);
+            continue;
+        }
+        let mut rest = line;
+        while let Some(pos) = rest.find("![") {
+            let (before, image) = rest.split_at(pos);
+            out.push_str(before);
+            let in_code = out.rsplit('\n').next().unwrap_or("").matches('`').count() % 2 == 1;
+            let parsed = image[2..].find("](").and_then(|alt_end| {
+                let end = image[alt_end + 4..].find(')')? + alt_end + 4;
+                Some((&image[2..alt_end + 2], &image[alt_end + 4..end], end + 1))
+            });
+            let Some((alt, inner, len)) = parsed.filter(|_| !in_code) else {
+                out.push_str("![");
+                rest = &image[2..];
+                continue;
+            };
+            let target = inner.split_whitespace().next().unwrap_or("").trim_start_matches('<').trim_end_matches('>');
+            let file = target.split(['#', '?']).next().unwrap_or("");
+            if is_local(file) && !chapter_dir.join(percent_decode(file)).exists() {
+                out.push_str(&placeholder(alt, target));
+                missing.push(target.to_string());
+            } else {
+                out.push_str(&image[..len]);
+            }
+            rest = &image[len..];
+        }
+        out.push_str(rest);
+    }
+    (out, missing)
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use toml::value::Value;
+
+use crate::frontmatter;
+use crate::summary::{self, Options, ScanStats, SummaryEntry};
+
+/// Front matter to write into one note so the generated summary keeps a
+/// hand-written title and position
+pub struct Import {
+    /// Note relative to src
+    pub path: PathBuf,
+    pub title: Option<String>,
+    pub order: Option<i64>,
+}
+
+/// Note paths (relative to src) of an existing summary's file entries,
+/// in order, leaving out READMEs, generated pages and missing files
+fn note_entries<'a>(src_path: &Path, options: &Options, entries: &'a [SummaryEntry]) -> Vec<(PathBuf, &'a str)> {
+    entries.iter()
+        .filter_map(|entry| {
+            let link = entry.path.as_deref()?;
+            let path = PathBuf::from(link.strip_prefix("./").unwrap_or(link));
+            let note = path.extension().is_some_and(|e| e == "md")
+                && path.file_name().is_some_and(|n| n != "README.md")
+                && !path.starts_with(&options.generated_dir)
+                && src_path.join(&path).is_file();
+            note.then_some((path, entry.title.as_str()))
+        })
+        .collect()
+}
+
+/// What to import from the hand-written summary `existing`: a `title`
+/// for each note it names differently than the generator would, and an
+/// `order` for every note of a directory whose notes it lists in another
+/// order. Directories themselves keep the generated order.
+pub fn plan(src_path: &Path, options: &Options, existing: &str) -> io::Result<Vec<Import>> {
+    let old_entries = summary::parse_entries(existing);
+    let old = note_entries(src_path, options, &old_entries);
+    let generated_entries = summary::parse_entries(&summary::generate_summary(src_path, options, &mut ScanStats::default())?);
+    let generated = note_entries(src_path, options, &generated_entries);
+    let generated_titles: HashMap<&Path, &str> = generated.iter().map(|(p, t)| (p.as_path(), *t)).collect();
+
+    // Notes by directory, in each summary's order, counting only notes
+    // listed in both
+    let by_dir = |notes: &[(PathBuf, &str)]| {
+        let mut dirs: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        for (path, _) in notes {
+            if old.iter().any(|(p, _)| p == path) && generated_titles.contains_key(path.as_path()) {
+                dirs.entry(path.parent().unwrap_or(Path::new("")).to_path_buf()).or_default().push(path.clone());
+            }
+        }
+        dirs
+    };
+    let old_dirs = by_dir(&old);
+    let generated_dirs = by_dir(&generated);
+
+    let mut imports = Vec::new();
+    for (path, title) in &old {
+        let Some(generated_title) = generated_titles.get(path.as_path()) else { continue };
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let order = match (old_dirs.get(dir), generated_dirs.get(dir)) {
+            (Some(old_order), Some(generated_order)) if old_order != generated_order => {
+                old_order.iter().position(|p| p == path).map(|i| i as i64 + 1)
+            }
+            _ => None,
+        };
+        let title = (title != generated_title).then(|| title.to_string());
+        if title.is_some() || order.is_some() {
+            imports.push(Import { path: path.clone(), title, order });
+        }
+    }
+    Ok(imports)
+}
+
+/// Write the planned front matter into the notes
+pub fn apply(src_path: &Path, imports: &[Import]) -> io::Result<()> {
+    for import in imports {
+        let path = src_path.join(&import.path);
+        let mut keys = Vec::new();
+        if let Some(title) = &import.title {
+            keys.push(("title", Value::String(title.clone())));
+        }
+        if let Some(order) = import.order {
+            keys.push(("order", Value::Integer(order)));
+        }
+        let content = fs::read_to_string(&path)?;
+        fs::write(&path, frontmatter::set_keys(&content, &keys))?;
+    }
+    Ok(())
+}
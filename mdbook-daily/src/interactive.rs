@@ -0,0 +1,186 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+use crate::summary::{escape_title, parse_entries, SummaryEntry};
+
+/// Ask a yes/no question on stdin, defaulting to no
+pub fn confirm(question: &str) -> io::Result<bool> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Ask for a value on stdin, keeping `default` when the answer is empty
+pub fn ask(question: &str, default: &str) -> io::Result<String> {
+    print!("{} [{}] ", question, default);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    let answer = answer.trim();
+    Ok(if answer.is_empty() { default.to_string() } else { answer.to_string() })
+}
+
+/// Show the planned changes per category and build the content to write
+/// from the categories the user accepted. Returns `None` when there is
+/// nothing left to write.
+pub fn confirm_changes(old_content: &str, new_content: &str) -> io::Result<Option<String>> {
+    review_changes(old_content, new_content, &mut confirm)
+}
+
+/// [`confirm_changes`] with the yes/no questions put to `answer`
+fn review_changes(
+    old_content: &str,
+    new_content: &str,
+    answer: &mut dyn FnMut(&str) -> io::Result<bool>,
+) -> io::Result<Option<String>> {
+    let old_entries = parse_entries(old_content);
+    let new_entries = parse_entries(new_content);
+    let old_by_key: HashMap<String, &SummaryEntry> = old_entries.iter()
+        .map(|e| (e.key(), e))
+        .collect();
+    let new_keys: HashSet<String> = new_entries.iter().map(|e| e.key()).collect();
+
+    let additions: Vec<&SummaryEntry> = new_entries.iter()
+        .filter(|e| !old_by_key.contains_key(&e.key()))
+        .collect();
+    let removals: Vec<&SummaryEntry> = old_entries.iter()
+        .filter(|e| !new_keys.contains(&e.key()))
+        .collect();
+    let retitles: Vec<(&SummaryEntry, &SummaryEntry)> = new_entries.iter()
+        .filter_map(|e| old_by_key.get(&e.key()).map(|old| (*old, e)))
+        .filter(|(old, new)| old.title != new.title)
+        .collect();
+
+    if additions.is_empty() && removals.is_empty() && retitles.is_empty() {
+        return Ok(None);
+    }
+
+    let mut accept_additions = true;
+    let mut accept_removals = true;
+    let mut accept_retitles = true;
+
+    if !additions.is_empty() {
+        println!("Additions:");
+        for e in &additions {
+            println!("  + [{}]({})", e.title, e.path.as_deref().unwrap_or(""));
+        }
+        accept_additions = answer(&format!("Apply {} addition(s)?", additions.len()))?;
+    }
+    if !removals.is_empty() {
+        println!("Removals:");
+        for e in &removals {
+            println!("  - [{}]({})", e.title, e.path.as_deref().unwrap_or(""));
+        }
+        accept_removals = answer(&format!("Apply {} removal(s)?", removals.len()))?;
+    }
+    if !retitles.is_empty() {
+        println!("Retitles:");
+        for (old, new) in &retitles {
+            println!("  ~ {}: \"{}\" -> \"{}\"", new.key(), old.title, new.title);
+        }
+        accept_retitles = answer(&format!("Apply {} retitle(s)?", retitles.len()))?;
+    }
+
+    if !accept_additions && !accept_removals && !accept_retitles {
+        return Ok(None);
+    }
+
+    // Start from the new content and undo the declined categories
+    let new_by_line: HashMap<usize, &SummaryEntry> = new_entries.iter()
+        .map(|e| (e.line, e))
+        .collect();
+    let mut lines: Vec<(Option<String>, String)> = Vec::new();
+    for (i, line) in new_content.lines().enumerate() {
+        match new_by_line.get(&i) {
+            Some(entry) => {
+                let old = old_by_key.get(&entry.key());
+                if old.is_none() && !accept_additions {
+                    continue;
+                }
+                let text = match old {
+                    Some(old) if !accept_retitles && old.title != entry.title => {
+                        let marker = if entry.numbered { "- " } else { "" };
+                        format!("{}{}[{}]({})", entry.indent, marker, escape_title(&old.title),
+                            entry.path.as_deref().unwrap_or(""))
+                    }
+                    _ => line.to_string(),
+                };
+                lines.push((Some(entry.key()), text));
+            }
+            None => lines.push((None, line.to_string())),
+        }
+    }
+
+    if !accept_removals {
+        // Put each removed entry back after the subtree of the entry that
+        // preceded it in the old summary
+        let old_lines: Vec<&str> = old_content.lines().collect();
+        for removed in &removals {
+            let predecessor = old_entries.iter()
+                .take_while(|e| e.line < removed.line)
+                .filter(|e| lines.iter().any(|(k, _)| k.as_deref() == Some(e.key().as_str())))
+                .last();
+            let position = match predecessor {
+                Some(pred) => {
+                    let pred_key = pred.key();
+                    let start = lines.iter()
+                        .position(|(k, _)| k.as_deref() == Some(pred_key.as_str()))
+                        .unwrap_or(0);
+                    let mut end = start + 1;
+                    while let Some((Some(_), text)) = lines.get(end) {
+                        let depth = text.len() - text.trim_start().len();
+                        if depth <= pred.indent.len() {
+                            break;
+                        }
+                        end += 1;
+                    }
+                    end
+                }
+                None => lines.iter().position(|(k, _)| k.is_some()).unwrap_or(lines.len()),
+            };
+            lines.insert(position, (Some(removed.key()), old_lines[removed.line].to_string()));
+        }
+    }
+
+    let mut content: Vec<String> = lines.into_iter().map(|(_, text)| text).collect();
+    if new_content.ends_with('\n') {
+        content.push(String::new());
+    }
+    Ok(Some(content.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "# Summary\n\n- [Monday](./journal/1012.md)\n- [Tusday](./journal/1013.md)\n- [Gone](./journal/1001.md)\n";
+    const NEW: &str = "# Summary\n\n- [Monday](./journal/1012.md)\n- [Tuesday](./journal/1013.md)\n- [Wednesday](./journal/1014.md)\n";
+
+    /// The content written when the questions are answered in turn
+    fn review(answers: &[bool]) -> Option<String> {
+        let mut answers = answers.iter();
+        review_changes(OLD, NEW, &mut |_| Ok(*answers.next().unwrap())).unwrap()
+    }
+
+    #[test]
+    fn accepting_everything_writes_the_new_summary() {
+        assert_eq!(review(&[true, true, true]).as_deref(), Some(NEW));
+    }
+
+    #[test]
+    fn rejecting_everything_writes_nothing() {
+        assert_eq!(review(&[false, false, false]), None);
+        assert_eq!(review_changes(NEW, NEW, &mut |_| panic!("nothing to ask")).unwrap(), None);
+    }
+
+    #[test]
+    fn declined_categories_are_undone() {
+        // Additions accepted, the removal and the retitle declined
+        assert_eq!(
+            review(&[true, false, false]).as_deref(),
+            Some("# Summary\n\n- [Monday](./journal/1012.md)\n- [Tusday](./journal/1013.md)\n- [Gone](./journal/1001.md)\n- [Wednesday](./journal/1014.md)\n"),
+        );
+    }
+}
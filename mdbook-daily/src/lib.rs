@@ -0,0 +1,191 @@
+//! Generates SUMMARY.md for a dated mdBook journal. The `mdbook-daily`
+//! binary is a thin command line over this crate; other tools can call
+//! [`SummaryBuilder`] directly.
+
+pub mod acronyms;
+pub mod adopt;
+pub mod aliases;
+pub mod archive;
+pub mod assets;
+pub mod bench;
+pub mod builder;
+pub mod cache;
+pub mod calendar;
+pub mod capture;
+pub mod changes;
+pub mod check;
+pub mod config;
+pub mod daemon;
+pub mod date;
+pub mod diff;
+pub mod digest;
+pub mod directives;
+pub mod eol;
+pub mod explain;
+pub mod feed;
+pub mod frontmatter;
+pub mod generated;
+pub mod git;
+pub mod head;
+pub mod ignore;
+pub mod images;
+pub mod import;
+pub mod interactive;
+pub mod link;
+pub mod lint;
+pub mod links;
+pub mod list;
+pub mod locale;
+pub mod manifest;
+pub mod navigation;
+pub mod new;
+pub mod notify;
+pub mod onthisday;
+pub mod partial;
+pub mod plan;
+pub mod preprocessor;
+pub mod print;
+pub mod promote;
+pub mod recent;
+pub mod related;
+pub mod render;
+pub mod reveal;
+pub mod rollup;
+pub mod rpc;
+pub mod series;
+pub mod sources;
+pub mod stats;
+pub mod summary;
+pub mod tags;
+pub mod team;
+pub mod timings;
+pub mod titles;
+pub mod tree;
+pub mod url;
+pub mod validate;
+pub mod vault;
+pub mod wikilinks;
+pub mod worklog;
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use summary::{Options, ScanStats};
+
+pub use builder::SummaryBuilder;
+pub use summary::{Candidate, Filter, Sorter};
+pub use render::SummaryRenderer;
+pub use tree::{BookTree, Change, Chapter, Part, MODEL_VERSION};
+
+const USAGE: &str = "\
+Usage: mdbook-daily update [--interactive] [--timings] [--max-file-size BYTES]
+                           [--head-size BYTES]
+                           [--year-separators] [--print-summary PATH]
+                           [--exclude-older-than AGE] [--archive-page]
+                           [--plain-titles DIR]... [--acronym-index]
+                           [--generated-dir DIR] [--changes-since REV] [--team]
+                           [--notify-webhook URL] [--site-url URL] [--read-only-sources]
+                           [--only DIR] [--hydrate-placeholders] [--include-drafts]
+                           [--line-endings lf|crlf] [--section-markers] [--time-log]
+                           [--tag-index] [--tag-pages] [--series-pages] [--calendar]
+                           [--stats-page] [--recent N] [--on-this-day]
+                           [--feed N] [--feed-html] [--feed-sections]
+                           [--jobs N] [--reads-per-second N]
+                           [--import-existing-summary] [--validate]
+                           [--dry-run | --diff] [--strict] [--commit]
+       mdbook-daily daemon [--schedule CRON] [--poll SECONDS]
+                           [--status-addr ADDR] [--health-log PATH] [--install]
+       mdbook-daily link QUERY... [--from FILE] [--copy]
+       mdbook-daily list [--format paths|fzf] [--author NAME]
+       mdbook-daily rpc [--read-only-sources]
+       mdbook-daily explain FILE
+       mdbook-daily stats [--json]
+       mdbook-daily tree [--format json|yaml|summary|mkdocs]
+       mdbook-daily rollup [--period week|month]
+       mdbook-daily reveal [today|yesterday|YYYY-MM-DD|PATH] [--browser]
+                           [--site-url URL] [--print]
+       mdbook-daily clean [--generated-dir DIR]
+       mdbook-daily changes REV
+       mdbook-daily digest [--date today|yesterday|YYYY-MM-DD] [--site-url URL]
+       mdbook-daily lint [--fix]
+       mdbook-daily new [--date YYYY-MM-DD] [--section NAME] [--template NAME]
+       mdbook-daily plan [--week this|next|YYYY-MM-DD] [--section NAME]
+       mdbook-daily log start LABEL | stop [LABEL] [--section NAME]
+       mdbook-daily capture [--type NAME] [--section NAME] TEXT...
+       mdbook-daily bench --synthetic N
+       mdbook-daily adopt DIR [--move|--link] [--yes]
+       mdbook-daily import --from obsidian|logseq VAULT [--section NAME]
+                           [--assets DIR] [--dry-run]
+       mdbook-daily check [--format text|json]
+       mdbook-daily config check | print [--effective]
+       mdbook-daily dedupe [--dry-run]
+       mdbook-daily archive --older-than AGE | [--tag TAG] [--status STATUS]
+                            [--to DIR] [--dry-run]
+       mdbook-daily promote FILE
+       mdbook-daily supports RENDERER
+       mdbook-daily                 (preprocessor mode, reads mdBook JSON on stdin)";
+
+/// How a command failed when it is not an ordinary I/O error. The
+/// binary looks for it in the error a command returns to pick the exit
+/// code, and is the only place that exits.
+#[derive(Debug)]
+pub enum Failure {
+    /// The arguments are wrong; shown with the usage text
+    Usage(String),
+    /// The command already reported what went wrong, such as the
+    /// problems `check` found
+    Reported,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::Usage(message) => write!(f, "{}\n{}", message, USAGE),
+            Failure::Reported => f.write_str("failed"),
+        }
+    }
+}
+
+impl std::error::Error for Failure {}
+
+impl Failure {
+    /// The failure carried by `error`, if it is one
+    pub fn of(error: &io::Error) -> Option<&Failure> {
+        error.get_ref().and_then(|inner| inner.downcast_ref())
+    }
+}
+
+/// An argument error, for a command to return
+pub fn usage_error(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, Failure::Usage(message.to_string()))
+}
+
+/// An error for a command that printed its own report of what failed
+pub fn reported_failure() -> io::Error {
+    io::Error::other(Failure::Reported)
+}
+
+/// Regenerate SUMMARY.md from the src directory. The file is replaced
+/// atomically, and not touched at all when its content would not change,
+/// so `mdbook serve` does not rebuild for nothing. Returns whether it
+/// changed.
+pub fn write_summary(src_path: &Path, options: &Options, stats: &mut ScanStats) -> io::Result<bool> {
+    let summary_path = src_path.join("SUMMARY.md");
+    // A hand-maintained summary only has its managed region replaced
+    let existing = fs::read_to_string(&summary_path).ok();
+    let content = summary::merge_managed(existing.as_deref(), summary::generate_summary(src_path, options, stats)?);
+    stats.check_strict(options.strict)?;
+    generated::write_if_changed(&summary_path, content.as_bytes(), options.line_ending)
+}
+
+/// Parse a byte count with an optional K or M suffix
+pub fn parse_size(value: &str) -> Option<u64> {
+    let (digits, multiplier) = match value.as_bytes().last()? {
+        b'k' | b'K' => (&value[..value.len() - 1], 1024),
+        b'm' | b'M' => (&value[..value.len() - 1], 1024 * 1024),
+        _ => (value, 1),
+    };
+    digits.parse::<u64>().ok().map(|n| n * multiplier)
+}
@@ -0,0 +1,152 @@
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::config;
+use crate::date::date_from_path;
+use crate::summary::{self, SummaryEntry};
+
+/// Score how well `query` matches `candidate`; higher is better.
+/// Substring matches beat scattered subsequence matches.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query = query.to_lowercase();
+    let candidate = candidate.to_lowercase();
+    if let Some(pos) = candidate.find(&query) {
+        return Some(1000 - pos as i64);
+    }
+    let mut score = 0;
+    let mut last_match: Option<usize> = None;
+    let mut chars = candidate.char_indices();
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let (i, _) = chars.by_ref().find(|(_, c)| *c == q)?;
+        score += match last_match {
+            Some(last) if i == last + 1 => 10,
+            _ => 1,
+        };
+        last_match = Some(i);
+    }
+    Some(score)
+}
+
+/// Text an entry is matched against: title, path and date
+fn haystack(entry: &SummaryEntry) -> String {
+    let path = entry.path.as_deref().unwrap_or("");
+    match date_from_path(path) {
+        Some(date) => format!("{} {} {}", entry.title, date, path),
+        None => format!("{} {}", entry.title, path),
+    }
+}
+
+/// Best matching entry with a link for the query
+pub fn find_entry<'a>(entries: &'a [SummaryEntry], query: &str) -> Option<&'a SummaryEntry> {
+    entries.iter()
+        .filter(|e| e.path.is_some())
+        .filter_map(|e| fuzzy_score(query, &haystack(e)).map(|score| (score, e)))
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, e)| e)
+}
+
+/// Relative path from directory `from_dir` to `target`, both relative to
+/// the same root
+pub fn relative_link(from_dir: &Path, target: &Path) -> String {
+    let from: Vec<Component> = from_dir.components().filter(|c| *c != Component::CurDir).collect();
+    let to: Vec<Component> = target.components().filter(|c| *c != Component::CurDir).collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(to[common..].iter().map(|c| c.as_os_str().to_string_lossy().into_owned()));
+    let link = parts.join("/");
+    if link.starts_with("..") { link } else { format!("./{}", link) }
+}
+
+/// Hand text to the first clipboard tool that is available
+fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let tools: [&[&str]; 5] = [
+        &["pbcopy"],
+        &["wl-copy"],
+        &["xclip", "-selection", "clipboard"],
+        &["xsel", "--clipboard", "--input"],
+        &["clip"],
+    ];
+    for tool in tools {
+        let Ok(mut child) = Command::new(tool[0]).args(&tool[1..]).stdin(Stdio::piped()).spawn() else {
+            continue;
+        };
+        if let Some(stdin) = child.stdin.as_mut() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::NotFound, "no clipboard tool found"))
+}
+
+/// A note found for a link query
+pub struct ResolvedLink {
+    pub title: String,
+    /// Path of the note relative to src
+    pub path: PathBuf,
+    /// `[title](relative/link.md)`
+    pub markdown: String,
+}
+
+/// Find the note best matching `query` and build a link to it from the
+/// directory of `from`, or from the src root when no file is given
+pub fn resolve(src_path: &Path, query: &str, from: Option<&Path>) -> io::Result<Option<ResolvedLink>> {
+    let entries = summary::book_entries(src_path, &config::book_options(Path::new("."))?)?;
+    let Some(entry) = find_entry(&entries, query) else {
+        return Ok(None);
+    };
+    let target = entry.path.as_deref().unwrap_or_default();
+    let target = Path::new(target.strip_prefix("./").unwrap_or(target));
+
+    let from_dir = match from {
+        Some(file) => {
+            let src = src_path.canonicalize()?;
+            let file = file.canonicalize()?;
+            let dir = file.parent().unwrap_or(&file);
+            dir.strip_prefix(&src)
+                .map(Path::to_path_buf)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "from file is not inside src"))?
+        }
+        None => PathBuf::new(),
+    };
+
+    Ok(Some(ResolvedLink {
+        title: entry.title.clone(),
+        path: target.to_path_buf(),
+        markdown: format!("[{}]({})", summary::escape_title(&entry.title), relative_link(&from_dir, target)),
+    }))
+}
+
+/// `mdbook-daily link <query> [--from FILE] [--copy]`
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut query = Vec::new();
+    let mut from = None;
+    let mut copy = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--from needs a file"))?;
+                from = Some(PathBuf::from(value));
+            }
+            "--copy" => copy = true,
+            _ => query.push(arg.as_str()),
+        }
+    }
+    if query.is_empty() {
+        return Err(crate::usage_error("link needs a query"));
+    }
+    let query = query.join(" ");
+
+    let Some(resolved) = resolve(src_path, &query, from.as_deref())? else {
+        eprintln!("No entry matches {:?}", query);
+        return Err(crate::reported_failure());
+    };
+    println!("{}", resolved.markdown);
+    if copy {
+        copy_to_clipboard(&resolved.markdown)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::link::relative_link;
+
+/// Targets of inline markdown links and images (`[text](target)`) that
+/// point at local files, with any `#fragment` removed
+pub fn markdown_links(content: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    let mut rest = content;
+    while let Some(pos) = rest.find("](") {
+        rest = &rest[pos + 2..];
+        let Some(end) = rest.find(')') else { break };
+        let target = rest[..end].split_whitespace().next().unwrap_or("");
+        let target = target.trim_start_matches('<').trim_end_matches('>');
+        let target = target.split('#').next().unwrap_or("");
+        if !target.is_empty() && !target.contains("://") && !target.starts_with("mailto:") {
+            links.push(target.to_string());
+        }
+        rest = &rest[end..];
+    }
+    links
+}
+
+/// Resolve a link target against the directory containing the linking
+/// file, collapsing `.` and `..` without touching the filesystem
+pub fn resolve_link(from_file: &Path, target: &str) -> PathBuf {
+    let base = from_file.parent().unwrap_or(Path::new(""));
+    let mut resolved = PathBuf::new();
+    for component in base.join(target).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            other => resolved.push(other),
+        }
+    }
+    resolved
+}
+
+/// Relative link from directory `from_dir` to the chapter at `target`
+/// (relative to src) for text mdBook renders. A `README.md` chapter is
+/// linked as `index.md`, as mdBook's index preprocessor renames it.
+pub fn chapter_link(from_dir: &Path, target: &Path) -> String {
+    let target = match target.file_name() {
+        Some(name) if name == "README.md" => target.with_file_name("index.md"),
+        _ => target.to_path_buf(),
+    };
+    relative_link(from_dir, &target)
+}
+
+/// Every `.md` file under `dir`, sorted, excluding SUMMARY.md
+pub fn markdown_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|e| e == "md")
+                && path.file_name().is_some_and(|n| n != "SUMMARY.md")
+            {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
@@ -0,0 +1,182 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use toml::Value;
+
+use crate::config;
+use crate::date::Date;
+use crate::frontmatter;
+use crate::links::markdown_files;
+use crate::summary;
+
+/// An ATX heading: line index, level and text
+pub struct Heading {
+    pub line: usize,
+    pub level: usize,
+    pub text: String,
+}
+
+/// ATX headings outside fenced code, from line `start` on
+pub fn headings(lines: &[&str], start: usize) -> Vec<Heading> {
+    let mut found = Vec::new();
+    let mut in_fence = false;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || !line.starts_with('#') {
+            continue;
+        }
+        let rest = line.trim_start_matches('#');
+        let level = line.len() - rest.len();
+        if level <= 6 && (rest.is_empty() || rest.starts_with(' ')) {
+            found.push(Heading { line: i, level, text: rest.trim().trim_end_matches('#').trim().to_string() });
+        }
+    }
+    found
+}
+
+/// Problems with a note's headings, by line number, and the content with
+/// them fixed: a missing H1 is added from `title`, extra H1s become H2s
+/// and skipped levels are closed up. Dated notes may use any H1; other
+/// notes must match their title, ignoring case. Front matter is left as
+/// it is.
+fn check(content: &str, title: &str, dated: bool) -> (Vec<(usize, String)>, String) {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let borrowed: Vec<&str> = content.lines().collect();
+    let body = frontmatter::split(content).1;
+    let start = content[..content.len() - body.len()].lines().count();
+    let found = headings(&borrowed, start);
+    let mut problems = Vec::new();
+
+    let h1s: Vec<&Heading> = found.iter().filter(|h| h.level == 1).collect();
+    match h1s.first() {
+        None => problems.push((start + 1, format!("no H1 heading, expected \"# {}\"", title))),
+        Some(h1) if !dated && h1.text.to_lowercase() != title.to_lowercase() => {
+            problems.push((h1.line + 1, format!("H1 \"{}\" does not match the title \"{}\"", h1.text, title)));
+        }
+        Some(_) => {}
+    }
+
+    let mut previous = 0;
+    let mut seen_h1 = false;
+    for heading in &found {
+        let mut level = heading.level;
+        if level == 1 {
+            if seen_h1 {
+                problems.push((heading.line + 1, "more than one H1 heading".to_string()));
+                level = 2;
+            }
+            seen_h1 = true;
+        }
+        if previous > 0 && level > previous + 1 {
+            problems.push((heading.line + 1, format!("heading level jumps from {} to {}", previous, level)));
+            level = previous + 1;
+        }
+        if level != heading.level {
+            lines[heading.line] = format!("{} {}", "#".repeat(level), heading.text);
+        }
+        previous = level;
+    }
+
+    if h1s.is_empty() {
+        lines.insert(start, format!("# {}", title));
+        if lines.get(start + 1).is_some_and(|l| !l.is_empty()) {
+            lines.insert(start + 1, String::new());
+        }
+    }
+    let mut fixed = lines.join("\n");
+    fixed.push('\n');
+    (problems, fixed)
+}
+
+/// The line of a front matter `date` other than `path_date`, the date in
+/// the note's path, and the content with the front matter set to it
+fn check_date(content: &str, path_date: Date) -> Option<(usize, Date, String)> {
+    let date = frontmatter::split(content).0?.date.filter(|date| *date != path_date)?;
+    let line = content.lines()
+        .position(|line| line.split([':', '=']).next().is_some_and(|key| key.trim() == "date"))
+        .map_or(1, |i| i + 1);
+    Some((line, date, frontmatter::set_keys(content, &[("date", Value::String(path_date.to_string()))])))
+}
+
+/// `mdbook-daily lint [--fix]`: check that every note has one H1
+/// following the title convention and no skipped heading levels and
+/// that front matter dates match the date in the path, and list notes
+/// past their front matter `expires` date that are still in src. Fixing
+/// a date sets the front matter to the path's date.
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let fix = match args {
+        [] => false,
+        [flag] if flag == "--fix" => true,
+        _ => return Err(crate::usage_error("lint only accepts --fix")),
+    };
+    let options = config::book_options(Path::new("."))?;
+    let mut remaining = 0;
+    for entry in summary::book_entries(src_path, &options)? {
+        let Some(link) = &entry.path else { continue };
+        let relative = Path::new(link.strip_prefix("./").unwrap_or(link));
+        if relative.starts_with(&options.generated_dir) {
+            continue;
+        }
+        let path = src_path.join(relative);
+        let content = fs::read_to_string(&path)?;
+        let dated = options.entry_date(link).is_some();
+        let (mut problems, mut fixed) = check(&content, &entry.title, dated);
+        if let Some(path_date) = options.path_date(link.strip_prefix("./").unwrap_or(link)) {
+            if let Some((line, date, updated)) = check_date(&fixed, path_date) {
+                problems.push((line, format!("front matter date {} does not match {} in the path", date, path_date)));
+                fixed = updated;
+            }
+        }
+        for (line, message) in &problems {
+            println!("{}:{}: {}", path.display(), line, message);
+        }
+        if fix && fixed != content {
+            fs::write(&path, &fixed)?;
+            println!("Fixed {}", path.display());
+            // A mismatched H1 is reported but left for the author to resolve
+            remaining += check(&fixed, &entry.title, dated).0.len();
+        } else {
+            remaining += problems.len();
+        }
+    }
+    // Expired notes are already left out of the summary; deleting them is
+    // left to the author
+    for path in markdown_files(src_path) {
+        let Ok(content) = fs::read_to_string(&path) else { continue };
+        let expires = frontmatter::split(&content).0.filter(|f| f.is_expired()).and_then(|f| f.expires);
+        if let Some(expires) = expires {
+            println!("{}:1: expired after {}, no longer listed", path.display(), expires);
+            remaining += 1;
+        }
+    }
+    if remaining > 0 {
+        return Err(crate::reported_failure());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn front_matter_date_follows_the_path() {
+        let content = "---\ntitle: Trip\ndate: 2026-03-02\n---\n# Trip\n";
+        let (line, date, fixed) = check_date(content, Date::new(2026, 3, 1).unwrap()).unwrap();
+        assert_eq!((line, date), (3, Date::new(2026, 3, 2).unwrap()));
+        assert_eq!(fixed, "---\ntitle: Trip\ndate: \"2026-03-01\"\n---\n# Trip\n");
+        assert!(check_date(&fixed, Date::new(2026, 3, 1).unwrap()).is_none());
+    }
+
+    #[test]
+    fn missing_h1_goes_after_front_matter() {
+        let (problems, fixed) = check("---\ntags: [x]\n# not a heading\n---\nBody\n", "Note", false);
+        assert_eq!(problems, [(5, "no H1 heading, expected \"# Note\"".to_string())]);
+        assert_eq!(fixed, "---\ntags: [x]\n# not a heading\n---\n# Note\n\nBody\n");
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::config;
+use crate::git;
+use crate::summary;
+
+/// `mdbook-daily list [--format paths|fzf] [--author NAME]`
+///
+/// `paths` prints one file path per line; `fzf` prints tab-separated
+/// path, title, date and tags for piping into a fuzzy finder. `--author`
+/// keeps only entries added by that git author.
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut format = "paths".to_string();
+    let mut author = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = args.next()
+                    .ok_or_else(|| crate::usage_error("--format needs a value"))?
+                    .clone();
+            }
+            "--author" => {
+                author = Some(args.next().ok_or_else(|| crate::usage_error("--author needs a name"))?);
+            }
+            _ => return Err(crate::usage_error(&format!("unknown list argument {:?}", arg))),
+        }
+    }
+    if format != "paths" && format != "fzf" {
+        return Err(crate::usage_error(&format!("unknown list format {:?}", format)));
+    }
+
+    let authors = if author.is_some() { git::authors(src_path) } else { HashMap::new() };
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let options = config::book_options(Path::new("."))?;
+    for entry in summary::book_entries(src_path, &options)? {
+        let Some(link) = &entry.path else { continue };
+        let relative = Path::new(link.strip_prefix("./").unwrap_or(link));
+        if author.is_some_and(|a| authors.get(relative) != Some(a)) {
+            continue;
+        }
+        let path = src_path.join(relative);
+        if format == "paths" {
+            writeln!(out, "{}", path.display())?;
+        } else {
+            let date = options.entry_date(link).map(|d| d.to_string()).unwrap_or_default();
+            // Tags are not tracked yet, so the column is always empty
+            writeln!(out, "{}\t{}\t{}\t", path.display(), entry.title, date)?;
+        }
+    }
+    out.flush()
+}
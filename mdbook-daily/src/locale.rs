@@ -0,0 +1,173 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::date::Date;
+
+/// Locales shipped with mdbook-daily besides the built-in English
+const BUNDLES: [(&str, &str); 3] = [
+    ("de", include_str!("../locales/de.toml")),
+    ("ja", include_str!("../locales/ja.toml")),
+    ("zh", include_str!("../locales/zh.toml")),
+];
+
+/// Text the generator writes into SUMMARY.md and the generated pages.
+/// A locale file sets any of these keys; the rest stay English.
+/// `{name}` placeholders are filled in where noted.
+#[derive(Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Strings {
+    pub summary: String,
+    pub acronyms: String,
+    pub archive: String,
+    pub changes: String,
+    pub time_log: String,
+    pub tags: String,
+    pub team_daily: String,
+    /// Print summary part for chapters without a date
+    pub other: String,
+    /// January to December
+    pub months: Vec<String>,
+    /// Calendar column headings, Monday first
+    pub weekdays: Vec<String>,
+    /// Heading for a month, with `{month}` and `{year}`
+    pub month_title: String,
+    /// Heading of a weekly rollup page, with `{week}` and `{year}`
+    pub week_title: String,
+    pub draft_notice: String,
+    pub archive_intro: String,
+    /// Changes page heading, with `{rev}`
+    pub changes_since: String,
+    pub added: String,
+    pub modified: String,
+    pub removed: String,
+    /// Appended to a listed entry, with `{author}`
+    pub by_author: String,
+    pub acronym: String,
+    pub meaning: String,
+    pub defined_in: String,
+    pub label: String,
+    pub sessions: String,
+    pub time: String,
+    pub no_sessions: String,
+    /// Digest heading, with `{date}`
+    pub daily_digest: String,
+    pub no_entries: String,
+    pub recent_entries: String,
+    pub on_this_day: String,
+    /// The on this day page when no earlier year has entries on the date
+    pub nothing_on_this_day: String,
+    pub stats: String,
+    pub entries: String,
+    pub words: String,
+    pub words_per_entry: String,
+    pub current_streak: String,
+    pub longest_streak: String,
+    /// Length of a writing streak, with `{days}`
+    pub streak_days: String,
+    pub month: String,
+    /// Entry footer link to the section's previous entry, with `{date}`
+    pub previous_entry: String,
+    /// Entry footer link to the section's next entry, with `{date}`
+    pub next_entry: String,
+    /// Entry footer heading over the chapters linking to it
+    pub linked_from: String,
+    /// Chapter footer heading over the notes related to it
+    pub related: String,
+    /// Position of a series part, with `{part}`, `{total}` and `{series}`
+    pub series_part: String,
+    /// Link to a series' previous part, with `{title}`
+    pub previous_part: String,
+    /// Link to a series' next part, with `{title}`
+    pub next_part: String,
+}
+
+impl Default for Strings {
+    fn default() -> Strings {
+        let strings = |list: &[&str]| list.iter().map(|s| s.to_string()).collect();
+        Strings {
+            summary: "Summary".to_string(),
+            acronyms: "Acronyms".to_string(),
+            archive: "Archive".to_string(),
+            changes: "Changes".to_string(),
+            time_log: "Time log".to_string(),
+            tags: "Tags".to_string(),
+            team_daily: "Team Daily".to_string(),
+            other: "Other".to_string(),
+            months: strings(&crate::date::MONTH_NAMES),
+            weekdays: strings(&["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"]),
+            month_title: "{month} {year}".to_string(),
+            week_title: "Week {week}, {year}".to_string(),
+            draft_notice: "**DRAFT** This chapter has no page yet. Add a README.md to its directory.".to_string(),
+            archive_intro: "Older entries kept in the repository but left out of the book.".to_string(),
+            changes_since: "Changes since {rev}".to_string(),
+            added: "Added".to_string(),
+            modified: "Modified".to_string(),
+            removed: "Removed".to_string(),
+            by_author: "by {author}".to_string(),
+            acronym: "Acronym".to_string(),
+            meaning: "Meaning".to_string(),
+            defined_in: "Defined in".to_string(),
+            label: "Label".to_string(),
+            sessions: "Sessions".to_string(),
+            time: "Time".to_string(),
+            no_sessions: "No sessions logged yet. Start one with `mdbook-daily log start LABEL`.".to_string(),
+            daily_digest: "Daily digest {date}".to_string(),
+            no_entries: "No entries.".to_string(),
+            recent_entries: "Recent entries".to_string(),
+            on_this_day: "On this day".to_string(),
+            nothing_on_this_day: "Nothing was written on this day in earlier years yet.".to_string(),
+            stats: "Stats".to_string(),
+            entries: "Entries".to_string(),
+            words: "Words".to_string(),
+            words_per_entry: "Words per entry".to_string(),
+            current_streak: "Current streak".to_string(),
+            longest_streak: "Longest streak".to_string(),
+            streak_days: "{days} days".to_string(),
+            month: "Month".to_string(),
+            previous_entry: "← {date}".to_string(),
+            next_entry: "{date} →".to_string(),
+            linked_from: "Linked from".to_string(),
+            related: "Related".to_string(),
+            series_part: "Part {part} of {total}: {series}".to_string(),
+            previous_part: "← {title}".to_string(),
+            next_part: "{title} →".to_string(),
+        }
+    }
+}
+
+impl Strings {
+    /// The strings for `locale`: `en`, a shipped locale (`de`, `ja`,
+    /// `zh`) or the path of a TOML locale file
+    pub fn load(locale: &str) -> io::Result<Strings> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, format!("locale {}: {}", locale, message));
+        if locale == "en" {
+            return Ok(Strings::default());
+        }
+        let content = match BUNDLES.iter().find(|(name, _)| *name == locale) {
+            Some((_, bundle)) => bundle.to_string(),
+            None if Path::new(locale).is_file() => fs::read_to_string(locale)?,
+            None => {
+                let names: Vec<&str> = BUNDLES.iter().map(|(name, _)| *name).collect();
+                return Err(invalid(format!("not a file or one of en, {}", names.join(", "))));
+            }
+        };
+        let strings: Strings = toml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+        if strings.months.len() != 12 {
+            return Err(invalid(format!("months needs 12 names, found {}", strings.months.len())));
+        }
+        if strings.weekdays.len() != 7 {
+            return Err(invalid(format!("weekdays needs 7 names, found {}", strings.weekdays.len())));
+        }
+        Ok(strings)
+    }
+
+    /// Heading for the month of `date`
+    pub fn month_title(&self, date: Date) -> String {
+        self.month_title
+            .replace("{month}", &self.months[date.month as usize - 1])
+            .replace("{year}", &date.year.to_string())
+    }
+}
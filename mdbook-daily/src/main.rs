@@ -0,0 +1,449 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use mdbook_daily::date::Date;
+use mdbook_daily::eol::{self, LineEnding, Normalize};
+use mdbook_daily::summary::{self, Options, ScanStats};
+use mdbook_daily::timings::Timings;
+use mdbook_daily::tree::{BookTree, Change};
+use mdbook_daily::{
+    adopt, archive, assets, bench, capture, changes, check, config, daemon, diff, digest, explain, generated, git, head, import, interactive, link, lint, list,
+    new, notify, partial, plan, preprocessor, print, promote, reveal, rollup, rpc, sources, stats, tree, validate, vault, worklog, parse_size, usage_error, write_summary, Failure,
+};
+
+/// Exit with the code for how the command went: 2 for bad arguments,
+/// shown with the usage text, and 1 for any other failure
+fn main() {
+    let Err(e) = run() else { return };
+    match Failure::of(&e) {
+        Some(Failure::Reported) => {}
+        Some(usage @ Failure::Usage(_)) => {
+            eprintln!("Error: {}", usage);
+            std::process::exit(2);
+        }
+        None => eprintln!("Error: {}", e),
+    }
+    std::process::exit(1);
+}
+
+fn run() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // mdBook runs a preprocessor with no arguments, or asks `supports RENDERER`
+    match args.first().map(String::as_str) {
+        None => {
+            if io::stdin().is_terminal() {
+                return Err(usage_error("with no arguments mdbook-daily runs as an mdBook preprocessor; use `mdbook-daily update` to regenerate SUMMARY.md"));
+            }
+            if let Err(e) = preprocessor::run() {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some("supports") => std::process::exit(if preprocessor::supports(args.get(1).map_or("", String::as_str)) { 0 } else { 1 }),
+        // Works on its own temp tree, so needs no src directory
+        Some("bench") => return bench::run(&args[1..]),
+        Some("config") => return config::run(&args[1..]),
+        _ => {}
+    }
+
+    // Get the src directory path
+    let src_path = PathBuf::from("src");
+
+    // Adopting a folder is how a book without src gets started
+    if args.first().map(String::as_str) == Some("adopt") {
+        return adopt::run(&src_path, &args[1..]);
+    }
+
+    if !src_path.exists() {
+        eprintln!("Error: {:?} does not exist", src_path);
+        std::process::exit(1);
+    }
+
+    match args.first().map(String::as_str) {
+        Some("update") => update(&src_path, &args[1..]),
+        Some("daemon") => daemon::run(&src_path, &args[1..]),
+        Some("link") => link::run(&src_path, &args[1..]),
+        Some("list") => list::run(&src_path, &args[1..]),
+        Some("rpc") => rpc::run(&src_path, &args[1..]),
+        Some("explain") => explain::run(&src_path, &args[1..]),
+        Some("stats") => stats::run(&src_path, &args[1..]),
+        Some("tree") => tree::run(&src_path, &args[1..]),
+        Some("rollup") => rollup::run(&src_path, &args[1..]),
+        Some("reveal") => reveal::run(&src_path, &args[1..]),
+        Some("clean") => generated::clean(&src_path, &args[1..]),
+        Some("changes") => changes::run(&src_path, &args[1..]),
+        Some("digest") => digest::run(&src_path, &args[1..]),
+        Some("lint") => lint::run(&src_path, &args[1..]),
+        Some("check") => check::run(&src_path, &args[1..]),
+        Some("new") => new::run(&src_path, &args[1..]),
+        Some("plan") => plan::run(&src_path, &args[1..]),
+        Some("log") => worklog::run(&src_path, &args[1..]),
+        Some("capture") => capture::run(&src_path, &args[1..]),
+        Some("dedupe") => assets::run(&src_path, &args[1..]),
+        Some("archive") => archive::run(&src_path, &args[1..]),
+        Some("promote") => promote::run(&src_path, &args[1..]),
+        Some("import") => vault::run(&src_path, &args[1..]),
+        // Flags without a command mean `update`, as before the subcommands existed
+        _ => update(&src_path, &args),
+    }
+}
+
+/// `update --dry-run` prints the SUMMARY.md that would be written and
+/// `update --diff` a unified diff against the current one. Neither writes
+/// anything; both exit with 1 when the file is out of date, for CI, and
+/// fail under `--strict` when paths were skipped.
+fn check_summary(
+    src_path: &Path,
+    options: &Options,
+    previous: Option<&str>,
+    diff: bool,
+    writing_flags: &[(&str, bool)],
+) -> io::Result<()> {
+    let flag = if diff { "--diff" } else { "--dry-run" };
+    if let Some((name, _)) = writing_flags.iter().find(|(_, set)| *set) {
+        return Err(usage_error(&format!("{} cannot be combined with {}", flag, name)));
+    }
+    let mut stats = ScanStats::default();
+    let generated = summary::merge_managed(previous, summary::generate_summary(src_path, options, &mut stats)?);
+    stats.print_warnings();
+    // CI runs these, so `--strict` fails here as it does when writing
+    stats.check_strict(options.strict)?;
+    let generated = String::from_utf8_lossy(&eol::normalize(generated.as_bytes(), options.line_ending)).into_owned();
+    let previous = previous.unwrap_or_default();
+    let up_to_date = previous == generated;
+    if diff {
+        let path = src_path.join("SUMMARY.md").display().to_string();
+        if let Some(changes) = diff::unified(previous, &generated, &path, &format!("{} (generated)", path)) {
+            print!("{}", changes);
+        } else if !up_to_date {
+            // Only line endings or trailing whitespace differ
+            println!("{} differs from the generated summary in whitespace only", path);
+        }
+    } else {
+        print!("{}", generated);
+    }
+    if !up_to_date {
+        let changes = BookTree::parse(previous).diff(&BookTree::parse(&generated));
+        let count = |kind: fn(&Change) -> bool| changes.iter().filter(|change| kind(change)).count();
+        eprintln!(
+            "SUMMARY.md is out of date: {} added, {} removed, {} moved, {} retitled",
+            count(|c| matches!(c, Change::Added { .. })),
+            count(|c| matches!(c, Change::Removed { .. })),
+            count(|c| matches!(c, Change::Moved { .. })),
+            count(|c| matches!(c, Change::Retitled { .. })),
+        );
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Offer to keep the titles and order of a hand-written SUMMARY.md as
+/// front matter before it is replaced
+fn import_summary(src_path: &Path, options: &Options, existing: Option<&str>) -> io::Result<()> {
+    let Some(existing) = existing else {
+        println!("No existing SUMMARY.md to import");
+        return Ok(());
+    };
+    let imports = import::plan(src_path, options, existing)?;
+    if imports.is_empty() {
+        println!("SUMMARY.md has no titles or ordering to import");
+        return Ok(());
+    }
+    for entry in &imports {
+        let title = entry.title.as_ref().map(|t| format!(" title {:?}", t)).unwrap_or_default();
+        let order = entry.order.map(|o| format!(" order {}", o)).unwrap_or_default();
+        println!("  {}:{}{}", entry.path.display(), title, order);
+    }
+    let question = format!("Write this front matter into {} notes?", imports.len());
+    if io::stdin().is_terminal() && !interactive::confirm(&question)? {
+        println!("Skipped the import");
+        return Ok(());
+    }
+    import::apply(src_path, &imports)?;
+    println!("Imported titles and order into {} notes", imports.len());
+    Ok(())
+}
+
+/// Say whether the summary was written or already up to date
+fn report_write(summary_path: &Path, changed: bool) {
+    if changed {
+        println!("Successfully updated {:?}", summary_path);
+    } else {
+        println!("No changes to {:?}", summary_path);
+    }
+}
+
+/// `update --commit`: commit SUMMARY.md and the files the update created
+/// or changed, which are the ones missing from `dirty`
+fn commit_update(src_path: &Path, options: &Options, dirty: &BTreeSet<String>) -> io::Result<()> {
+    let prefix = git::run(Path::new("."), &["rev-parse", "--show-prefix"])?;
+    let summary = format!("{}{}", prefix.trim(), src_path.join("SUMMARY.md").to_string_lossy().replace('\\', "/"));
+    let paths: Vec<String> = git::changed_paths(Path::new("."))?
+        .into_iter()
+        .filter(|path| *path == summary || !dirty.contains(path))
+        .collect();
+    if paths.is_empty() {
+        println!("Nothing to commit");
+        return Ok(());
+    }
+    let message = options.commit_message
+        .replace("{date}", &Date::today().to_string())
+        .replace("{count}", &paths.len().to_string());
+    git::commit(Path::new("."), &paths, &message)?;
+    println!("Committed {} file(s): {}", paths.len(), paths.join(", "));
+    Ok(())
+}
+
+/// `mdbook-daily update [FLAGS]`: regenerate SUMMARY.md and the generated pages
+fn update(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut interactive = false;
+    let mut show_timings = false;
+    let mut options = config::book_options(Path::new("."))?;
+    let mut print_summary = None;
+    let mut webhook = std::env::var(notify::WEBHOOK_ENV).ok().filter(|url| !url.is_empty());
+    let mut only = None;
+    let mut import_existing = false;
+    let mut validate = false;
+    let mut dry_run = false;
+    let mut diff = false;
+    let mut commit = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--interactive" => interactive = true,
+            "--timings" => show_timings = true,
+            "--year-separators" => options.year_separators = true,
+            "--exclude-older-than" => {
+                let value = args.next().ok_or_else(|| usage_error("--exclude-older-than needs an age"))?;
+                let cutoff = Date::today().minus_age(value)
+                    .ok_or_else(|| usage_error(&format!("invalid age {:?}, expected e.g. 90d or 2y", value)))?;
+                options.exclude_before = Some(cutoff);
+            }
+            "--archive-page" => options.archive_page = true,
+            "--plain-titles" => {
+                let value = args.next().ok_or_else(|| usage_error("--plain-titles needs a directory"))?;
+                let dir = Path::new(value);
+                options.plain_title_dirs.push(dir.strip_prefix(src_path).unwrap_or(dir).to_path_buf());
+            }
+            "--acronym-index" => options.acronym_index = true,
+            "--team" => options.team_digest = true,
+            "--time-log" => options.time_log = true,
+            "--tag-index" => options.tag_index = true,
+            "--tag-pages" => options.tag_pages = true,
+            "--series-pages" => options.series_pages = true,
+            "--calendar" => options.calendar_pages = true,
+            "--stats-page" => options.stats_page = true,
+            "--on-this-day" => options.on_this_day = true,
+            "--recent" => {
+                let value = args.next().ok_or_else(|| usage_error("--recent needs a number of entries"))?;
+                let count = value.parse().ok().filter(|n| *n > 0)
+                    .ok_or_else(|| usage_error(&format!("invalid --recent {:?}, expected a positive number", value)))?;
+                options.recent_entries = Some(count);
+            }
+            "--feed" => {
+                let value = args.next().ok_or_else(|| usage_error("--feed needs a number of entries"))?;
+                let count = value.parse().ok().filter(|n| *n > 0)
+                    .ok_or_else(|| usage_error(&format!("invalid --feed {:?}, expected a positive number", value)))?;
+                options.feed_entries = Some(count);
+            }
+            "--feed-html" => options.feed_html = true,
+            "--feed-sections" => options.feed_sections = true,
+            "--import-existing-summary" => import_existing = true,
+            "--validate" => validate = true,
+            "--dry-run" => dry_run = true,
+            "--diff" => diff = true,
+            "--commit" => commit = true,
+            "--read-only-sources" => options.read_only_sources = true,
+            "--strict" => options.strict = true,
+            "--hydrate-placeholders" => options.hydrate_placeholders = true,
+            "--include-drafts" => options.include_drafts = true,
+            "--section-markers" => options.section_markers = true,
+            "--line-endings" => {
+                let value = args.next().ok_or_else(|| usage_error("--line-endings needs lf or crlf"))?;
+                options.line_ending = LineEnding::parse(value)
+                    .ok_or_else(|| usage_error(&format!("invalid line ending {:?}, expected lf or crlf", value)))?;
+            }
+            "--changes-since" => {
+                let value = args.next().ok_or_else(|| usage_error("--changes-since needs a git revision"))?;
+                options.changes_since = Some(value.clone());
+            }
+            "--generated-dir" => {
+                let value = args.next().ok_or_else(|| usage_error("--generated-dir needs a directory"))?;
+                options.generated_dir = config::generated_dir(value).map_err(|e| usage_error(&e))?;
+            }
+            "--notify-webhook" => {
+                let value = args.next().ok_or_else(|| usage_error("--notify-webhook needs a URL"))?;
+                webhook = Some(value.clone());
+            }
+            "--site-url" => {
+                let value = args.next().ok_or_else(|| usage_error("--site-url needs a URL"))?;
+                options.site_url = Some(value.clone());
+            }
+            "--only" => {
+                let value = args.next().ok_or_else(|| usage_error("--only needs a directory"))?;
+                let dir = Path::new(value);
+                let relative = dir.strip_prefix(src_path).unwrap_or(dir).to_path_buf();
+                if !src_path.join(&relative).is_dir() || relative.components().count() == 0 {
+                    return Err(usage_error(&format!("--only needs a directory inside {:?}", src_path)));
+                }
+                only = Some(relative);
+            }
+            "--print-summary" => {
+                let value = args.next().ok_or_else(|| usage_error("--print-summary needs a path"))?;
+                print_summary = Some(PathBuf::from(value));
+            }
+            "--head-size" => {
+                let value = args.next().ok_or_else(|| usage_error("--head-size needs a value"))?;
+                options.head_size = parse_size(value)
+                    .ok_or_else(|| usage_error(&format!("invalid size {:?}", value)))?;
+            }
+            "--jobs" => {
+                let value = args.next().ok_or_else(|| usage_error("--jobs needs a number of threads"))?;
+                let jobs = value.parse().ok().filter(|n| *n > 0)
+                    .ok_or_else(|| usage_error(&format!("invalid --jobs {:?}, expected a positive number", value)))?;
+                options.jobs = Some(jobs);
+            }
+            "--reads-per-second" => {
+                let value = args.next().ok_or_else(|| usage_error("--reads-per-second needs a number"))?;
+                let rate = value.parse().ok().filter(|n| *n > 0)
+                    .ok_or_else(|| usage_error(&format!("invalid --reads-per-second {:?}, expected a positive number", value)))?;
+                options.reads_per_second = Some(rate);
+            }
+            "--max-file-size" => {
+                let value = args.next().ok_or_else(|| usage_error("--max-file-size needs a value"))?;
+                options.max_file_size = parse_size(value)
+                    .ok_or_else(|| usage_error(&format!("invalid size {:?}", value)))?;
+            }
+            _ => return Err(usage_error(&format!("unknown argument {:?}", arg))),
+        }
+    }
+    if options.feed_html && options.feed_entries.is_none() {
+        return Err(usage_error("--feed-html needs --feed"));
+    }
+    if options.feed_sections && options.feed_entries.is_none() {
+        return Err(usage_error("--feed-sections needs --feed"));
+    }
+
+    let mut timings = Timings::new();
+    let mut stats = ScanStats::default();
+    let previous = fs::read_to_string(src_path.join("SUMMARY.md")).ok();
+    if dry_run || diff {
+        return check_summary(src_path, &options, previous.as_deref(), diff, &[
+            ("--interactive", interactive),
+            ("--only", only.is_some()),
+            ("--import-existing-summary", import_existing),
+            ("--print-summary", print_summary.is_some()),
+            ("--validate", validate),
+            ("--commit", commit),
+        ]);
+    }
+    if import_existing {
+        if options.read_only_sources {
+            return Err(usage_error("--import-existing-summary writes front matter, so it cannot be combined with --read-only-sources"));
+        }
+        import_summary(src_path, &options, previous.as_deref())?;
+    }
+    let before = options.read_only_sources.then(|| sources::snapshot(src_path));
+    // Files already changed before the update are not committed with it
+    let dirty = if commit { Some(git::changed_paths(Path::new("."))?) } else { None };
+    if interactive && only.is_some() {
+        return Err(usage_error("--only cannot be combined with --interactive"));
+    }
+    let partial = match &only {
+        Some(relative) => partial::update_subtree(src_path, relative, &options, &mut stats)?,
+        None => false,
+    };
+    if partial {
+        timings.phase("partial update");
+        println!("Updated {:?} in {:?}", only.as_deref().unwrap_or(Path::new("")), src_path.join("SUMMARY.md"));
+    } else if interactive {
+        let summary_path = src_path.join("SUMMARY.md");
+        let generated = summary::merge_managed(previous.as_deref(), summary::generate_summary(src_path, &options, &mut stats)?);
+        timings.phase("generate");
+        stats.check_strict(options.strict)?;
+        let existing = previous.clone().unwrap_or_default();
+        // The generated pages below are still written: they follow the
+        // notes, and declining an addition only keeps it out of the summary
+        match interactive::confirm_changes(&existing, &generated)? {
+            Some(content) => {
+                timings.phase("confirm");
+                let changed = generated::write_if_changed(&summary_path, content.as_bytes(), options.line_ending)?;
+                timings.phase("write");
+                report_write(&summary_path, changed);
+            }
+            None => println!("No changes to write to {:?}", summary_path),
+        }
+    } else {
+        let changed = write_summary(src_path, &options, &mut stats)?;
+        timings.phase("generate+write");
+        report_write(&src_path.join("SUMMARY.md"), changed);
+    }
+    // Generated pages need a full scan, so a partial update leaves them alone
+    if !partial {
+        for line in generated::write_pages(src_path, &options, &stats)? {
+            println!("{}", line);
+        }
+        if let Some(line) = head::write_head(Path::new("theme"), &stats, options.line_ending)? {
+            println!("{}", line);
+        }
+        if !options.generated_pages().is_empty() || options.team_digest || options.include_drafts || options.calendar_pages {
+            timings.phase("generated pages");
+        }
+    }
+
+    if validate {
+        match validate::validate_book(Path::new("."), src_path) {
+            Ok(chapters) => println!("mdBook loads all {} chapters", chapters),
+            Err(e) => {
+                eprintln!("Error: mdBook cannot load the book: {}", e);
+                std::process::exit(1);
+            }
+        }
+        timings.phase("validate");
+    }
+
+    if let Some(print_path) = print_summary {
+        sources::check_write(options.read_only_sources, src_path, &options.generated_dir, &print_path)?;
+        let summary = fs::read_to_string(src_path.join("SUMMARY.md"))?;
+        let mut file = Normalize::new(BufWriter::new(fs::File::create(&print_path)?), options.line_ending);
+        print::write_print_summary(&options.strings, &summary, &mut file)?;
+        file.flush()?;
+        timings.phase("print summary");
+        println!("Wrote print summary to {:?}", print_path);
+    }
+
+    if let Some(webhook) = webhook {
+        let current = fs::read_to_string(src_path.join("SUMMARY.md"))?;
+        match previous {
+            Some(previous) => {
+                let added = notify::added_entries(&previous, &current, &options.generated_dir);
+                if !added.is_empty() {
+                    notify::post(&webhook, options.site_url.as_deref(), &added)?;
+                    timings.phase("notify");
+                    println!("Posted {} new entries to the webhook", added.len());
+                }
+            }
+            None => println!("No previous SUMMARY.md, skipping the webhook notification"),
+        }
+    }
+
+    if let Some(before) = before {
+        sources::verify_unchanged(src_path, &options.generated_dir, &before, &sources::snapshot(src_path))?;
+    }
+
+    if let Some(dirty) = dirty {
+        commit_update(src_path, &options, &dirty)?;
+        timings.phase("commit");
+    }
+
+    stats.print_warnings();
+    if show_timings {
+        timings.report(&stats);
+    }
+
+    Ok(())
+}
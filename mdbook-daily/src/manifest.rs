@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+/// Manifest file a directory may hold to curate its children
+pub const FILE_NAME: &str = "index.toml";
+
+/// How one directory's children are listed, overriding the sort order
+/// and derived titles:
+///
+/// ```toml
+/// order = ["overview.md", "design"]
+/// hide = ["scratch.md"]
+///
+/// [titles]
+/// "overview.md" = "Project overview"
+/// ```
+///
+/// Children are named by file or directory name. Listed ones come first,
+/// in the given order, and the rest follow in the usual order; files and
+/// directories are still listed apart.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Manifest {
+    pub order: Vec<String>,
+    /// Children left out of the summary
+    pub hide: Vec<String>,
+    /// Link titles, overriding headings, front matter and names
+    pub titles: HashMap<String, String>,
+}
+
+impl Manifest {
+    /// The manifest in `dir`, or an empty one when it has none
+    pub fn read(dir: &Path) -> io::Result<Manifest> {
+        let content = match fs::read_to_string(dir.join(FILE_NAME)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Manifest::default()),
+            Err(e) => return Err(e),
+        };
+        toml::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+    }
+
+    pub fn hides(&self, name: &str) -> bool {
+        self.hide.iter().any(|hidden| hidden == name)
+    }
+
+    pub fn title(&self, name: &str) -> Option<&str> {
+        self.titles.get(name).map(String::as_str)
+    }
+
+    /// Move the children `order` lists to the front, keeping the order of
+    /// the others. `name` gives a child's file or directory name.
+    pub fn reorder<T>(&self, items: &mut [T], name: impl Fn(&T) -> String) {
+        if self.order.is_empty() {
+            return;
+        }
+        items.sort_by_cached_key(|item| {
+            let name = name(item);
+            self.order.iter().position(|listed| *listed == name).unwrap_or(self.order.len())
+        });
+    }
+}
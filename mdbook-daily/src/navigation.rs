@@ -0,0 +1,210 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use mdbook::book::{Book, BookItem};
+
+use crate::date::Date;
+use crate::links::{chapter_link, markdown_links, resolve_link};
+use crate::locale::Strings;
+use crate::series::{series_path, Series};
+use crate::summary::{escape_title, Options, ScanStats};
+
+/// An entry's date and source path relative to src
+type Entry = (Date, PathBuf);
+
+/// Chapters linking to each chapter, as `(title, source path)`, both
+/// paths relative to src. Links inside fenced code, site-absolute links
+/// and links from generated pages are not counted.
+pub fn backlinks(book: &Book, options: &Options) -> HashMap<PathBuf, Vec<(String, PathBuf)>> {
+    let mut backlinks: HashMap<PathBuf, Vec<(String, PathBuf)>> = HashMap::new();
+    for item in book.iter() {
+        let BookItem::Chapter(chapter) = item else { continue };
+        let Some(source) = &chapter.source_path else { continue };
+        if source.starts_with(&options.generated_dir) {
+            continue;
+        }
+        let mut targets = HashSet::new();
+        let mut in_fence = false;
+        for line in chapter.content.lines() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+            if in_fence {
+                continue;
+            }
+            for target in markdown_links(line).into_iter().filter(|t| !t.starts_with('/')) {
+                let mut resolved = resolve_link(source, &target);
+                if target.ends_with('/') {
+                    resolved.push("README.md");
+                } else if resolved.extension().is_some_and(|e| e == "html") {
+                    resolved.set_extension("md");
+                }
+                if resolved != *source {
+                    targets.insert(resolved);
+                }
+            }
+        }
+        for target in targets {
+            backlinks.entry(target).or_default().push((chapter.name.clone(), source.clone()));
+        }
+    }
+    for sources in backlinks.values_mut() {
+        sources.sort_by(|a, b| a.1.cmp(&b.1));
+    }
+    backlinks
+}
+
+/// The dated entries of each section in date order, by section directory
+/// name (empty for the top level)
+fn sections(stats: &ScanStats) -> HashMap<String, Vec<Entry>> {
+    let mut sections: HashMap<String, Vec<Entry>> = HashMap::new();
+    for (date, _, link) in &stats.dated {
+        let path = PathBuf::from(link.trim_start_matches("./"));
+        let section = match path.components().count() {
+            1 => String::new(),
+            _ => path.components().next().map(|c| c.as_os_str().to_string_lossy().into_owned()).unwrap_or_default(),
+        };
+        sections.entry(section).or_default().push((*date, path));
+    }
+    for entries in sections.values_mut() {
+        entries.sort();
+    }
+    sections
+}
+
+/// Footer for the entry at `source`: links to its neighbours and the
+/// chapters linking to it, or nothing when there are neither
+fn footer(
+    source: &Path,
+    previous: Option<&Entry>,
+    next: Option<&Entry>,
+    linked_from: &[(String, PathBuf)],
+    strings: &Strings,
+) -> String {
+    if previous.is_none() && next.is_none() && linked_from.is_empty() {
+        return String::new();
+    }
+    let dir = source.parent().unwrap_or(Path::new(""));
+    let mut footer = String::from("\n\n---\n");
+    let nav: Vec<String> = [(previous, &strings.previous_entry), (next, &strings.next_entry)]
+        .into_iter()
+        .filter_map(|(entry, label)| {
+            let (date, path) = entry?;
+            Some(format!("[{}]({})", label.replace("{date}", &date.to_string()), chapter_link(dir, path)))
+        })
+        .collect();
+    if !nav.is_empty() {
+        footer.push_str(&format!("\n{}\n", nav.join(" | ")));
+    }
+    if !linked_from.is_empty() {
+        footer.push_str(&format!("\n**{}**\n\n", strings.linked_from));
+        for (title, path) in linked_from {
+            footer.push_str(&format!("- [{}]({})\n", escape_title(title), chapter_link(dir, path)));
+        }
+    }
+    footer
+}
+
+/// Append to each dated entry links to the previous and next entry of
+/// its section and a list of the chapters linking to it
+pub fn add_navigation(book: &mut Book, stats: &ScanStats, options: &Options) {
+    let backlinks = backlinks(book, options);
+    let sections = sections(stats);
+    let positions: HashMap<&Path, (&[Entry], usize)> = sections.values()
+        .flat_map(|entries| entries.iter().enumerate().map(move |(i, (_, path))| (path.as_path(), (entries.as_slice(), i))))
+        .collect();
+    book.for_each_mut(|item| {
+        let BookItem::Chapter(chapter) = item else { return };
+        let Some(source) = &chapter.source_path else { return };
+        let Some((entries, i)) = positions.get(source.as_path()) else { return };
+        let previous = i.checked_sub(1).map(|i| &entries[i]);
+        let linked_from = backlinks.get(source).map_or(&[][..], Vec::as_slice);
+        let footer = footer(source, previous, entries.get(i + 1), linked_from, &options.strings);
+        if footer.is_empty() {
+            return;
+        }
+        chapter.content = format!("{}{}", chapter.content.trim_end(), footer);
+    });
+}
+
+/// Header for part `i` of `series`, at `source`: its number and links to
+/// the previous and next part
+fn series_header(source: &Path, series: &Series, i: usize, options: &Options) -> String {
+    let dir = source.parent().unwrap_or(Path::new(""));
+    let link = |path: &str| chapter_link(dir, Path::new(path.trim_start_matches("./")));
+    let title = if options.series_pages {
+        format!("[{}]({})", escape_title(&series.title), chapter_link(dir, &series_path(options, &series.name)))
+    } else {
+        series.title.clone()
+    };
+    let position = options.strings.series_part
+        .replace("{part}", &(i + 1).to_string())
+        .replace("{total}", &series.parts.len().to_string())
+        .replace("{series}", &title);
+    let mut header = format!("*{}*\n", position);
+    let previous = i.checked_sub(1).map(|i| (&series.parts[i], &options.strings.previous_part));
+    let next = series.parts.get(i + 1).map(|part| (part, &options.strings.next_part));
+    let nav: Vec<String> = previous.into_iter().chain(next)
+        .map(|((title, path, _), label)| format!("[{}]({})", escape_title(&label.replace("{title}", title)), link(path)))
+        .collect();
+    if !nav.is_empty() {
+        header.push_str(&format!("\n{}\n", nav.join(" | ")));
+    }
+    header
+}
+
+/// Head each part of a series, after its title when it starts with one,
+/// with its number in the series and links to the previous and next part
+pub fn add_series_navigation(book: &mut Book, stats: &ScanStats, options: &Options) {
+    let positions: HashMap<&Path, (&Series, usize)> = stats.series.iter()
+        .flat_map(|series| series.parts.iter().enumerate().map(move |(i, (_, path, _))| (Path::new(path.trim_start_matches("./")), (series, i))))
+        .collect();
+    book.for_each_mut(|item| {
+        let BookItem::Chapter(chapter) = item else { return };
+        let Some(source) = &chapter.source_path else { return };
+        let Some(&(series, i)) = positions.get(source.as_path()) else { return };
+        let header = series_header(source, series, i, options);
+        let body = chapter.content.trim_start();
+        chapter.content = match body.strip_prefix("# ").map(|_| body.split_once('\n').unwrap_or((body, ""))) {
+            Some((title, rest)) => format!("{}\n\n{}\n{}", title, header, rest.trim_start_matches(['\r', '\n'])),
+            None => format!("{}\n{}", header, body),
+        };
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mdbook::book::Chapter;
+
+    #[test]
+    fn neighbours_stop_at_the_edges_of_a_section() {
+        let notes = [
+            ("journal/2026/1012.md", 12, "Monday"),
+            ("journal/2026/1013.md", 13, "Tuesday, see [Monday](./1012.md)"),
+            ("journal/2026/1014.md", 14, "Wednesday"),
+            ("work/2026/1013.md", 13, "Work"),
+        ];
+        let mut book = Book::new();
+        for (path, _, content) in notes {
+            book.push_item(BookItem::Chapter(Chapter::new(content, content.to_string(), path, Vec::new())));
+        }
+        let stats = ScanStats {
+            dated: notes.iter().map(|(path, day, _)| (Date::new(2026, 10, *day).unwrap(), String::new(), format!("./{}", path))).collect(),
+            ..ScanStats::default()
+        };
+        add_navigation(&mut book, &stats, &Options::default());
+        let contents: Vec<String> = book.iter().filter_map(|item| match item {
+            BookItem::Chapter(chapter) => Some(chapter.content.clone()),
+            _ => None,
+        }).collect();
+        assert_eq!(contents, [
+            "Monday\n\n---\n\n[2026-10-13 →](./1013.md)\n\n**Linked from**\n\n- [Tuesday, see \\[Monday\\](./1012.md)](./1013.md)\n",
+            "Tuesday, see [Monday](./1012.md)\n\n---\n\n[← 2026-10-12](./1012.md) | [2026-10-14 →](./1014.md)\n",
+            "Wednesday\n\n---\n\n[← 2026-10-13](./1013.md)\n",
+            // The only entry of its section, unlinked, has no footer
+            "Work",
+        ]);
+    }
+}
@@ -0,0 +1,211 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use mdbook::config::Config as BookConfig;
+
+use crate::config::{self, Config};
+use crate::date::{date_from_path, Date};
+use crate::link::relative_link;
+use crate::links::markdown_files;
+use crate::summary::{Options, ScanStats};
+
+/// Template used when the book has none: front matter with the entry's
+/// date, no tags yet and the first of the default statuses, so every
+/// note can be read by tools from the start
+const DEFAULT_TEMPLATE: &str = "---\ndate: {{date}}\ntags: []\nstatus: seed\n---\n\n# {{title}}\n\n";
+
+/// Directory (relative to the book root) holding the named templates
+pub const TEMPLATES_DIR: &str = "templates";
+
+/// Values for a template's placeholders
+struct Variables<'a> {
+    date: Date,
+    /// The entry's file stem, as the summary shows it
+    title: &'a str,
+    /// Link to the section's latest entry before this one, if any
+    prev_entry: Option<String>,
+    /// The book's title and authors from book.toml
+    book_title: String,
+    book_authors: Vec<String>,
+}
+
+/// Fill in a template's `{{date}}` (YYYY-MM-DD), `{{title}}`,
+/// `{{weekday}}` (`Monday`), `{{iso_week}}` (`2024-W03`),
+/// `{{prev_entry}}` (a link to the previous entry, or nothing),
+/// `{{book.title}}` and `{{book.authors}}` (comma-separated)
+/// placeholders
+fn render(template: &str, vars: &Variables) -> String {
+    let (week_year, week) = vars.date.iso_week();
+    template.replace("{{book.title}}", &vars.book_title)
+        .replace("{{book.authors}}", &vars.book_authors.join(", "))
+        .replace("{{date}}", &vars.date.to_string())
+        .replace("{{title}}", vars.title)
+        .replace("{{weekday}}", vars.date.weekday_name())
+        .replace("{{iso_week}}", &format!("{}-W{:02}", week_year, week))
+        .replace("{{prev_entry}}", vars.prev_entry.as_deref().unwrap_or_default())
+}
+
+/// Link from the entry for `date` in `section_dir` to the latest entry
+/// there dated before it
+fn prev_entry(section_dir: &Path, date: Date) -> Option<String> {
+    let (prev_date, prev_path) = markdown_files(section_dir).into_iter()
+        .filter_map(|path| {
+            let relative = path.strip_prefix(section_dir).ok()?.to_path_buf();
+            let prev_date = date_from_path(&relative.to_string_lossy().replace('\\', "/"))?;
+            (prev_date < date).then_some((prev_date, relative))
+        })
+        .max()?;
+    let entry_dir = date.entry_path().parent().map(Path::to_path_buf).unwrap_or_default();
+    Some(format!("[{}]({})", prev_date, relative_link(&entry_dir, &prev_path)))
+}
+
+/// Section directories under src, sorted
+fn sections(src_path: &Path, options: &Options) -> io::Result<Vec<String>> {
+    let mut sections: Vec<String> = fs::read_dir(src_path)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir() && e.path() != src_path.join(&options.generated_dir))
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .collect();
+    sections.sort();
+    Ok(sections)
+}
+
+/// The template for a new entry in `section`: the named one (given or
+/// configured in `section-templates`) from `templates/`, else the
+/// configured `template`, else `templates/daily.md`, else the default
+pub fn template(config: &Config, name: Option<&str>, section: &str) -> io::Result<String> {
+    let read = |path: &Path| fs::read_to_string(path)
+        .map_err(|e| io::Error::new(e.kind(), format!("template {}: {}", path.display(), e)));
+    let name = name.or(config.section_templates.get(section).map(String::as_str));
+    if let Some(name) = name {
+        return read(&Path::new(TEMPLATES_DIR).join(format!("{}.md", name)));
+    }
+    if let Some(path) = &config.template {
+        return read(path);
+    }
+    let daily = Path::new(TEMPLATES_DIR).join("daily.md");
+    if daily.exists() {
+        return read(&daily);
+    }
+    Ok(DEFAULT_TEMPLATE.to_string())
+}
+
+/// Write the entry for `date` at `path` (`SECTION/YYYY/MMDD.md`) from
+/// `template`, adding a README to its year directory if that has none
+pub fn create_entry(path: &Path, date: Date, template: &str) -> io::Result<()> {
+    let year_dir = path.parent().unwrap_or(Path::new("."));
+    let section_dir = year_dir.parent().unwrap_or(Path::new("."));
+    let vars = Variables {
+        date,
+        title: &format!("{:02}{:02}", date.month, date.day),
+        prev_entry: prev_entry(section_dir, date),
+        book_title: String::new(),
+        book_authors: Vec::new(),
+    };
+    // Commands run from the book root, where book.toml is
+    let vars = match BookConfig::from_disk("book.toml") {
+        Ok(config) => Variables {
+            book_title: config.book.title.unwrap_or_default(),
+            book_authors: config.book.authors,
+            ..vars
+        },
+        Err(_) => vars,
+    };
+    fs::create_dir_all(year_dir)?;
+    // Without a README the year would only be a draft chapter
+    let readme = year_dir.join("README.md");
+    if !readme.exists() {
+        fs::write(&readme, format!("# {}\n", date.year))?;
+    }
+    fs::write(path, render(template, &vars))
+}
+
+/// The `--section` a `command` was given, checked against the section
+/// directories, or the only section when there is just one
+pub fn pick_section(src_path: &Path, options: &Options, section: Option<String>, command: &str) -> io::Result<String> {
+    let sections = sections(src_path, options)?;
+    Ok(match section {
+        Some(section) if sections.contains(&section) => section,
+        Some(section) => return Err(crate::usage_error(&format!(
+            "no section directory {:?}, expected one of {}", section, sections.join(", ")))),
+        None if sections.len() == 1 => sections[0].clone(),
+        None => return Err(crate::usage_error(&format!("{} needs --section, one of {}", command, sections.join(", ")))),
+    })
+}
+
+/// `mdbook-daily new [--date YYYY-MM-DD] [--section NAME] [--template
+/// NAME]`: create the entry for a day as `SECTION/YYYY/MMDD.md` from its
+/// template, then regenerate SUMMARY.md. Prints the new file's path.
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut date = Date::today();
+    let mut section = None;
+    let mut template_name = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--date" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--date needs a date"))?;
+                date = date_from_path(value)
+                    .ok_or_else(|| crate::usage_error(&format!("invalid date {:?}, expected YYYY-MM-DD", value)))?;
+            }
+            "--section" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--section needs a directory"))?;
+                section = Some(value.clone());
+            }
+            "--template" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--template needs a name"))?;
+                template_name = Some(value.clone());
+            }
+            _ => return Err(crate::usage_error(&format!("unknown new argument {:?}", arg))),
+        }
+    }
+
+    let config = Config::load(Path::new("."))?;
+    let options = config::book_options(Path::new("."))?;
+
+    let section = pick_section(src_path, &options, section, "new")?;
+    let template = template(&config, template_name.as_deref(), &section)?;
+    let path = src_path.join(&section).join(date.entry_path());
+    if path.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{:?} already exists", path)));
+    }
+    create_entry(&path, date, &template)?;
+
+    let mut stats = ScanStats::default();
+    crate::write_summary(src_path, &options, &mut stats)?;
+    stats.print_warnings();
+    println!("{}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontmatter;
+
+    #[test]
+    fn default_template_scaffolds_front_matter() {
+        let date = Date::new(2026, 1, 15).unwrap();
+        let vars = Variables { date, title: "0115", prev_entry: None, book_title: String::new(), book_authors: Vec::new() };
+        let content = render(DEFAULT_TEMPLATE, &vars);
+        let (front, body) = frontmatter::split(&content);
+        let front = front.expect("front matter");
+        assert_eq!(front.date, Some(date));
+        assert!(front.tags.is_empty());
+        assert_eq!(front.status.as_deref(), Some("seed"));
+        assert_eq!(body, "\n# 0115\n\n");
+    }
+
+    #[test]
+    fn templates_see_the_book_metadata() {
+        let vars = Variables {
+            date: Date::new(2026, 1, 15).unwrap(),
+            title: "0115",
+            prev_entry: None,
+            book_title: "Field notes".to_string(),
+            book_authors: vec!["Ann".to_string(), "Bo".to_string()],
+        };
+        assert_eq!(render("{{book.title}} by {{book.authors}}, {{date}}", &vars), "Field notes by Ann, Bo, 2026-01-15");
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde_json::json;
+
+use crate::summary::{parse_entries, SummaryEntry};
+use crate::url::page_url;
+
+/// Environment variable read when `--notify-webhook` is not given, so the
+/// URL can stay out of command lines and CI logs
+pub const WEBHOOK_ENV: &str = "MDBOOK_DAILY_WEBHOOK";
+
+/// Linked entries in `new` that are not in `old`, leaving out generated pages
+pub fn added_entries(old: &str, new: &str, generated_dir: &Path) -> Vec<SummaryEntry> {
+    let old_keys: HashSet<String> = parse_entries(old).iter().map(|e| e.key()).collect();
+    parse_entries(new)
+        .into_iter()
+        .filter(|e| {
+            e.path.as_deref().is_some_and(|p| !Path::new(p.trim_start_matches("./")).starts_with(generated_dir))
+                && !old_keys.contains(&e.key())
+        })
+        .collect()
+}
+
+/// `value` as a double-quoted curl config value
+fn curl_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' | '"' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Post a message listing `entries` to a Slack or Discord webhook with curl
+pub fn post(webhook: &str, site_url: Option<&str>, entries: &[SummaryEntry]) -> io::Result<()> {
+    let mut text = format!("{} new entr{}:", entries.len(), if entries.len() == 1 { "y" } else { "ies" });
+    for entry in entries {
+        let link = entry.path.as_deref().unwrap_or_default();
+        match site_url {
+            Some(site) => text.push_str(&format!("\n- {} {}", entry.title, page_url(site, link))),
+            None => text.push_str(&format!("\n- {} ({})", entry.title, link.trim_start_matches("./"))),
+        }
+    }
+    // Discord expects `content` where Slack expects `text`
+    let key = if webhook.contains("discord.com/") || webhook.contains("discordapp.com/") { "content" } else { "text" };
+    let payload = json!({ key: text }).to_string();
+    // The URL holds the webhook's secret, so it goes to curl as a config
+    // on stdin instead of on the command line other users can see
+    let config = format!("url = {}\ndata-binary = {}\n", curl_quote(webhook), curl_quote(&payload));
+
+    let mut child = Command::new("curl")
+        .args(["-sS", "-f", "-X", "POST", "-H", "Content-Type: application/json", "-K", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()?;
+    child.stdin.take().expect("stdin is piped").write_all(config.as_bytes())?;
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("webhook post failed: curl {}", status)));
+    }
+    Ok(())
+}
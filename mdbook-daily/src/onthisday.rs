@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::date::Date;
+use crate::eol;
+use crate::generated;
+use crate::links::chapter_link;
+use crate::sources;
+use crate::summary::{escape_title, managed_region, merge_managed, Options, ScanStats, MANAGED_END, MANAGED_START};
+
+/// Page (relative to src) listing the entries written on today's date in
+/// earlier years, pinned after the other pinned chapters
+pub const ON_THIS_DAY_PAGE: &str = "on-this-day.md";
+
+/// Whether `date` falls on the month and day of `today` in an earlier
+/// year. Outside leap years, entries from February 29 come up on the 28th.
+pub fn is_anniversary(date: Date, today: Date) -> bool {
+    if date.year >= today.year {
+        return false;
+    }
+    let same_day = (date.month, date.day) == (today.month, today.day);
+    let leap_day = (date.month, date.day) == (2, 29) && (today.month, today.day) == (2, 28) && Date::new(today.year, 2, 29).is_none();
+    same_day || leap_day
+}
+
+/// Write the entries dated on `today` in earlier years, grouped under a
+/// heading per year, newest year first
+pub fn write_on_this_day_list(options: &Options, stats: &ScanStats, today: Date, out: &mut dyn Write) -> io::Result<usize> {
+    let mut years: BTreeMap<i64, Vec<(&str, &str)>> = BTreeMap::new();
+    for (date, title, link) in &stats.dated {
+        if is_anniversary(*date, today) {
+            years.entry(date.year).or_default().push((title, link));
+        }
+    }
+    if years.is_empty() {
+        writeln!(out, "{}", options.strings.nothing_on_this_day)?;
+        return Ok(0);
+    }
+    let mut listed = 0;
+    for (year, entries) in years.iter_mut().rev() {
+        entries.sort_by_key(|(_, link)| *link);
+        writeln!(out, "## {}", year)?;
+        writeln!(out)?;
+        for (title, link) in entries.iter() {
+            writeln!(out, "- [{}]({})", escape_title(title), chapter_link(Path::new(""), Path::new(link.trim_start_matches("./"))))?;
+        }
+        writeln!(out)?;
+        listed += entries.len();
+    }
+    Ok(listed)
+}
+
+/// Write `on-this-day.md` for today's date, only replacing its managed
+/// region when it already exists. Returns the number of entries listed.
+pub fn write_on_this_day_page(src_path: &Path, options: &Options, stats: &ScanStats) -> io::Result<usize> {
+    let path = src_path.join(ON_THIS_DAY_PAGE);
+    let existing = fs::read_to_string(&path).ok();
+    if existing.as_deref().is_some_and(|e| managed_region(e).is_none()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} has no {} ... {} region to fill in", path.display(), MANAGED_START, MANAGED_END),
+        ));
+    }
+    let mut list = Vec::new();
+    let listed = write_on_this_day_list(options, stats, Date::today(), &mut list)?;
+    let list = String::from_utf8_lossy(&list);
+    let content = match existing {
+        Some(existing) => merge_managed(Some(&existing), list.into_owned()),
+        None => format!("# {}\n\n{}\n\n{}\n\n{}\n", options.strings.on_this_day, MANAGED_START, list.trim(), MANAGED_END),
+    };
+    if fs::read(&path).is_ok_and(|e| e == eol::normalize(content.as_bytes(), options.line_ending)) {
+        return Ok(listed);
+    }
+    sources::check_write(options.read_only_sources, src_path, &options.generated_dir, &path)?;
+    generated::write_if_changed(&path, content.as_bytes(), options.line_ending)?;
+    Ok(listed)
+}
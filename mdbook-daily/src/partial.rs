@@ -0,0 +1,123 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::generated;
+use crate::summary::{self, section_title, Disambiguator, Options, ScanStats};
+
+/// Start of the comments `--section-markers` puts around each part
+const MARKER_PREFIX: &str = "<!-- mdbook-daily:";
+
+/// Indentation width of a SUMMARY line, in nesting levels
+fn depth(line: &str, options: &Options) -> usize {
+    (line.len() - line.trim_start().len()) / options.indent_width
+}
+
+/// Line range holding the entries below `relative` in an existing
+/// SUMMARY, found by following section and chapter titles
+fn find_children(lines: &[&str], relative: &Path, options: &Options) -> Option<(usize, usize)> {
+    let mut components = relative.components().map(|c| c.as_os_str().to_string_lossy().into_owned());
+    let section = components.next()?;
+    let header = format!("# {}", section_title(options, &section));
+    let start = lines.iter().position(|l| *l == header)? + 1;
+    let mut end = lines[start..].iter()
+        .position(|l| l.starts_with("# ") || l.starts_with(MARKER_PREFIX))
+        .map_or(lines.len(), |i| start + i);
+    // The part's entries sit between a blank line after the header and
+    // the blank line before the next part or its end marker
+    let mut start = start + 1;
+    while end > start && lines[end - 1].is_empty() {
+        end -= 1;
+    }
+
+    let mut path = Path::new(&section).to_path_buf();
+    for (level, name) in components.enumerate() {
+        path.push(&name);
+        let prefix = format!("- [{}]", summary::escape_title(&options.title_for(&name, &path)));
+        let line = (start..end).find(|&i| {
+            let line = lines[i];
+            depth(line, options) == level && line.trim_start().starts_with(&prefix)
+        })?;
+        start = line + 1;
+        end = (start..end).find(|&i| depth(lines[i], options) <= level || lines[i].trim().is_empty()).unwrap_or(end);
+    }
+    Some((start, end))
+}
+
+/// Re-walk only `relative` (a directory under src) and splice its entries
+/// into the existing SUMMARY.md. Returns false when the directory is not
+/// in the current summary yet, its section tells shared titles apart or
+/// rolls entries up, or it holds part of a series, and a full
+/// regeneration is needed.
+pub fn update_subtree(src_path: &Path, relative: &Path, options: &Options, stats: &mut ScanStats) -> io::Result<bool> {
+    let summary_path = src_path.join("SUMMARY.md");
+    let Ok(existing) = fs::read_to_string(&summary_path) else { return Ok(false) };
+    let lines: Vec<&str> = existing.lines().collect();
+    let Some((start, end)) = find_children(&lines, relative, options) else { return Ok(false) };
+    // Telling shared titles apart and nesting under rollup pages need
+    // the whole section
+    if !matches!(options.disambiguator_for(relative), Disambiguator::None) || options.rollup.is_some() {
+        return Ok(false);
+    }
+
+    let mut children = Vec::new();
+    summary::write_subtree(src_path, relative, options, stats, &mut children)?;
+    // A series may gather entries from outside the directory
+    if stats.front_matter.values().any(|front| front.series.is_some()) {
+        return Ok(false);
+    }
+    let children = String::from_utf8_lossy(&children);
+
+    let mut content = String::with_capacity(existing.len() + children.len());
+    for line in &lines[..start] {
+        content.push_str(line);
+        content.push('\n');
+    }
+    content.push_str(&children);
+    for line in &lines[end..] {
+        content.push_str(line);
+        content.push('\n');
+    }
+    generated::write_if_changed(&summary_path, content.as_bytes(), options.line_ending)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn siblings_of_the_subtree_stay_byte_identical() {
+        let src = std::env::temp_dir().join(format!("mdbook-daily-partial-{}", std::process::id()));
+        for (path, content) in [
+            ("journal/2025/README.md", "# 2025"),
+            ("journal/2025/1231.md", "# Last"),
+            ("journal/2026/README.md", "# 2026"),
+            ("journal/2026/0101.md", "# First"),
+            ("notes/ideas.md", "# Ideas"),
+        ] {
+            let path = src.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        // Hand edits outside the subtree, which a full regeneration would undo
+        let existing = "# Summary\n\n# Journal\n\n\
+            - [2025](./journal/2025/README.md)\n  \
+              - [Last  day](./journal/2025/1231.md)\n\
+            - [2026](./journal/2026/README.md)\n  \
+              - [First](./journal/2026/0101.md)\n\n\
+            # Notes\n\n\
+            - [Ideas](./notes/ideas.md) <!-- kept -->\n";
+        fs::write(src.join("SUMMARY.md"), existing).unwrap();
+        fs::write(src.join("journal/2026/0102.md"), "# Second").unwrap();
+
+        let updated = update_subtree(&src, Path::new("journal/2026"), &Options::default(), &mut ScanStats::default());
+        let summary = fs::read_to_string(src.join("SUMMARY.md"));
+        fs::remove_dir_all(&src).unwrap();
+        assert!(updated.unwrap());
+        assert_eq!(summary.unwrap(), existing.replace(
+            "  - [First](./journal/2026/0101.md)\n",
+            "  - [First](./journal/2026/0101.md)\n  - [Second](./journal/2026/0102.md)\n",
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::config::{self, Config};
+use crate::date::{date_from_path, Date};
+use crate::frontmatter;
+use crate::link::relative_link;
+use crate::links::markdown_files;
+use crate::new::pick_section;
+use crate::summary::{capitalize_first, ScanStats};
+
+/// `recurring` keys, in week order starting on Monday
+pub const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// Open `- [ ]` tasks in the section's notes dated in the week before
+/// `monday`, including that week's planning page, without duplicates
+fn carried_over(src_path: &Path, section: &str, monday: Date) -> io::Result<Vec<String>> {
+    let week = (monday.days() - 7)..monday.days();
+    let mut tasks: Vec<String> = Vec::new();
+    for path in markdown_files(&src_path.join(section)) {
+        let content = fs::read_to_string(&path)?;
+        let (front, body) = frontmatter::split(&content);
+        let relative = path.strip_prefix(src_path).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+        let date = front.as_ref().and_then(|f| f.week.or(f.date)).or_else(|| date_from_path(&relative));
+        if !date.is_some_and(|d| week.contains(&d.days())) {
+            continue;
+        }
+        for line in body.lines() {
+            let Some(task) = ["- [ ] ", "* [ ] "].iter().find_map(|p| line.trim_start().strip_prefix(p)) else { continue };
+            let task = task.trim().to_string();
+            if !task.is_empty() && !tasks.contains(&task) {
+                tasks.push(task);
+            }
+        }
+    }
+    Ok(tasks)
+}
+
+/// The planning page for the week starting `monday`, kept in `dir`
+fn render(monday: Date, dir: &Path, carried: &[String], recurring: &HashMap<String, Vec<String>>) -> String {
+    let title = format!("Week of {}", monday);
+    let mut page = format!("---\ntitle: {}\ndate: {}\nweek: {}\n---\n# {}\n", title, monday, monday, title);
+    if !carried.is_empty() {
+        page.push_str("\n## Carried over\n\n");
+        for task in carried {
+            page.push_str(&format!("- [ ] {}\n", task));
+        }
+    }
+    let weekly = recurring.get("weekly").into_iter().flatten().cloned();
+    let daily = WEEKDAYS.iter().enumerate().flat_map(|(i, day)| {
+        let date = Date::from_days(monday.days() + i as i64);
+        recurring.get(*day).into_iter().flatten().map(move |task| format!("{} {}: {}", capitalize_first(day), date, task))
+    });
+    let scheduled: Vec<String> = weekly.chain(daily).collect();
+    if !scheduled.is_empty() {
+        page.push_str("\n## Recurring\n\n");
+        for task in scheduled {
+            page.push_str(&format!("- [ ] {}\n", task));
+        }
+    }
+    page.push_str("\n## Days\n\n");
+    for (i, day) in WEEKDAYS.iter().enumerate() {
+        let date = Date::from_days(monday.days() + i as i64);
+        let link = relative_link(dir, &date.entry_path());
+        page.push_str(&format!("- [{} {}]({})\n", capitalize_first(day), date, link));
+    }
+    page
+}
+
+/// `mdbook-daily plan [--week this|next|YYYY-MM-DD] [--section NAME]`:
+/// create the planning page for a week (next week by default) as
+/// `SECTION/YYYY/plan-MMDD.md`, named after its Monday, with the open
+/// tasks of the week before, the configured `recurring` tasks and links
+/// to the week's daily notes, then regenerate SUMMARY.md. Days without
+/// a note yet show up under the page as draft chapters.
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut day = Date::from_days(Date::today().days() + 7);
+    let mut section = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--week" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--week needs this, next or a date"))?;
+                day = match value.as_str() {
+                    "this" => Date::today(),
+                    "next" => Date::from_days(Date::today().days() + 7),
+                    _ => date_from_path(value).ok_or_else(|| {
+                        crate::usage_error(&format!("invalid week {:?}, expected this, next or YYYY-MM-DD", value))
+                    })?,
+                };
+            }
+            "--section" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--section needs a directory"))?;
+                section = Some(value.clone());
+            }
+            _ => return Err(crate::usage_error(&format!("unknown plan argument {:?}", arg))),
+        }
+    }
+
+    let mut config = Config::load(Path::new("."))?;
+    let recurring = std::mem::take(&mut config.recurring);
+    let options = config::book_options(Path::new("."))?;
+    let section = pick_section(src_path, &options, section, "plan")?;
+
+    let monday = day.monday();
+    let year_dir = src_path.join(&section).join(monday.year.to_string());
+    let path = year_dir.join(format!("plan-{:02}{:02}.md", monday.month, monday.day));
+    if path.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{:?} already exists", path)));
+    }
+    let carried = carried_over(src_path, &section, monday)?;
+    fs::create_dir_all(&year_dir)?;
+    // Without a README the year would only be a draft chapter
+    let readme = year_dir.join("README.md");
+    if !readme.exists() {
+        fs::write(&readme, format!("# {}\n", monday.year))?;
+    }
+    fs::write(&path, render(monday, Path::new(&monday.year.to_string()), &carried, &recurring))?;
+
+    let mut stats = ScanStats::default();
+    crate::write_summary(src_path, &options, &mut stats)?;
+    stats.print_warnings();
+    println!("{}", path.display());
+    Ok(())
+}
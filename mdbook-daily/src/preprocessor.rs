@@ -0,0 +1,242 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use mdbook::book::{self, Book, BookItem};
+use mdbook::errors::Result;
+use mdbook::preprocess::{CmdPreprocessor, Preprocessor, PreprocessorContext};
+
+use crate::config;
+use crate::directives;
+use crate::frontmatter;
+use crate::generated;
+use crate::head;
+use crate::images;
+use crate::navigation;
+use crate::related;
+use crate::summary::{self, Options, ScanStats};
+use crate::wikilinks;
+
+/// Regenerates SUMMARY.md before every `mdbook build` or `mdbook serve`
+/// reload and hands mdBook the book loaded from the fresh summary.
+///
+/// Configured under `[preprocessor.daily]` in book.toml with the same
+/// settings as the `update` flags, in kebab-case: `year-separators`,
+/// `exclude-older-than`, `archive-page`, `acronym-index`, `generated-dir`,
+/// `plain-titles`, `changes-since`, `team`, `time-log`, `tag-index`,
+/// `tag-pages`, `series-pages`, `calendar`, `stats-page`, `include-drafts`, `section-markers`,
+/// `max-file-size`, `head-size`, `jobs`, `reads-per-second`, `recent-entries`,
+/// `on-this-day`, `feed-entries`, `feed-html`, `feed-sections`,
+/// `hydrate-placeholders`, `strict`, `line-endings` (defaulting to the
+/// book's `.editorconfig`) and `site-url` (which falls back to
+/// `output.html.site-url`). `missing-image-placeholders` has no flag: it
+/// swaps images whose file is missing for a visible placeholder, with a
+/// warning, so they stand out in previews. `entry-navigation` has none
+/// either: it ends each dated entry with links to the previous and next
+/// entry of its section and a list of the chapters linking to it.
+/// `series-navigation` heads each part of a series with "Part 2 of 5" and
+/// links to the previous and next part.
+/// `wiki-links` turns Obsidian-style `[[Page Name]]` links into markdown
+/// links to the chapter with that path, date, file name or title; set it
+/// to `keep`, `strip` or `warn` for what happens to links that match no
+/// chapter.
+/// `related-notes = N` ends each chapter with the N notes most related
+/// to it, ranked by the signals of `related-by` in order: shared `tags`,
+/// `links` between them or to the same pages, and shared `titles` words.
+///
+/// Chapters can hold `{{#daily latest}}` (a link to the newest entry),
+/// `{{#daily recent N}}` (a list of the N newest) and `{{#daily today}}`
+/// (the body of today's entry), expanded on every build, and
+/// `{{#daily include PATH#HEADING}}`, which embeds the section of
+/// another note under that heading, or all of it without one.
+///
+/// Front matter `canonical` and `description` values are written into
+/// the theme's head.hbs, where mdBook adds them to each chapter's page.
+/// Each old path in an entry's `aliases` gets an HTML page redirecting to
+/// the entry.
+///
+/// The same table, or a standalone daily.toml next to book.toml, also
+/// holds the layout settings described on [`config::Config`].
+pub struct Daily;
+
+impl Daily {
+    fn options(ctx: &PreprocessorContext) -> Result<Options> {
+        let site_url = ctx.config.get("output.html.site-url").and_then(|v| v.as_str());
+        let src_path = ctx.root.join(&ctx.config.book.src);
+        Ok(config::options_for(&ctx.root, &src_path, ctx.config.get_preprocessor("daily"), site_url)?)
+    }
+}
+
+impl Preprocessor for Daily {
+    fn name(&self) -> &str {
+        "daily"
+    }
+
+    fn run(&self, ctx: &PreprocessorContext, _book: Book) -> Result<Book> {
+        let src_path = ctx.root.join(&ctx.config.book.src);
+        let options = Daily::options(ctx)?;
+        let mut stats = ScanStats::default();
+        let summary_path = src_path.join("SUMMARY.md");
+        let existing = fs::read_to_string(&summary_path).ok();
+        let content = summary::merge_managed(existing.as_deref(), summary::generate_summary(&src_path, &options, &mut stats)?);
+        stats.check_strict(options.strict)?;
+        generated::write_if_changed(&summary_path, content.as_bytes(), options.line_ending)?;
+        generated::write_pages(&src_path, &options, &stats)?;
+        let theme_dir = ctx.config.html_config().unwrap_or_default().theme_dir(&ctx.root);
+        head::write_head(&theme_dir, &stats, options.line_ending)?;
+        stats.print_warnings();
+        // The book mdBook passed in was loaded from the old summary
+        let mut book = book::load_book(&src_path, &ctx.config.build)?;
+        strip_front_matter(&mut book);
+        if options.missing_image_placeholders {
+            book.for_each_mut(|item| {
+                let BookItem::Chapter(chapter) = item else { return };
+                let Some(source) = &chapter.source_path else { return };
+                let dir = src_path.join(source).parent().map(PathBuf::from).unwrap_or_else(|| src_path.clone());
+                let (content, missing) = images::mark_missing_images(&chapter.content, &dir);
+                for target in missing {
+                    eprintln!("Warning: {}: missing image {}", source.display(), target);
+                }
+                chapter.content = content;
+            });
+        }
+        directives::expand_directives(&mut book, &src_path, &stats, &options);
+        // Before the navigation pass, so wiki-links count as backlinks
+        if let Some(unresolved) = options.wiki_links {
+            wikilinks::add_wiki_links(&mut book, &stats, unresolved);
+        }
+        if options.entry_navigation {
+            navigation::add_navigation(&mut book, &stats, &options);
+        }
+        if options.series_navigation {
+            navigation::add_series_navigation(&mut book, &stats, &options);
+        }
+        if let Some(count) = options.related_notes {
+            related::add_related(&mut book, &stats, &options, count);
+        }
+        unnumber_sections(&mut book, &options);
+        Ok(book)
+    }
+
+    fn supports_renderer(&self, _renderer: &str) -> bool {
+        true
+    }
+}
+
+/// Drop each chapter's front matter, which is for the generator, not the
+/// rendered page. A leading `---` block that is not front matter is a
+/// pair of thematic breaks and stays.
+fn strip_front_matter(book: &mut Book) {
+    book.for_each_mut(|item| {
+        let BookItem::Chapter(chapter) = item else { return };
+        let body = frontmatter::split(&chapter.content).1;
+        if body.len() != chapter.content.len() {
+            chapter.content = body.to_string();
+        }
+    });
+}
+
+/// Drop the chapter numbers of the sections whose `section-layout` sets
+/// `numbered = false`. mdBook's summary grammar numbers every chapter in
+/// a part, but renders a chapter without a number as it is.
+fn unnumber_sections(book: &mut Book, options: &Options) {
+    let unnumbered: Vec<&str> = options.section_layout.iter()
+        .filter(|(_, layout)| !layout.numbered)
+        .map(|(section, _)| section.as_str())
+        .collect();
+    if unnumbered.is_empty() {
+        return;
+    }
+    book.for_each_mut(|item| {
+        let BookItem::Chapter(chapter) = item else { return };
+        let section = chapter.source_path.as_ref().and_then(|path| path.components().next());
+        if section.is_some_and(|section| unnumbered.iter().any(|name| section.as_os_str() == *name)) {
+            chapter.number = None;
+        }
+    });
+}
+
+/// The answer to mdBook's `supports RENDERER` query, which the binary
+/// gives as its exit code
+pub fn supports(renderer: &str) -> bool {
+    Daily.supports_renderer(renderer)
+}
+
+/// Read the `[context, book]` JSON mdBook sends on stdin and write the
+/// processed book to stdout
+pub fn run() -> Result<()> {
+    let (ctx, book) = CmdPreprocessor::parse_input(io::stdin())?;
+    if ctx.mdbook_version != mdbook::MDBOOK_VERSION {
+        eprintln!(
+            "Warning: mdbook-daily was built against mdBook {}, but is being called from {}",
+            mdbook::MDBOOK_VERSION, ctx.mdbook_version
+        );
+    }
+    let processed = Daily.run(&ctx, book)?;
+    serde_json::to_writer(io::stdout(), &processed)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::SectionLayout;
+
+    fn chapter(path: &str, content: &str) -> BookItem {
+        BookItem::Chapter(book::Chapter::new("Chapter", content.to_string(), path, Vec::new()))
+    }
+
+    fn contents(book: &Book) -> Vec<(String, Option<String>)> {
+        book.iter().filter_map(|item| match item {
+            BookItem::Chapter(chapter) => Some((chapter.content.clone(), chapter.number.as_ref().map(ToString::to_string))),
+            _ => None,
+        }).collect()
+    }
+
+    #[test]
+    fn front_matter_is_stripped_and_thematic_breaks_stay() {
+        let mut book = Book::new();
+        book.push_item(chapter("a.md", "---\ntags: [x]\n---\n# A\n"));
+        book.push_item(chapter("b.md", "---\nA quote set off by rules\n---\n"));
+        strip_front_matter(&mut book);
+        let contents: Vec<String> = contents(&book).into_iter().map(|(content, _)| content).collect();
+        assert_eq!(contents, ["# A\n", "---\nA quote set off by rules\n---\n"]);
+    }
+
+    #[test]
+    fn unnumbered_sections_lose_their_numbers() {
+        let mut book = Book::new();
+        for (path, number) in [("journal/2026/1014.md", 1), ("notes/ideas.md", 2)] {
+            let mut item = chapter(path, "");
+            if let BookItem::Chapter(chapter) = &mut item {
+                chapter.number = Some(book::SectionNumber(vec![number]));
+            }
+            book.push_item(item);
+        }
+        let mut options = Options::default();
+        options.section_layout.insert("journal".to_string(), SectionLayout { numbered: false, ..SectionLayout::default() });
+        unnumber_sections(&mut book, &options);
+        let numbers: Vec<Option<String>> = contents(&book).into_iter().map(|(_, number)| number).collect();
+        assert_eq!(numbers, [None, Some("2.".to_string())]);
+    }
+
+    #[test]
+    fn run_regenerates_the_summary_and_loads_the_book_from_it() {
+        let root = std::env::temp_dir().join(format!("mdbook-daily-preprocessor-{}", std::process::id()));
+        let notes = root.join("src/journal/2026");
+        fs::create_dir_all(&notes).unwrap();
+        fs::write(notes.join("README.md"), "# 2026\n").unwrap();
+        fs::write(notes.join("1014.md"), "---\ntitle: Wednesday\n---\nBody\n").unwrap();
+        let input = serde_json::json!([
+            {"root": root, "config": {"book": {"src": "src"}}, "renderer": "html", "mdbook_version": mdbook::MDBOOK_VERSION},
+            {"sections": [], "__non_exhaustive": null},
+        ]);
+        let (ctx, book) = CmdPreprocessor::parse_input(input.to_string().as_bytes()).unwrap();
+        let book = Daily.run(&ctx, book);
+        let summary = fs::read_to_string(root.join("src/SUMMARY.md"));
+        fs::remove_dir_all(&root).unwrap();
+        let contents: Vec<String> = contents(&book.unwrap()).into_iter().map(|(content, _)| content).collect();
+        assert!(summary.unwrap().contains("[Wednesday](./journal/2026/1014.md)"));
+        assert_eq!(contents, ["# 2026\n", "Body\n"]);
+    }
+}
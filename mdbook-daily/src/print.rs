@@ -0,0 +1,61 @@
+use std::io::{self, Write};
+
+use crate::date::{date_from_path, Date};
+use crate::locale::Strings;
+use crate::summary::{escape_title, parse_entries};
+
+/// Write a flattened, chronological SUMMARY suited to mdBook's print
+/// output: prefix chapters first, then dated entries under one part per
+/// month, then everything undated in its original order
+pub fn write_print_summary(strings: &Strings, summary: &str, out: &mut dyn Write) -> io::Result<()> {
+    let first_part = summary.lines()
+        .enumerate()
+        .skip(1)
+        .find(|(_, line)| line.starts_with("# "))
+        .map(|(i, _)| i)
+        .unwrap_or(usize::MAX);
+
+    let mut prefix = Vec::new();
+    let mut dated: Vec<(Date, String, String)> = Vec::new();
+    let mut undated = Vec::new();
+    for entry in parse_entries(summary) {
+        let Some(path) = entry.path else { continue };
+        if entry.line < first_part {
+            prefix.push((entry.title, path));
+        } else if let Some(date) = date_from_path(&path) {
+            dated.push((date, entry.title, path));
+        } else {
+            undated.push((entry.title, path));
+        }
+    }
+    dated.sort_by(|a, b| (a.0, &a.2).cmp(&(b.0, &b.2)));
+
+    writeln!(out, "# {}", strings.summary)?;
+    if !prefix.is_empty() {
+        writeln!(out)?;
+        for (title, path) in &prefix {
+            writeln!(out, "- [{}]({})", escape_title(title), path)?;
+        }
+    }
+
+    let mut current_month = None;
+    for (date, title, path) in &dated {
+        if current_month != Some((date.year, date.month)) {
+            current_month = Some((date.year, date.month));
+            writeln!(out)?;
+            writeln!(out, "# {}", strings.month_title(*date))?;
+            writeln!(out)?;
+        }
+        writeln!(out, "- [{}]({})", escape_title(title), path)?;
+    }
+
+    if !undated.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "# {}", strings.other)?;
+        writeln!(out)?;
+        for (title, path) in &undated {
+            writeln!(out, "- [{}]({})", escape_title(title), path)?;
+        }
+    }
+    Ok(())
+}
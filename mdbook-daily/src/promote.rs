@@ -0,0 +1,63 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use toml::value::Value;
+
+use crate::config;
+use crate::frontmatter;
+use crate::summary::ScanStats;
+
+/// The status after `current` in `statuses`: the first one for a note
+/// without a status, `None` for one at the last
+pub fn next_status<'a>(statuses: &'a [String], current: Option<&str>) -> Result<Option<&'a String>, String> {
+    let Some(current) = current else { return Ok(statuses.first()) };
+    match statuses.iter().position(|status| status == current) {
+        Some(i) => Ok(statuses.get(i + 1)),
+        None => Err(format!("status {:?} is not one of {}", current, statuses.join(", "))),
+    }
+}
+
+/// `mdbook-daily promote FILE`: advance a note to the next of the
+/// configured `statuses`, then regenerate SUMMARY.md, which lists the
+/// note once it leaves the `hidden-statuses`
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let [file] = args else {
+        return Err(crate::usage_error("promote needs exactly one file"));
+    };
+    let file = Path::new(file);
+    let path = src_path.join(file.strip_prefix(src_path).unwrap_or(file));
+    if !path.is_file() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("{:?} is not a file", path)));
+    }
+    let options = config::book_options(Path::new("."))?;
+    let content = fs::read_to_string(&path)?;
+    let current = frontmatter::split(&content).0.and_then(|front| front.status);
+    let next = next_status(&options.statuses, current.as_deref())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e)))?;
+    let Some(next) = next else {
+        println!("{} is already {}", path.display(), current.unwrap_or_default());
+        return Ok(());
+    };
+    fs::write(&path, frontmatter::set_keys(&content, &[("status", Value::String(next.clone()))]))?;
+    println!("{}: {} -> {}", path.display(), current.as_deref().unwrap_or("none"), next);
+
+    let mut stats = ScanStats::default();
+    crate::write_summary(src_path, &options, &mut stats)?;
+    stats.print_warnings();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_advance_in_order() {
+        let statuses = ["seed", "growing", "evergreen"].map(String::from);
+        assert_eq!(next_status(&statuses, None).unwrap().map(String::as_str), Some("seed"));
+        assert_eq!(next_status(&statuses, Some("seed")).unwrap().map(String::as_str), Some("growing"));
+        assert_eq!(next_status(&statuses, Some("evergreen")).unwrap(), None);
+        assert!(next_status(&statuses, Some("done")).is_err());
+    }
+}
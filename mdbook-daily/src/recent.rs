@@ -0,0 +1,101 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::eol;
+use crate::frontmatter;
+use crate::generated;
+use crate::links::chapter_link;
+use crate::sources;
+use crate::summary::{escape_title, managed_region, merge_managed, read_text, Options, ScanStats, MANAGED_END, MANAGED_START};
+
+/// Page (relative to src) listing the most recent entries, pinned after
+/// the other pinned chapters
+pub const RECENT_PAGE: &str = "recent.md";
+
+/// Longest excerpt, in characters, before it is cut at a word
+const EXCERPT_LEN: usize = 200;
+
+/// The first paragraph of a note's text, on one line and cut to about
+/// `max` characters. Front matter, headings, fenced code, HTML comments
+/// and image-only lines are skipped.
+pub fn excerpt(content: &str, max: usize) -> Option<String> {
+    let (_, body) = frontmatter::split(content);
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    let mut in_comment = false;
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_comment || trimmed.starts_with("<!--") {
+            in_comment = !trimmed.contains("-->");
+            continue;
+        }
+        let skipped = in_fence || trimmed.starts_with('#') || (trimmed.starts_with("![") && trimmed.ends_with(')'));
+        if trimmed.is_empty() || skipped {
+            if !lines.is_empty() {
+                break;
+            }
+            continue;
+        }
+        lines.push(trimmed);
+    }
+    let text = lines.join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= max {
+        return Some(text);
+    }
+    let cut: String = text.chars().take(max).collect();
+    let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+    Some(format!("{}…", cut.trim_end_matches([',', ';', ':', '.'])))
+}
+
+/// Write the list of the `count` most recent dated entries, newest first,
+/// each with its date and an excerpt
+pub fn write_recent_list(src_path: &Path, options: &Options, stats: &ScanStats, count: usize, out: &mut dyn Write) -> io::Result<usize> {
+    let mut dated: Vec<_> = stats.dated.iter().collect();
+    dated.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.cmp(&b.2)));
+    dated.truncate(count);
+    for (date, title, link) in &dated {
+        let relative = Path::new(link.trim_start_matches("./"));
+        writeln!(out, "- [{}]({}) ({})", escape_title(title), chapter_link(Path::new(""), relative), date)?;
+        let content = read_text(&src_path.join(relative), options.head_size).unwrap_or_default();
+        if let Some(excerpt) = excerpt(&content, EXCERPT_LEN) {
+            writeln!(out)?;
+            writeln!(out, "  {}", excerpt)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(dated.len())
+}
+
+/// Write `recent.md`, only replacing its managed region when it already
+/// exists. Returns the number of entries listed.
+pub fn write_recent_page(src_path: &Path, options: &Options, stats: &ScanStats, count: usize) -> io::Result<usize> {
+    let path = src_path.join(RECENT_PAGE);
+    let existing = fs::read_to_string(&path).ok();
+    if existing.as_deref().is_some_and(|e| managed_region(e).is_none()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} has no {} ... {} region to fill in", path.display(), MANAGED_START, MANAGED_END),
+        ));
+    }
+    let mut list = Vec::new();
+    let listed = write_recent_list(src_path, options, stats, count, &mut list)?;
+    let list = String::from_utf8_lossy(&list);
+    let content = match existing {
+        Some(existing) => merge_managed(Some(&existing), list.into_owned()),
+        None => format!("# {}\n\n{}\n\n{}\n\n{}\n", options.strings.recent_entries, MANAGED_START, list.trim(), MANAGED_END),
+    };
+    if fs::read(&path).is_ok_and(|e| e == eol::normalize(content.as_bytes(), options.line_ending)) {
+        return Ok(listed);
+    }
+    sources::check_write(options.read_only_sources, src_path, &options.generated_dir, &path)?;
+    generated::write_if_changed(&path, content.as_bytes(), options.line_ending)?;
+    Ok(listed)
+}
@@ -0,0 +1,179 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use mdbook::book::{Book, BookItem};
+
+use crate::links::chapter_link;
+use crate::navigation::backlinks;
+use crate::summary::{escape_title, Options, ScanStats};
+
+/// What makes two notes related, listed in `related-by` in the order
+/// they rank by
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RelatedBy {
+    /// Tags both notes carry
+    Tags,
+    /// Links between the notes, and pages both link to
+    Links,
+    /// Words both titles use
+    Titles,
+}
+
+impl RelatedBy {
+    pub fn parse(value: &str) -> Option<RelatedBy> {
+        match value {
+            "tags" => Some(RelatedBy::Tags),
+            "links" => Some(RelatedBy::Links),
+            "titles" => Some(RelatedBy::Titles),
+            _ => None,
+        }
+    }
+}
+
+/// What is known about one chapter for relating it to the others
+struct Note {
+    title: String,
+    tags: BTreeSet<String>,
+    /// Chapters it links to, relative to src
+    links: HashSet<PathBuf>,
+    /// Lowercased title words of three or more characters
+    words: BTreeSet<String>,
+}
+
+fn title_words(title: &str) -> BTreeSet<String> {
+    title.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// How related `b` is to `a` by each signal of `by`, in order
+fn scores(a: (&Path, &Note), b: (&Path, &Note), by: &[RelatedBy]) -> Vec<usize> {
+    by.iter().map(|signal| match signal {
+        RelatedBy::Tags => a.1.tags.intersection(&b.1.tags).count(),
+        RelatedBy::Links => {
+            let direct = usize::from(a.1.links.contains(b.0)) + usize::from(b.1.links.contains(a.0));
+            direct + a.1.links.intersection(&b.1.links).count()
+        }
+        RelatedBy::Titles => a.1.words.intersection(&b.1.words).count(),
+    }).collect()
+}
+
+/// The notes of `book` to relate: every chapter with a source outside the
+/// generated pages, by source path
+fn notes(book: &Book, stats: &ScanStats, options: &Options) -> HashMap<PathBuf, Note> {
+    let mut notes: HashMap<PathBuf, Note> = HashMap::new();
+    for item in book.iter() {
+        let BookItem::Chapter(chapter) = item else { continue };
+        let Some(source) = &chapter.source_path else { continue };
+        if source.starts_with(&options.generated_dir) {
+            continue;
+        }
+        let link = format!("./{}", source.to_string_lossy().replace('\\', "/"));
+        let tags = stats.front_matter.get(&link).map(|front| front.tags.iter().cloned().collect()).unwrap_or_default();
+        notes.insert(source.clone(), Note { title: chapter.name.clone(), tags, links: HashSet::new(), words: title_words(&chapter.name) });
+    }
+    for (target, sources) in backlinks(book, options) {
+        for (_, source) in sources {
+            if let Some(note) = notes.get_mut(&source) {
+                note.links.insert(target.clone());
+            }
+        }
+    }
+    notes
+}
+
+/// The `count` notes most related to each one, best first. Notes only
+/// count as related when one of the `by` signals finds something in
+/// common; ties go to the path that sorts first.
+fn related(notes: &HashMap<PathBuf, Note>, by: &[RelatedBy], count: usize) -> HashMap<PathBuf, Vec<PathBuf>> {
+    // Notes sharing a tag, a title word or a link, so not every pair is scored
+    let mut shared: HashMap<String, Vec<&Path>> = HashMap::new();
+    for (path, note) in notes {
+        let keys = note.tags.iter().filter(|_| by.contains(&RelatedBy::Tags)).map(|tag| format!("tag:{}", tag))
+            .chain(note.words.iter().filter(|_| by.contains(&RelatedBy::Titles)).map(|word| format!("word:{}", word)))
+            .chain(note.links.iter().filter(|_| by.contains(&RelatedBy::Links)).map(|target| format!("link:{}", target.display())));
+        for key in keys {
+            shared.entry(key).or_default().push(path);
+        }
+    }
+    let mut candidates: HashMap<&Path, BTreeSet<&Path>> = HashMap::new();
+    for paths in shared.values() {
+        for a in paths {
+            candidates.entry(a).or_default().extend(paths.iter().filter(|b| *b != a));
+        }
+    }
+    if by.contains(&RelatedBy::Links) {
+        for (path, note) in notes {
+            for (target, _) in note.links.iter().filter_map(|target| notes.get_key_value(target)) {
+                candidates.entry(path).or_default().insert(target);
+                candidates.entry(target).or_default().insert(path);
+            }
+        }
+    }
+    let mut related = HashMap::new();
+    for (path, others) in candidates {
+        let Some(note) = notes.get(path) else { continue };
+        let mut ranked: Vec<(Vec<usize>, &Path)> = others.into_iter()
+            .map(|other| (scores((path, note), (other, &notes[other]), by), other))
+            .filter(|(scores, _)| scores.iter().any(|score| *score > 0))
+            .collect();
+        ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+        ranked.truncate(count);
+        if !ranked.is_empty() {
+            related.insert(path.to_path_buf(), ranked.into_iter().map(|(_, other)| other.to_path_buf()).collect());
+        }
+    }
+    related
+}
+
+/// End each chapter with a list of the `related-notes` chapters most
+/// related to it by the `related-by` signals
+pub fn add_related(book: &mut Book, stats: &ScanStats, options: &Options, count: usize) {
+    let notes = notes(book, stats, options);
+    let related = related(&notes, &options.related_by, count);
+    book.for_each_mut(|item| {
+        let BookItem::Chapter(chapter) = item else { return };
+        let Some(source) = &chapter.source_path else { return };
+        let Some(others) = related.get(source) else { return };
+        let dir = source.parent().unwrap_or(Path::new(""));
+        let mut footer = format!("\n\n---\n\n**{}**\n\n", options.strings.related);
+        for other in others {
+            footer.push_str(&format!("- [{}]({})\n", escape_title(&notes[other].title), chapter_link(dir, other)));
+        }
+        chapter.content = format!("{}{}", chapter.content.trim_end(), footer);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(title: &str, tags: &[&str], links: &[&str]) -> Note {
+        Note {
+            title: title.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            links: links.iter().map(PathBuf::from).collect(),
+            words: title_words(title),
+        }
+    }
+
+    #[test]
+    fn related_notes_rank_by_the_signals_in_order() {
+        let notes: HashMap<PathBuf, Note> = [
+            ("a.md", note("Rust lifetimes", &["rust", "types"], &[])),
+            ("b.md", note("Garden", &["rust", "types"], &[])),
+            ("c.md", note("Rust macros", &["rust"], &["a.md"])),
+            ("d.md", note("Unrelated", &["cooking"], &[])),
+        ].into_iter().map(|(path, note)| (PathBuf::from(path), note)).collect();
+        let all = [RelatedBy::Tags, RelatedBy::Links, RelatedBy::Titles];
+        let related_to_a = |by: &[RelatedBy], count| related(&notes, by, count).remove(Path::new("a.md")).unwrap_or_default();
+        assert_eq!(related_to_a(&all, 5), [PathBuf::from("b.md"), PathBuf::from("c.md")]);
+        assert_eq!(related_to_a(&[RelatedBy::Links, RelatedBy::Tags], 5), [PathBuf::from("c.md"), PathBuf::from("b.md")]);
+        assert_eq!(related_to_a(&all, 1), [PathBuf::from("b.md")]);
+        assert_eq!(related_to_a(&[RelatedBy::Titles], 5), [PathBuf::from("c.md")]);
+        // A link alone relates both ends
+        assert_eq!(related_to_a(&[RelatedBy::Links], 5), [PathBuf::from("c.md")]);
+        assert!(!related(&notes, &all, 5).contains_key(Path::new("d.md")));
+    }
+}
@@ -0,0 +1,195 @@
+use std::io::{self, Write};
+
+use crate::summary::escape_title;
+use crate::tree::{BookTree, Chapter};
+
+/// Writes a [`BookTree`] in one output format. The `tree` formats are
+/// renderers, and library users add their own, such as a wiki sidebar,
+/// by implementing this on top of the same model.
+pub trait SummaryRenderer {
+    fn render(&self, tree: &BookTree, out: &mut dyn Write) -> io::Result<()>;
+}
+
+/// Renderer of a `tree --format` name
+pub fn renderer(format: &str) -> Option<Box<dyn SummaryRenderer>> {
+    match format {
+        "json" => Some(Box::new(Json)),
+        "yaml" => Some(Box::new(Yaml)),
+        "summary" => Some(Box::new(MdBookSummary)),
+        "mkdocs" => Some(Box::new(MkDocsNav)),
+        _ => None,
+    }
+}
+
+/// The model as pretty-printed JSON
+pub struct Json;
+
+impl SummaryRenderer for Json {
+    fn render(&self, tree: &BookTree, out: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, tree).map_err(io::Error::other)?;
+        writeln!(out)
+    }
+}
+
+/// `text` as a double-quoted YAML string, whose escapes are JSON's
+fn quoted(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_default()
+}
+
+/// Write `nodes` as a YAML block sequence indented by `indent` spaces
+fn write_yaml_nodes(nodes: &[Chapter], indent: usize, out: &mut dyn Write) -> io::Result<()> {
+    let pad = " ".repeat(indent);
+    for node in nodes {
+        writeln!(out, "{}- title: {}", pad, quoted(&node.title))?;
+        match &node.path {
+            Some(path) => writeln!(out, "{}  path: {}", pad, quoted(path))?,
+            None => writeln!(out, "{}  path: null", pad)?,
+        }
+        if let Some(date) = &node.date {
+            writeln!(out, "{}  date: {}", pad, quoted(&date.to_string()))?;
+        }
+        if !node.tags.is_empty() {
+            let tags: Vec<String> = node.tags.iter().map(|tag| quoted(tag)).collect();
+            writeln!(out, "{}  tags: [{}]", pad, tags.join(", "))?;
+        }
+        if node.children.is_empty() {
+            writeln!(out, "{}  children: []", pad)?;
+        } else {
+            writeln!(out, "{}  children:", pad)?;
+            write_yaml_nodes(&node.children, indent + 4, out)?;
+        }
+    }
+    Ok(())
+}
+
+/// The model as YAML, with the same shape as [`Json`]
+pub struct Yaml;
+
+impl SummaryRenderer for Yaml {
+    fn render(&self, tree: &BookTree, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "version: {}", tree.version)?;
+        let list = |key: &str, nodes: &[Chapter], out: &mut dyn Write| {
+            if nodes.is_empty() {
+                return writeln!(out, "{}: []", key);
+            }
+            writeln!(out, "{}:", key)?;
+            write_yaml_nodes(nodes, 2, out)
+        };
+        list("prefix", &tree.prefix, out)?;
+        if tree.parts.is_empty() {
+            writeln!(out, "sections: []")?;
+        } else {
+            writeln!(out, "sections:")?;
+            for part in &tree.parts {
+                writeln!(out, "  - title: {}", quoted(&part.title))?;
+                if part.chapters.is_empty() {
+                    writeln!(out, "    chapters: []")?;
+                } else {
+                    writeln!(out, "    chapters:")?;
+                    write_yaml_nodes(&part.chapters, 6, out)?;
+                }
+            }
+        }
+        list("suffix", &tree.suffix, out)
+    }
+}
+
+/// The model as mdBook SUMMARY.md: prefix chapters, the parts and
+/// unnumbered suffix chapters. Separators and comments of the
+/// generated summary are not part of the model.
+pub struct MdBookSummary;
+
+fn write_summary_entries(entries: &[Chapter], indent: usize, out: &mut dyn Write) -> io::Result<()> {
+    for entry in entries {
+        let link = entry.path.as_ref().map(|path| format!("./{}", path)).unwrap_or_default();
+        writeln!(out, "{}- [{}]({})", " ".repeat(indent), escape_title(&entry.title), link)?;
+        write_summary_entries(&entry.children, indent + 2, out)?;
+    }
+    Ok(())
+}
+
+impl SummaryRenderer for MdBookSummary {
+    fn render(&self, tree: &BookTree, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "# Summary")?;
+        writeln!(out)?;
+        write_summary_entries(&tree.prefix, 0, out)?;
+        for part in &tree.parts {
+            writeln!(out)?;
+            writeln!(out, "# {}", part.title)?;
+            writeln!(out)?;
+            write_summary_entries(&part.chapters, 0, out)?;
+        }
+        if !tree.suffix.is_empty() {
+            writeln!(out)?;
+        }
+        for entry in &tree.suffix {
+            writeln!(out, "[{}](./{})", escape_title(&entry.title), entry.path.as_deref().unwrap_or_default())?;
+        }
+        Ok(())
+    }
+}
+
+/// The model as the `nav` setting of an mkdocs.yml. A chapter with
+/// children becomes a section starting with its own page; drafts without
+/// a page are left out.
+pub struct MkDocsNav;
+
+fn write_nav_entries(entries: &[Chapter], indent: usize, out: &mut dyn Write) -> io::Result<()> {
+    let pad = " ".repeat(indent);
+    for entry in entries {
+        match (&entry.path, entry.children.is_empty()) {
+            (Some(path), true) => writeln!(out, "{}- {}: {}", pad, quoted(&entry.title), quoted(path))?,
+            (None, true) => {}
+            (path, false) => {
+                writeln!(out, "{}- {}:", pad, quoted(&entry.title))?;
+                if let Some(path) = path {
+                    writeln!(out, "{}    - {}", pad, quoted(path))?;
+                }
+                write_nav_entries(&entry.children, indent + 4, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+impl SummaryRenderer for MkDocsNav {
+    fn render(&self, tree: &BookTree, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "nav:")?;
+        write_nav_entries(&tree.prefix, 2, out)?;
+        for part in &tree.parts {
+            writeln!(out, "  - {}:", quoted(&part.title))?;
+            write_nav_entries(&part.chapters, 6, out)?;
+        }
+        write_nav_entries(&tree.suffix, 2, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SummaryBuilder;
+    use crate::tree::Part;
+
+    #[test]
+    fn summary_renderer_round_trips() {
+        let builder = SummaryBuilder::from_files([
+            ("journal/2026/README.md", "# 2026"),
+            ("journal/2026/0115.md", "# A [bracketed] title"),
+            ("notes/ideas.md", "# Ideas"),
+        ])
+        .unwrap();
+        let tree = builder.tree().unwrap();
+        let mut summary = Vec::new();
+        MdBookSummary.render(&tree, &mut summary).unwrap();
+        let reparsed = BookTree::parse(&String::from_utf8(summary).unwrap());
+        // Dates and tags are not part of SUMMARY.md
+        assert_eq!(reparsed.parts, tree.parts.iter().map(|part| Part {
+            title: part.title.clone(),
+            chapters: part.chapters.iter().map(without_metadata).collect(),
+        }).collect::<Vec<_>>());
+    }
+
+    fn without_metadata(chapter: &Chapter) -> Chapter {
+        Chapter { date: None, tags: Vec::new(), children: chapter.children.iter().map(without_metadata).collect(), ..chapter.clone() }
+    }
+}
@@ -0,0 +1,129 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use mdbook::config::Config as BookConfig;
+
+use crate::config;
+use crate::date::{date_from_path, Date};
+use crate::summary::{self, ScanStats};
+use crate::url::page_url;
+
+/// Where `mdbook serve` listens unless told otherwise
+const SERVE_URL: &str = "http://localhost:3000";
+
+/// Site URL of the book in `root`: `site-url` in `[preprocessor.daily]`,
+/// then `output.html.site-url` when it is absolute, then the local
+/// `mdbook serve` address
+fn site_url(root: &Path) -> String {
+    let book_toml = root.join("book.toml");
+    let config = BookConfig::from_disk(&book_toml).ok();
+    let text = |key: &str| config.as_ref()?.get(key)?.as_str().map(str::to_string);
+    text("preprocessor.daily.site-url")
+        .or_else(|| text("output.html.site-url").filter(|url| url.contains("://")))
+        .unwrap_or_else(|| SERVE_URL.to_string())
+}
+
+/// The note (relative to src) for a `today`, `yesterday`, `YYYY-MM-DD`
+/// or path argument
+fn target(src_path: &Path, arg: &str) -> io::Result<PathBuf> {
+    let date = match arg {
+        "today" => Some(Date::today()),
+        "yesterday" => Some(Date::from_days(Date::today().days() - 1)),
+        _ if arg.len() == 10 => date_from_path(arg),
+        _ => None,
+    };
+    let Some(date) = date else {
+        let path = Path::new(arg);
+        // A path from the book root or the working directory, else from src
+        let path = if path.exists() { path.to_path_buf() } else { src_path.join(path) };
+        if !path.exists() {
+            return Err(crate::usage_error(&format!("{} does not exist", path.display())));
+        }
+        let src = src_path.canonicalize()?;
+        let path = path.canonicalize()?;
+        return path.strip_prefix(&src)
+            .map(Path::to_path_buf)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("{} is not inside src", path.display())));
+    };
+
+    let options = config::book_options(Path::new("."))?;
+    let mut stats = ScanStats::default();
+    summary::write_summary_to(src_path, &options, &mut stats, &mut io::sink())?;
+    let links: Vec<&str> = stats.dated.iter()
+        .filter(|(d, _, _)| *d == date)
+        .map(|(_, _, link)| link.trim_start_matches("./"))
+        .collect();
+    match links.as_slice() {
+        [] => Err(crate::usage_error(&format!("no entry is dated {}", date))),
+        [link] => Ok(PathBuf::from(link)),
+        _ => Err(crate::usage_error(&format!("several entries are dated {}, pass one of: {}", date, links.join(", ")))),
+    }
+}
+
+/// Open `arg` (a directory or an http URL) with the desktop's default
+/// application
+fn open(arg: &str) -> io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        // `start` treats its first quoted argument as a window title
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command.arg(arg).status()
+        .map_err(|e| io::Error::new(e.kind(), format!("could not open {}: {}", arg, e)))?;
+    if !status.success() {
+        return Err(io::Error::other(format!("could not open {}: {}", arg, status)));
+    }
+    Ok(())
+}
+
+/// `mdbook-daily reveal [today|yesterday|YYYY-MM-DD|PATH] [--browser]
+/// [--site-url URL] [--print]`: open the folder holding a note in the
+/// file manager, or its page in the browser. `--print` prints the folder
+/// or URL instead of opening it.
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut note = None;
+    let mut browser = false;
+    let mut site = None;
+    let mut print = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--browser" => browser = true,
+            "--site-url" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--site-url needs a URL"))?;
+                site = Some(value.clone());
+            }
+            "--print" => print = true,
+            _ if arg.starts_with("--") => return Err(crate::usage_error(&format!("unknown reveal argument {:?}", arg))),
+            _ if note.is_some() => return Err(crate::usage_error("reveal takes one date or path")),
+            _ => note = Some(arg.as_str()),
+        }
+    }
+    if site.is_some() && !browser {
+        return Err(crate::usage_error("--site-url needs --browser"));
+    }
+
+    let relative = target(src_path, note.unwrap_or("today"))?;
+    let location = if browser {
+        let site = site.unwrap_or_else(|| site_url(Path::new(".")));
+        let link = relative.to_string_lossy().replace('\\', "/");
+        // A directory is served as its README's index page
+        let link = if src_path.join(&relative).is_dir() { format!("{}/README.md", link.trim_end_matches('/')) } else { link };
+        page_url(&site, link.trim_start_matches('/'))
+    } else {
+        let path = src_path.join(&relative);
+        let dir = if path.is_dir() { path } else { path.parent().map(Path::to_path_buf).unwrap_or_default() };
+        dir.canonicalize()?.display().to_string()
+    };
+    if print {
+        println!("{}", location);
+        return Ok(());
+    }
+    open(&location)
+}
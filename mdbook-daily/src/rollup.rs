@@ -0,0 +1,233 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::date::Date;
+use crate::generated;
+use crate::links::chapter_link;
+use crate::recent::excerpt;
+use crate::sources;
+use crate::summary::{escape_title, managed_region, merge_managed, parse_entry, read_text, Options, ScanStats, MANAGED_END, MANAGED_START};
+
+/// Longest excerpt of an entry on a rollup page, in characters
+const EXCERPT_LEN: usize = 200;
+
+/// Period a rollup page covers
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Period {
+    /// `YYYY/week-NN.md` per ISO week and calendar year
+    Week,
+    /// `YYYY/MM/README.md` per month
+    Month,
+}
+
+/// Year directory of the entry at `relative`: its `YYYY` ancestor, or a
+/// `YYYY` directory next to the entry when it has none
+fn year_dir(relative: &Path, date: Date) -> PathBuf {
+    let own = format!("{:04}", date.year);
+    let parent = relative.parent().unwrap_or(Path::new(""));
+    match parent.ancestors().find(|dir| dir.file_name().is_some_and(|name| name == own.as_str())) {
+        Some(dir) => dir.to_path_buf(),
+        None => parent.join(own),
+    }
+}
+
+/// Rollup page (relative to src) of the entry at `relative` dated `date`.
+/// Pages stay in the entry's own year directory, so they are listed in
+/// that year's chapter: a week spanning New Year gets a page in each
+/// year, both titled with its ISO year and week.
+pub fn page_path(relative: &Path, date: Date, period: Period) -> PathBuf {
+    let dir = year_dir(relative, date);
+    match period {
+        Period::Week => dir.join(format!("week-{:02}.md", date.iso_week().1)),
+        Period::Month => dir.join(format!("{:02}", date.month)).join("README.md"),
+    }
+}
+
+/// Link of the rollup page of every dated entry, by entry link
+fn page_links(period: Period, stats: &ScanStats) -> HashMap<&str, String> {
+    stats.dated.iter()
+        .filter(|(_, _, link)| !link.ends_with("README.md"))
+        .map(|(date, _, link)| {
+            let page = page_path(Path::new(link.trim_start_matches("./")), *date, period);
+            (link.as_str(), format!("./{}", page.to_string_lossy().replace('\\', "/")))
+        })
+        .collect()
+}
+
+/// Title of a rollup page, for the period `date` falls in
+fn title(options: &Options, date: Date, period: Period) -> String {
+    match period {
+        Period::Week => {
+            let (year, week) = date.iso_week();
+            options.strings.week_title.replace("{week}", &week.to_string()).replace("{year}", &year.to_string())
+        }
+        Period::Month => options.strings.month_title(date),
+    }
+}
+
+/// `section` (the buffered summary lines of one section) with its dated
+/// entries moved under the rollup page of their period. A page already
+/// listed in the section keeps its place; one that does not exist yet is
+/// listed where the first of its entries was.
+pub fn nest_entries(section: &str, options: &Options, stats: &ScanStats) -> String {
+    let Some(period) = options.rollup else { return section.to_string() };
+    let links = page_links(period, stats);
+    let dates: HashMap<&str, Date> = stats.dated.iter().map(|(date, _, link)| (link.as_str(), *date)).collect();
+    let lines: Vec<&str> = section.lines().collect();
+    let entries: Vec<_> = lines.iter().enumerate().map(|(i, line)| parse_entry(i, line)).collect();
+    let pages: HashSet<&str> = links.values().map(String::as_str).collect();
+    let listed: HashSet<&str> = entries.iter().flatten()
+        .filter_map(|entry| entry.path.as_deref())
+        .filter(|path| pages.contains(path))
+        .collect();
+
+    // Entry lines by the page they move under, in summary order
+    let mut moved: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    // Where each page that is not listed yet goes, and its indent
+    let mut new_pages: HashMap<usize, (&str, &str)> = HashMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let Some(entry) = entry else { continue };
+        let Some(page) = entry.path.as_deref().and_then(|path| links.get(path)) else { continue };
+        let group = moved.entry(page.as_str()).or_default();
+        if group.is_empty() && !listed.contains(page.as_str()) {
+            new_pages.insert(i, (page.as_str(), entry.indent.as_str()));
+        }
+        group.push(i);
+    }
+    if moved.is_empty() {
+        return section.to_string();
+    }
+    let skipped: HashSet<usize> = moved.values().flatten().copied().collect();
+
+    let child = " ".repeat(options.indent_width);
+    let mut out = String::new();
+    let nest = |page: &str, indent: &str, out: &mut String| {
+        for &i in &moved[page] {
+            out.push_str(&format!("{}{}{}\n", indent, child, lines[i].trim_start()));
+        }
+    };
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(&(page, indent)) = new_pages.get(&i) {
+            let path = page.trim_start_matches("./");
+            let date = entries[i].as_ref().and_then(|entry| entry.path.as_deref()).and_then(|path| dates.get(path)).copied();
+            let title = date.map(|date| title(options, date, period)).unwrap_or_else(|| path.to_string());
+            out.push_str(&format!("{}- [{}]({})\n", indent, escape_title(&title), page));
+            nest(page, indent, &mut out);
+            continue;
+        }
+        if skipped.contains(&i) {
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+        if let Some(entry) = &entries[i] {
+            if let Some(page) = entry.path.as_deref().filter(|path| moved.contains_key(path)) {
+                nest(page, &entry.indent, &mut out);
+            }
+        }
+    }
+    out
+}
+
+/// Write the rollup page of every period with dated entries, listing each
+/// entry with its date and an excerpt in the page's managed region. A page
+/// that exists without a managed region, such as an authored month
+/// README, is left alone. Returns the number of pages written.
+pub fn write_rollup_pages(src_path: &Path, options: &Options, stats: &ScanStats) -> io::Result<usize> {
+    let Some(period) = options.rollup else { return Ok(0) };
+    let links = page_links(period, stats);
+    let mut pages: BTreeMap<&str, Vec<&(Date, String, String)>> = BTreeMap::new();
+    for dated in &stats.dated {
+        if let Some(page) = links.get(dated.2.as_str()) {
+            pages.entry(page.as_str()).or_default().push(dated);
+        }
+    }
+    let mut written = 0;
+    for (page, mut entries) in pages {
+        entries.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.2.cmp(&b.2)));
+        let relative = Path::new(page.trim_start_matches("./"));
+        let path = src_path.join(relative);
+        let existing = fs::read_to_string(&path).ok();
+        if existing.as_deref().is_some_and(|e| managed_region(e).is_none()) {
+            continue;
+        }
+        let page_dir = relative.parent().unwrap_or(Path::new(""));
+        let mut list = String::new();
+        for (date, title, link) in &entries {
+            let entry = Path::new(link.trim_start_matches("./"));
+            list.push_str(&format!("- [{}]({}) ({})\n", escape_title(title), chapter_link(page_dir, entry), date));
+            let content = read_text(&src_path.join(entry), options.head_size).unwrap_or_default();
+            if let Some(excerpt) = excerpt(&content, EXCERPT_LEN) {
+                list.push_str(&format!("\n  {}\n\n", excerpt));
+            }
+        }
+        let content = match existing {
+            Some(existing) => merge_managed(Some(&existing), list),
+            None => format!("# {}\n\n{}\n\n{}\n\n{}\n", title(options, entries[0].0, period), MANAGED_START, list.trim(), MANAGED_END),
+        };
+        sources::check_write(options.read_only_sources, src_path, &options.generated_dir, &path)?;
+        generated::write_if_changed(&path, content.as_bytes(), options.line_ending)?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// `mdbook-daily rollup [--period week|month]`: write a rollup page per
+/// week or month of dated entries and regenerate SUMMARY.md with the
+/// entries nested under them. The period defaults to the `rollup`
+/// setting, or weeks; setting `rollup` keeps the nesting, and the pages
+/// current, on later updates and builds.
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut options = config::book_options(Path::new("."))?;
+    let period = match args {
+        [] => options.rollup.unwrap_or(Period::Week),
+        [flag, period] if flag == "--period" => match period.as_str() {
+            "week" => Period::Week,
+            "month" => Period::Month,
+            _ => return Err(crate::usage_error(&format!("unknown rollup period {:?}, expected week or month", period))),
+        },
+        _ => return Err(crate::usage_error("rollup only accepts --period week|month")),
+    };
+    options.rollup = Some(period);
+    let mut stats = ScanStats::default();
+    let changed = crate::write_summary(src_path, &options, &mut stats)?;
+    let written = write_rollup_pages(src_path, &options, &stats)?;
+    stats.print_warnings();
+    println!("Wrote {} rollup pages{}", written, if changed { " and updated SUMMARY.md" } else { "" });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SummaryBuilder;
+
+    const NOTES: [(&str, &str); 5] = [
+        ("journal/2026/README.md", "# 2026"),
+        ("journal/2026/0105.md", "# Standup"),
+        ("journal/2026/0212.md", "# Standup"),
+        ("journal/2026/0213.md", "---\nseries: Move\npart: 2\n---\n# Packing"),
+        ("journal/2026/0214.md", "---\nseries: Move\npart: 1\n---\n# Boxes"),
+    ];
+
+    #[test]
+    fn entries_nest_under_their_month() {
+        let options = Options { rollup: Some(Period::Month), ..Options::default() };
+        let summary = SummaryBuilder::from_files(NOTES).unwrap().options(options).build().unwrap();
+        assert_eq!(summary, "# Summary\n\n# Journal\n\n\
+            - [2026](./journal/2026/README.md)\n\
+            \x20 - [January 2026](./journal/2026/01/README.md)\n\
+            \x20   - [Standup](./journal/2026/0105.md)\n\
+            \x20 - [February 2026](./journal/2026/02/README.md)\n\
+            \x20   - [Standup](./journal/2026/0212.md)\n\
+            \x20   - [Move]()\n\
+            \x20     - [Boxes](./journal/2026/0214.md)\n\
+            \x20     - [Packing](./journal/2026/0213.md)\n");
+    }
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use serde_json::{json, Value};
+
+use crate::config;
+use crate::sources;
+use crate::summary::{self, ScanStats};
+
+/// Largest message body accepted, in bytes
+const MAX_MESSAGE: usize = 16 << 20;
+
+/// Read one `Content-Length` framed message, as used by LSP. `None` at
+/// the end of the input; a message that is badly framed, too large or
+/// not UTF-8 is skipped and its problem returned, so the server can
+/// answer it and read on.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Result<String, String>>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let Some(length) = length else { return Ok(Some(Err("missing or invalid Content-Length".to_string()))) };
+    if length > MAX_MESSAGE {
+        io::copy(&mut input.by_ref().take(length as u64), &mut io::sink())?;
+        return Ok(Some(Err(format!("message of {} bytes is over the {} byte limit", length, MAX_MESSAGE))));
+    }
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(String::from_utf8(body).map_err(|_| "message is not UTF-8".to_string())))
+}
+
+fn write_message(out: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(out, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    out.flush()
+}
+
+/// An error returned to the client as a JSON-RPC error object
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn invalid_params(message: impl Into<String>) -> RpcError {
+        RpcError { code: -32602, message: message.into() }
+    }
+}
+
+impl From<io::Error> for RpcError {
+    fn from(e: io::Error) -> RpcError {
+        RpcError { code: -32000, message: e.to_string() }
+    }
+}
+
+fn str_param<'a>(params: &'a Value, name: &str) -> Result<&'a str, RpcError> {
+    params.get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::invalid_params(format!("missing string parameter {:?}", name)))
+}
+
+/// Resolve a client path (relative to the book root or src) to a path
+/// inside src, refusing anything that escapes it
+fn note_path(src_path: &Path, path: &str) -> Result<PathBuf, RpcError> {
+    let path = Path::new(path);
+    let relative = path.strip_prefix(src_path).unwrap_or(path);
+    if relative.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return Err(RpcError::invalid_params(format!("{:?} must be a relative path inside src", path)));
+    }
+    if relative.extension().and_then(|e| e.to_str()) != Some("md") {
+        return Err(RpcError::invalid_params(format!("{:?} is not a markdown file", path)));
+    }
+    Ok(src_path.join(relative))
+}
+
+fn regenerate(src_path: &Path) -> Result<Value, RpcError> {
+    let mut stats = ScanStats::default();
+    let changed = crate::write_summary(src_path, &config::book_options(Path::new("."))?, &mut stats)?;
+    Ok(json!({
+        "summary": src_path.join("SUMMARY.md").to_string_lossy(),
+        "changed": changed,
+        "skipped": stats.skipped.len(),
+    }))
+}
+
+fn list_entries(src_path: &Path) -> Result<Value, RpcError> {
+    let options = config::book_options(Path::new("."))?;
+    let entries = summary::book_entries(src_path, &options)?
+        .into_iter()
+        .filter_map(|entry| {
+            let link = entry.path?;
+            let path = src_path.join(link.strip_prefix("./").unwrap_or(&link));
+            Some(json!({
+                "path": path.to_string_lossy(),
+                "title": entry.title,
+                "date": options.entry_date(&link).map(|d| d.to_string()),
+            }))
+        })
+        .collect();
+    Ok(Value::Array(entries))
+}
+
+fn resolve_link(src_path: &Path, params: &Value) -> Result<Value, RpcError> {
+    let query = str_param(params, "query")?;
+    let from = params.get("from").and_then(Value::as_str).map(Path::new);
+    Ok(match crate::link::resolve(src_path, query, from)? {
+        Some(resolved) => json!({
+            "title": resolved.title,
+            "path": src_path.join(&resolved.path).to_string_lossy(),
+            "link": resolved.markdown,
+        }),
+        None => Value::Null,
+    })
+}
+
+fn create_note(src_path: &Path, read_only: bool, params: &Value) -> Result<Value, RpcError> {
+    let path = note_path(src_path, str_param(params, "path")?)?;
+    sources::check_write(read_only, src_path, &config::book_options(Path::new("."))?.generated_dir, &path)?;
+    if path.exists() {
+        return Err(RpcError::invalid_params(format!("{:?} already exists", path)));
+    }
+    let title = match params.get("title").and_then(Value::as_str) {
+        Some(title) => title.to_string(),
+        None => path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default(),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, format!("# {}\n", title))?;
+    regenerate(src_path)?;
+    Ok(json!({ "path": path.to_string_lossy() }))
+}
+
+fn rename(src_path: &Path, read_only: bool, params: &Value) -> Result<Value, RpcError> {
+    let from = note_path(src_path, str_param(params, "from")?)?;
+    let to = note_path(src_path, str_param(params, "to")?)?;
+    sources::check_write(read_only, src_path, &config::book_options(Path::new("."))?.generated_dir, &from)?;
+    if to.exists() {
+        return Err(RpcError::invalid_params(format!("{:?} already exists", to)));
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&from, &to)?;
+    regenerate(src_path)?;
+    Ok(json!({ "path": to.to_string_lossy() }))
+}
+
+/// Serve JSON-RPC 2.0 requests on stdin/stdout until `exit` or EOF.
+///
+/// Methods: `listEntries`, `resolveLink {query, from?}`,
+/// `createNote {path, title?}`, `rename {from, to}`, `regenerate`,
+/// `shutdown` and the `exit` notification. With `--read-only-sources`,
+/// `createNote` and `rename` fail instead of touching authored files.
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let read_only = match args {
+        [] => false,
+        [flag] if flag == "--read-only-sources" => true,
+        _ => return Err(crate::usage_error("rpc only accepts --read-only-sources")),
+    };
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+
+    while let Some(message) = read_message(&mut input)? {
+        let body = match message {
+            Ok(body) => body,
+            Err(problem) => {
+                write_message(&mut out, &json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": -32600, "message": problem },
+                }))?;
+                continue;
+            }
+        };
+        let request: Value = match serde_json::from_str(&body) {
+            Ok(request) => request,
+            Err(e) => {
+                write_message(&mut out, &json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": -32700, "message": e.to_string() },
+                }))?;
+                continue;
+            }
+        };
+        let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "listEntries" => list_entries(src_path),
+            "resolveLink" => resolve_link(src_path, &params),
+            "createNote" => create_note(src_path, read_only, &params),
+            "rename" => rename(src_path, read_only, &params),
+            "regenerate" => regenerate(src_path),
+            "shutdown" => Ok(Value::Null),
+            "exit" => return Ok(()),
+            _ => Err(RpcError { code: -32601, message: format!("unknown method {:?}", method) }),
+        };
+
+        // Notifications carry no id and get no response
+        let Some(id) = request.get("id").cloned() else { continue };
+        let response = match result {
+            Ok(value) => json!({ "jsonrpc": "2.0", "id": id, "result": value }),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": e.code, "message": e.message },
+            }),
+        };
+        write_message(&mut out, &response)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bad_messages_are_skipped_with_their_problem() {
+        let big = MAX_MESSAGE + 1;
+        let input = format!(
+            "Content-Length: 2\r\n\r\n{{}}X-Other: 1\r\n\r\nContent-Length: {}\r\n\r\n{}Content-Length: 4\r\n\r\nnull",
+            big, " ".repeat(big)
+        );
+        let mut input = io::Cursor::new(input.into_bytes());
+        assert_eq!(read_message(&mut input).unwrap(), Some(Ok("{}".to_string())));
+        assert!(read_message(&mut input).unwrap().is_some_and(|m| m.unwrap_err().contains("Content-Length")));
+        assert!(read_message(&mut input).unwrap().is_some_and(|m| m.unwrap_err().contains("limit")));
+        assert_eq!(read_message(&mut input).unwrap(), Some(Ok("null".to_string())));
+        assert_eq!(read_message(&mut input).unwrap(), None);
+    }
+}
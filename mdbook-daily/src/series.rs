@@ -0,0 +1,159 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::date::Date;
+use crate::frontmatter::FrontMatter;
+use crate::generated::{remove_stale, write_if_changed};
+use crate::link::relative_link;
+use crate::summary::{escape_title, parse_entry, Options, ScanStats};
+use crate::tags;
+
+/// Entries from different days sharing a `series` front matter value
+pub struct Series {
+    pub name: String,
+    pub title: String,
+    /// Title, link and date of each part, oldest first
+    pub parts: Vec<(String, String, Option<Date>)>,
+}
+
+/// Put `parts` in reading order: numbered by their `part` front matter
+/// first, then by date, undated ones last. Stable, so parts alike keep
+/// their summary order.
+fn sort_parts(parts: &mut [(String, String, Option<Date>)], front_matter: &HashMap<String, FrontMatter>) {
+    parts.sort_by_key(|(_, path, date)| {
+        let part = front_matter.get(path).and_then(|front| front.part);
+        (part.is_none(), part, date.is_none(), *date)
+    });
+}
+
+/// Directory (relative to src) holding the series index pages
+pub fn series_dir(options: &Options) -> PathBuf {
+    options.generated_dir.join("series")
+}
+
+/// Path of the index page of one series, relative to src
+pub fn series_path(options: &Options, name: &str) -> PathBuf {
+    series_dir(options).join(format!("{}.md", tags::slug(name)))
+}
+
+/// `section` (the buffered summary lines of one section) with the entries
+/// of each series moved under a line for the series, put where its first
+/// entry was. Parts are listed in reading order, see [`sort_parts`]. The
+/// series found are added to `stats.series`.
+pub fn group_series(section: &str, options: &Options, stats: &mut ScanStats) -> String {
+    let lines: Vec<&str> = section.lines().collect();
+    let dates: HashMap<&str, Date> = stats.dated.iter().map(|(date, _, link)| (link.as_str(), *date)).collect();
+    let mut found: Vec<Series> = Vec::new();
+    // Series starting at each line, and every line of an entry in one
+    let mut starts: HashMap<usize, usize> = HashMap::new();
+    let mut moved = HashSet::new();
+    let mut indents = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let Some(entry) = parse_entry(i, line) else { continue };
+        let Some(path) = entry.path.filter(|path| !path.ends_with("README.md")) else { continue };
+        let Some(name) = stats.front_matter.get(&path).and_then(|front| front.series.clone()) else { continue };
+        let index = match found.iter().position(|series| series.name == name) {
+            Some(index) => index,
+            None => {
+                starts.insert(i, found.len());
+                indents.push(entry.indent);
+                found.push(Series { title: options.title_transform.apply(&name), name, parts: Vec::new() });
+                found.len() - 1
+            }
+        };
+        found[index].parts.push((entry.title, path.clone(), dates.get(path.as_str()).copied()));
+        moved.insert(i);
+    }
+    if found.is_empty() {
+        return section.to_string();
+    }
+    for series in &mut found {
+        sort_parts(&mut series.parts, &stats.front_matter);
+    }
+
+    let mut out = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if let Some(&index) = starts.get(&i) {
+            let series = &found[index];
+            let indent = &indents[index];
+            let link = if options.series_pages {
+                format!("./{}", series_path(options, &series.name).to_string_lossy().replace('\\', "/"))
+            } else {
+                String::new()
+            };
+            out.push_str(&format!("{}- [{}]({})\n", indent, escape_title(&series.title), link));
+            for (title, path, _) in &series.parts {
+                out.push_str(&format!("{}{}- [{}]({})\n", indent, " ".repeat(options.indent_width), escape_title(title), path));
+            }
+        } else if !moved.contains(&i) {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    // A series spanning sections gets one index page
+    for series in found {
+        match stats.series.iter_mut().find(|known| known.name == series.name) {
+            Some(known) => {
+                known.parts.extend(series.parts);
+                sort_parts(&mut known.parts, &stats.front_matter);
+            }
+            None => stats.series.push(series),
+        }
+    }
+    out
+}
+
+/// Write an index page per series listing its numbered parts. Returns the
+/// number of pages.
+pub fn write_series_pages(src_path: &Path, options: &Options, stats: &ScanStats) -> io::Result<usize> {
+    let mut keep = HashSet::new();
+    for series in &stats.series {
+        let relative = series_path(options, &series.name);
+        let page_dir = relative.parent().unwrap_or(Path::new(""));
+        let mut content = format!("# {}\n\n", series.title);
+        for (number, (title, path, date)) in series.parts.iter().enumerate() {
+            let link = relative_link(page_dir, Path::new(path.trim_start_matches("./")));
+            match date {
+                Some(date) => content.push_str(&format!("{}. [{}]({}) ({})\n", number + 1, escape_title(title), link, date)),
+                None => content.push_str(&format!("{}. [{}]({})\n", number + 1, escape_title(title), link)),
+            }
+        }
+        let path = src_path.join(&relative);
+        write_if_changed(&path, content.as_bytes(), options.line_ending)?;
+        keep.insert(path);
+    }
+    remove_stale(&src_path.join(series_dir(options)), &keep)?;
+    Ok(stats.series.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SummaryBuilder;
+
+    const NOTES: [(&str, &str); 5] = [
+        ("journal/2026/README.md", "# 2026"),
+        ("journal/2026/0105.md", "# Standup"),
+        ("journal/2026/0212.md", "# Standup"),
+        ("journal/2026/0213.md", "---\nseries: Move\npart: 2\n---\n# Packing"),
+        ("journal/2026/0214.md", "---\nseries: Move\npart: 1\n---\n# Boxes"),
+    ];
+
+    #[test]
+    fn series_gather_under_a_draft_chapter_in_part_order() {
+        let summary = SummaryBuilder::from_files(NOTES).unwrap().build().unwrap();
+        assert_eq!(summary, "# Summary\n\n# Journal\n\n\
+            - [2026](./journal/2026/README.md)\n\
+            \x20 - [Standup](./journal/2026/0105.md)\n\
+            \x20 - [Standup](./journal/2026/0212.md)\n\
+            \x20 - [Move]()\n\
+            \x20   - [Boxes](./journal/2026/0214.md)\n\
+            \x20   - [Packing](./journal/2026/0213.md)\n");
+        // With series pages the series chapter is its index page
+        let options = Options { series_pages: true, ..Options::default() };
+        let summary = SummaryBuilder::from_files(NOTES).unwrap().options(options).build().unwrap();
+        let page = series_path(&Options::default(), "Move");
+        assert!(summary.contains(&format!("  - [Move](./{})\n", page.display())), "{}", summary);
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Modification time and size of every file under a directory
+pub type Snapshot = HashMap<PathBuf, (SystemTime, u64)>;
+
+/// Modification times of every file under `src`, excluding SUMMARY.md
+pub fn snapshot(src_path: &Path) -> Snapshot {
+    let mut files = HashMap::new();
+    let mut pending = vec![src_path.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else { continue };
+            if metadata.is_dir() {
+                pending.push(path);
+            } else if path != src_path.join("SUMMARY.md") {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                files.insert(path, (modified, metadata.len()));
+            }
+        }
+    }
+    files
+}
+
+/// Whether `path` is an authored markdown file: a `.md` file under src
+/// other than SUMMARY.md and the generated pages
+pub fn is_authored(src_path: &Path, generated_dir: &Path, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(src_path) else { return false };
+    relative.extension().is_some_and(|e| e == "md")
+        && relative != Path::new("SUMMARY.md")
+        && !relative.starts_with(generated_dir)
+}
+
+/// Refuse a write to an authored file when sources are read-only
+pub fn check_write(read_only: bool, src_path: &Path, generated_dir: &Path, path: &Path) -> io::Result<()> {
+    if read_only && is_authored(src_path, generated_dir, path) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("--read-only-sources: refusing to write {}", path.display()),
+        ));
+    }
+    Ok(())
+}
+
+/// Fail if any authored file was created, modified or removed between
+/// two snapshots
+pub fn verify_unchanged(src_path: &Path, generated_dir: &Path, before: &Snapshot, after: &Snapshot) -> io::Result<()> {
+    let mut changed: Vec<&PathBuf> = before.iter()
+        .filter(|(path, meta)| after.get(*path) != Some(meta))
+        .map(|(path, _)| path)
+        .chain(after.keys().filter(|path| !before.contains_key(*path)))
+        .filter(|path| is_authored(src_path, generated_dir, path))
+        .collect();
+    if changed.is_empty() {
+        return Ok(());
+    }
+    changed.sort();
+    let list: Vec<String> = changed.iter().map(|p| p.display().to_string()).collect();
+    Err(io::Error::other(format!("--read-only-sources: authored files changed: {}", list.join(", "))))
+}
@@ -0,0 +1,191 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::config;
+use crate::date::Date;
+use crate::frontmatter;
+use crate::locale::Strings;
+use crate::summary::{self, Options, ScanStats};
+
+/// A run of consecutive days with at least one entry
+#[derive(Clone, Copy, Serialize)]
+pub struct Streak {
+    pub days: usize,
+    #[serde(serialize_with = "serialize_date")]
+    pub start: Date,
+    #[serde(serialize_with = "serialize_date")]
+    pub end: Date,
+}
+
+/// Entries and words of one month
+#[derive(Serialize)]
+pub struct MonthStats {
+    /// `YYYY-MM`
+    pub month: String,
+    pub entries: usize,
+    pub words: usize,
+}
+
+/// Writing statistics over the dated entries of a book
+#[derive(Serialize)]
+pub struct WritingStats {
+    pub entries: usize,
+    /// Days with at least one entry
+    pub days: usize,
+    pub words: usize,
+    pub words_per_entry: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub longest_streak: Option<Streak>,
+    /// The streak ending today, or yesterday while today has no entry yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_streak: Option<Streak>,
+    /// Oldest month first, including months without entries
+    pub months: Vec<MonthStats>,
+}
+
+fn serialize_date<S: serde::Serializer>(date: &Date, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&date.to_string())
+}
+
+/// Words of a note's text, leaving out front matter and fenced code
+pub fn word_count(content: &str) -> usize {
+    let (_, body) = frontmatter::split(content);
+    let mut in_fence = false;
+    let mut words = 0;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if !in_fence {
+            // Markdown markers such as `#`, `-` and `>` are not words
+            words += line.split_whitespace().filter(|w| w.chars().any(char::is_alphanumeric)).count();
+        }
+    }
+    words
+}
+
+impl WritingStats {
+    /// Statistics over the entries in `stats.dated`, reading each from
+    /// `src_path` up to `options.max_file_size`. Streaks are counted up to
+    /// `today`.
+    pub fn collect(src_path: &Path, options: &Options, stats: &ScanStats, today: Date) -> WritingStats {
+        let mut days = BTreeSet::new();
+        let mut months: BTreeMap<(i64, u32), (usize, usize)> = BTreeMap::new();
+        let mut total = 0;
+        for (date, _, link) in &stats.dated {
+            let words = summary::read_text(&src_path.join(link.trim_start_matches("./")), options.max_file_size)
+                .map_or(0, |content| word_count(&content));
+            total += words;
+            days.insert(date.days());
+            let month = months.entry((date.year, date.month)).or_default();
+            month.0 += 1;
+            month.1 += words;
+        }
+
+        let mut streaks: Vec<Streak> = Vec::new();
+        for &day in &days {
+            match streaks.last_mut() {
+                Some(streak) if streak.end.days() + 1 == day => {
+                    streak.days += 1;
+                    streak.end = Date::from_days(day);
+                }
+                _ => streaks.push(Streak { days: 1, start: Date::from_days(day), end: Date::from_days(day) }),
+            }
+        }
+        let longest_streak = streaks.iter().rev().max_by_key(|s| s.days).copied();
+        let current_streak = streaks.last().filter(|s| today.days() - s.end.days() <= 1).copied();
+
+        let mut month_stats = Vec::new();
+        if let (Some(&first), Some(&last)) = (months.keys().next(), months.keys().next_back()) {
+            let (mut year, mut month) = first;
+            while (year, month) <= last {
+                let (entries, words) = months.get(&(year, month)).copied().unwrap_or_default();
+                month_stats.push(MonthStats { month: format!("{:04}-{:02}", year, month), entries, words });
+                (year, month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+            }
+        }
+
+        let entries = stats.dated.len();
+        WritingStats {
+            entries,
+            days: days.len(),
+            words: total,
+            words_per_entry: total.checked_div(entries).unwrap_or(0),
+            longest_streak,
+            current_streak,
+            months: month_stats,
+        }
+    }
+
+    /// `#` bars for the entries of each month, scaled to at most `width`
+    fn bars(&self, width: usize) -> Vec<String> {
+        let max = self.months.iter().map(|m| m.entries).max().unwrap_or(0).max(1);
+        self.months.iter().map(|m| "#".repeat(m.entries.div_ceil(max.div_ceil(width)))).collect()
+    }
+}
+
+fn streak_text(strings: &Strings, streak: Option<Streak>) -> String {
+    match streak {
+        Some(streak) => format!("{} ({} – {})", strings.streak_days.replace("{days}", &streak.days.to_string()), streak.start, streak.end),
+        None => strings.streak_days.replace("{days}", "0"),
+    }
+}
+
+/// Write the stats page: totals, streaks and a table of the months
+pub fn write_stats_page(strings: &Strings, stats: &WritingStats, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "# {}", strings.stats)?;
+    writeln!(out)?;
+    writeln!(out, "- {}: {}", strings.entries, stats.entries)?;
+    writeln!(out, "- {}: {}", strings.words, stats.words)?;
+    writeln!(out, "- {}: {}", strings.words_per_entry, stats.words_per_entry)?;
+    writeln!(out, "- {}: {}", strings.current_streak, streak_text(strings, stats.current_streak))?;
+    writeln!(out, "- {}: {}", strings.longest_streak, streak_text(strings, stats.longest_streak))?;
+    if stats.months.is_empty() {
+        return Ok(());
+    }
+    writeln!(out)?;
+    writeln!(out, "| {} | {} | {} | |", strings.month, strings.entries, strings.words)?;
+    writeln!(out, "| --- | --- | --- | --- |")?;
+    for (month, bar) in stats.months.iter().zip(stats.bars(30)).rev() {
+        writeln!(out, "| {} | {} | {} | `{}` |", month.month, month.entries, month.words, bar)?;
+    }
+    Ok(())
+}
+
+/// `mdbook-daily stats [--json]`: print streaks, word counts and entries
+/// per month over the dated entries
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let json = match args {
+        [] => false,
+        [flag] if flag == "--json" => true,
+        _ => return Err(crate::usage_error("stats only accepts --json")),
+    };
+    let options = config::book_options(Path::new("."))?;
+    let mut stats = ScanStats::default();
+    summary::write_summary_to(src_path, &options, &mut stats, &mut io::sink())?;
+    let writing = WritingStats::collect(src_path, &options, &stats, Date::today());
+    if json {
+        println!("{}", serde_json::to_string_pretty(&writing).map_err(io::Error::other)?);
+        return Ok(());
+    }
+    let streak = |streak: Option<Streak>| match streak {
+        Some(streak) => format!("{} days ({} to {})", streak.days, streak.start, streak.end),
+        None => "none".to_string(),
+    };
+    println!("Entries:        {} on {} days", writing.entries, writing.days);
+    println!("Words:          {} ({} per entry)", writing.words, writing.words_per_entry);
+    println!("Current streak: {}", streak(writing.current_streak));
+    println!("Longest streak: {}", streak(writing.longest_streak));
+    if !writing.months.is_empty() {
+        println!();
+    }
+    for (month, bar) in writing.months.iter().zip(writing.bars(40)) {
+        println!("{} {:>4} {}", month.month, month.entries, bar);
+    }
+    Ok(())
+}
@@ -0,0 +1,1695 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{self, HeadCache};
+use crate::calendar;
+use crate::date::{date_from_path, named_date, Date, DateParser};
+use crate::eol::LineEnding;
+use crate::frontmatter::{self, FrontMatter};
+use crate::git;
+use crate::ignore::Ignore;
+use crate::locale::Strings;
+use crate::manifest::{self, Manifest};
+use crate::related::RelatedBy;
+use crate::titles::TitleTransform;
+use crate::wikilinks::Unresolved;
+use crate::rollup::{self, Period};
+use crate::{onthisday, recent, series, tags, team};
+
+/// Get display name from filename
+fn get_display_name(filename: &str) -> Option<String> {
+    let name = filename.strip_suffix(".md").unwrap_or(filename);
+    if name == "README" {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Pages the generator can write into the generated directory
+#[derive(Clone, Copy)]
+pub enum GeneratedPage {
+    Acronyms,
+    Archive,
+    Changes,
+    TimeLog,
+    Tags,
+    Stats,
+}
+
+impl GeneratedPage {
+    pub fn title(self, strings: &Strings) -> &str {
+        match self {
+            GeneratedPage::Acronyms => &strings.acronyms,
+            GeneratedPage::Archive => &strings.archive,
+            GeneratedPage::Changes => &strings.changes,
+            GeneratedPage::TimeLog => &strings.time_log,
+            GeneratedPage::Tags => &strings.tags,
+            GeneratedPage::Stats => &strings.stats,
+        }
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            GeneratedPage::Acronyms => "acronyms.md",
+            GeneratedPage::Archive => "archive.md",
+            GeneratedPage::Changes => "changes.md",
+            GeneratedPage::TimeLog => "time-log.md",
+            GeneratedPage::Tags => "tags.md",
+            GeneratedPage::Stats => "stats.md",
+        }
+    }
+}
+
+/// Order of the chapters inside each part. Dated names compare by their
+/// date, so `2024-6-1.md`-style and `YYYYMMDD.md` names interleave
+/// correctly.
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrder {
+    /// By date, then name; oldest first
+    #[serde(alias = "name")]
+    Asc,
+    /// By date, then name; newest first
+    #[serde(alias = "name-desc")]
+    Desc,
+    /// Most recently modified first
+    Mtime,
+    /// By the front matter `date`, falling back to the date in the path;
+    /// newest first
+    FrontmatterDate,
+}
+
+/// Where an entry's date can come from
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DateSource {
+    /// The front matter `date`
+    FrontMatter,
+    /// A date in the file or directory names
+    Filename,
+    /// The commit that added the note, when `git_dates` is set
+    Git,
+    /// The file's modification time
+    Mtime,
+}
+
+impl DateSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DateSource::FrontMatter => "front-matter",
+            DateSource::Filename => "filename",
+            DateSource::Git => "git",
+            DateSource::Mtime => "mtime",
+        }
+    }
+}
+
+/// What a [`Sorter`] or [`Filter`] is told about a chapter
+pub struct Candidate<'a> {
+    /// Path relative to src
+    pub relative: &'a Path,
+    pub is_dir: bool,
+    /// Date from the front matter or the path
+    pub date: Option<Date>,
+    /// Front matter of a note that has it
+    pub front_matter: Option<&'a FrontMatter>,
+}
+
+/// Orders sibling chapters in a book's own way, for library users whose
+/// order no `sort` setting describes. A sorter in
+/// [`Options::sorters`] replaces the `sort` of its section. Sorters are
+/// shared by the threads that read notes.
+pub trait Sorter: Send + Sync {
+    fn compare(&self, a: &Candidate, b: &Candidate) -> std::cmp::Ordering;
+}
+
+impl<F: Fn(&Candidate, &Candidate) -> std::cmp::Ordering + Send + Sync> Sorter for F {
+    fn compare(&self, a: &Candidate, b: &Candidate) -> std::cmp::Ordering {
+        self(a, b)
+    }
+}
+
+/// Leaves out notes and directories in a book's own way, on top of the
+/// `exclude` patterns. A chapter is listed when every filter in
+/// [`Options::filters`] includes it.
+pub trait Filter: Send + Sync {
+    fn include(&self, candidate: &Candidate) -> bool;
+}
+
+impl<F: Fn(&Candidate) -> bool + Send + Sync> Filter for F {
+    fn include(&self, candidate: &Candidate) -> bool {
+        self(candidate)
+    }
+}
+
+/// How entries of a section that share a title are told apart
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Disambiguator {
+    /// Append the entry's date: `Standup (2024-01-05)`
+    Date,
+    /// Append the name of the entry's directory: `Standup (2024)`
+    Parent,
+    /// Leave duplicate titles as they are, with a warning
+    None,
+}
+
+/// Whether symlinked files and directories are part of the book
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SymlinkPolicy {
+    /// List them like the files and directories they point to. Links
+    /// back to a directory being walked are skipped with a warning.
+    Follow,
+    /// Leave them out
+    Skip,
+}
+
+/// How a top-level section is laid out in the summary, set per section
+/// directory in `section-layout`
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct SectionLayout {
+    /// Start a part titled after the section. Without one, its chapters
+    /// continue the part before it.
+    pub part: bool,
+    /// Put a `---` separator before the section
+    pub separator: bool,
+    /// Show mdBook's chapter numbers (`3.14.2`) on the section's
+    /// chapters. mdBook numbers every chapter of a part, so turning this
+    /// off takes the preprocessor, which drops the numbers from the book
+    /// it hands over.
+    pub numbered: bool,
+}
+
+impl Default for SectionLayout {
+    fn default() -> SectionLayout {
+        SectionLayout { part: true, separator: false, numbered: true }
+    }
+}
+
+/// Settings that control SUMMARY generation
+pub struct Options {
+    /// Markdown files larger than this many bytes are skipped
+    pub max_file_size: u64,
+    /// Bytes read from the start of each note for its title, front matter
+    /// and excerpt, and sniffed for binary content
+    pub head_size: u64,
+    /// Threads reading notes, at most; one per CPU when unset
+    pub jobs: Option<usize>,
+    /// Notes read per second, at most, across all threads
+    pub reads_per_second: Option<u32>,
+    /// Insert `---` separators between year directories of a section
+    pub year_separators: bool,
+    /// Dated entries before this day are left out of the summary
+    pub exclude_before: Option<Date>,
+    /// Directory (relative to src) holding every generated page
+    pub generated_dir: PathBuf,
+    /// Generate a page listing the entries excluded by age
+    pub archive_page: bool,
+    /// Generate an acronym index page
+    pub acronym_index: bool,
+    /// Generate a page of entries changed since this git revision
+    pub changes_since: Option<String>,
+    /// Generate a digest page per date combining every section's entries
+    pub team_digest: bool,
+    /// Generate a page totalling the sessions logged with `log`
+    pub time_log: bool,
+    /// Generate a page listing the entries by front matter tag
+    pub tag_index: bool,
+    /// Also generate a page per tag, listed under the tag index
+    pub tag_pages: bool,
+    /// Link each series of entries to an index page numbering its parts
+    pub series_pages: bool,
+    /// Generate a calendar page per month, listed under its year
+    pub calendar_pages: bool,
+    /// Generate a page of writing streaks, word counts and entries per month
+    pub stats_page: bool,
+    /// List this many of the newest entries, with excerpts, on a
+    /// `recent.md` page pinned after the other pinned chapters
+    pub recent_entries: Option<usize>,
+    /// List the entries from today's date in earlier years on an
+    /// `on-this-day.md` page pinned after the recent entries
+    pub on_this_day: bool,
+    /// Nest dated entries under a rollup page per week or month
+    pub rollup: Option<Period>,
+    /// Write an Atom feed of this many of the newest entries to
+    /// `feed.xml`, linked under `site_url`
+    pub feed_entries: Option<usize>,
+    /// Put each entry's body, rendered to HTML, in the feed instead of
+    /// an excerpt
+    pub feed_html: bool,
+    /// Also write a feed per top-level section, see
+    /// [`crate::feed::write_section_feeds`]
+    pub feed_sections: bool,
+    /// Fail instead of writing to authored markdown files
+    pub read_only_sources: bool,
+    /// Fail instead of writing the summary when any path was skipped
+    pub strict: bool,
+    /// Read cloud-only placeholder files, downloading them, instead of skipping them
+    pub hydrate_placeholders: bool,
+    /// Base URL of the published book, for features that emit absolute links
+    pub site_url: Option<String>,
+    /// Link draft chapters to generated placeholder pages for local previews
+    pub include_drafts: bool,
+    /// Values of the `status` front matter key in the order `promote`
+    /// advances notes through them
+    pub statuses: Vec<String>,
+    /// Statuses whose notes are left out of the summary
+    pub hidden_statuses: Vec<String>,
+    /// Line ending for SUMMARY.md and generated pages
+    pub line_ending: LineEnding,
+    /// Wrap each part in `<!-- mdbook-daily:section:NAME -->` comments
+    pub section_markers: bool,
+    /// Preprocessor only: show a placeholder for images whose file is missing
+    pub missing_image_placeholders: bool,
+    /// Preprocessor only: add previous/next and backlink footers to entries
+    pub entry_navigation: bool,
+    /// Preprocessor only: head each part of a series with its number and
+    /// links to the previous and next part
+    pub series_navigation: bool,
+    /// Preprocessor only: end each chapter with this many related notes
+    pub related_notes: Option<usize>,
+    /// What relates notes, in the order they are ranked by
+    pub related_by: Vec<RelatedBy>,
+    /// Preprocessor only: resolve `[[wiki-links]]`, handling the ones that
+    /// do not resolve as set
+    pub wiki_links: Option<Unresolved>,
+    /// Directories (relative to src) whose names are used as titles verbatim
+    pub plain_title_dirs: Vec<PathBuf>,
+    /// Chapters (relative to src) listed before the first part
+    pub pinned: Vec<PathBuf>,
+    /// Write the pinned chapters as mdBook prefix chapters, which are not
+    /// numbered, instead of numbered ones
+    pub pinned_unnumbered: bool,
+    /// Chapters (relative to src) listed after everything else as
+    /// unnumbered suffix chapters
+    pub suffix: Vec<PathBuf>,
+    /// Part, separator and numbering settings by section directory name
+    pub section_layout: HashMap<String, SectionLayout>,
+    /// Order of the chapters inside each part
+    pub sort: SortOrder,
+    /// Per-section overrides of `sort`, by section directory name
+    pub section_sort: HashMap<String, SortOrder>,
+    /// Custom orders by section directory name, replacing `sort` and
+    /// `section_sort` there; the `""` entry orders every other section
+    pub sorters: HashMap<String, Box<dyn Sorter>>,
+    /// Custom filters every listed chapter passes
+    pub filters: Vec<Box<dyn Filter>>,
+    /// How duplicate titles within a section are told apart
+    pub disambiguate: Disambiguator,
+    /// Per-section overrides of `disambiguate`, by section directory name
+    pub section_disambiguate: HashMap<String, Disambiguator>,
+    /// Files and directories (relative to src) left out of the summary
+    pub exclude: Ignore,
+    /// Whether symlinks are followed
+    pub symlinks: SymlinkPolicy,
+    /// Directory levels listed below each section; deeper directories are
+    /// left out, or flattened into their ancestor at the limit
+    pub max_depth: Option<usize>,
+    /// List the notes of directories past `max_depth` at the limit
+    /// instead of leaving them out
+    pub flatten_deep: bool,
+    /// Spaces per nesting level
+    pub indent_width: usize,
+    /// Part titles by section directory name, overriding the derived ones
+    pub section_titles: HashMap<String, String>,
+    /// Text of the generated headings and pages
+    pub strings: Strings,
+    /// How titles are derived from file and directory names
+    pub title_transform: TitleTransform,
+    /// Parsers for entry dates in file names, tried in order before the
+    /// built-in `YYYY-MM-DD` style formats; `date-patterns` adds to it
+    pub date_parsers: Vec<Box<dyn DateParser>>,
+    /// Date notes without a date in their name or front matter by the
+    /// commit that added them
+    pub git_dates: bool,
+    /// Where entry dates come from, first source that has one wins
+    pub date_sources: Vec<DateSource>,
+    /// Per-section overrides of `date_sources`, by section directory name
+    pub section_date_sources: HashMap<String, Vec<DateSource>>,
+    /// Warn about entries whose date sources are further apart than this
+    /// many days
+    pub date_tolerance: Option<u32>,
+    /// Dates of the commits that added each note, relative to src, read
+    /// by [`Options::read_git_dates`]
+    pub created: HashMap<PathBuf, Date>,
+    /// Message of the `update --commit` commit, with `{date}` and
+    /// `{count}` (the number of files) placeholders
+    pub commit_message: String,
+    /// Keep the front matter and first heading of each note in a cache
+    /// next to src, so unchanged notes are not read again
+    pub head_cache: bool,
+}
+
+impl Options {
+    /// Whether title heuristics are switched off for a src-relative path
+    pub fn plain_titles(&self, relative: &Path) -> bool {
+        self.plain_title_dirs.iter().any(|dir| relative.starts_with(dir))
+    }
+
+    /// Sort strategy for the section a src-relative path is in
+    pub fn sort_for(&self, relative: &Path) -> SortOrder {
+        relative.components().next()
+            .and_then(|section| self.section_sort.get(section.as_os_str().to_string_lossy().as_ref()))
+            .copied()
+            .unwrap_or(self.sort)
+    }
+
+    /// Custom order for the section a src-relative path is in, if any
+    pub fn sorter_for(&self, relative: &Path) -> Option<&dyn Sorter> {
+        let section = relative.components().next().map(|c| c.as_os_str().to_string_lossy()).unwrap_or_default();
+        self.sorters.get(section.as_ref()).or_else(|| self.sorters.get("")).map(Box::as_ref)
+    }
+
+    /// Whether every custom filter includes a chapter
+    pub fn passes_filters(&self, candidate: &Candidate) -> bool {
+        self.filters.iter().all(|filter| filter.include(candidate))
+    }
+
+    /// How duplicate titles are told apart in the section a src-relative
+    /// path is in
+    pub fn disambiguator_for(&self, relative: &Path) -> Disambiguator {
+        relative.components().next()
+            .and_then(|section| self.section_disambiguate.get(section.as_os_str().to_string_lossy().as_ref()))
+            .copied()
+            .unwrap_or(self.disambiguate)
+    }
+
+    /// Whether a src-relative path is configured to be left out
+    pub fn is_excluded(&self, relative: &Path, is_dir: bool) -> bool {
+        self.exclude.is_ignored(relative, is_dir)
+    }
+
+    /// Generated pages to list in the summary, in order
+    pub fn generated_pages(&self) -> Vec<GeneratedPage> {
+        let mut pages = Vec::new();
+        if self.acronym_index {
+            pages.push(GeneratedPage::Acronyms);
+        }
+        if self.archive_page {
+            pages.push(GeneratedPage::Archive);
+        }
+        if self.changes_since.is_some() {
+            pages.push(GeneratedPage::Changes);
+        }
+        if self.time_log {
+            pages.push(GeneratedPage::TimeLog);
+        }
+        if self.tag_index || self.tag_pages {
+            pages.push(GeneratedPage::Tags);
+        }
+        if self.stats_page {
+            pages.push(GeneratedPage::Stats);
+        }
+        pages
+    }
+
+    /// Path of a generated page relative to src
+    pub fn generated_path(&self, page: GeneratedPage) -> PathBuf {
+        self.generated_dir.join(page.file_name())
+    }
+
+    /// Directory (relative to src) holding draft placeholder pages
+    pub fn drafts_dir(&self) -> PathBuf {
+        self.generated_dir.join("drafts")
+    }
+
+    /// Draft line for a chapter without a page. With `include_drafts` it
+    /// links to a placeholder page that is recorded in `stats`.
+    fn draft_entry(&self, title: &str, relative: &Path, stats: &mut ScanStats) -> String {
+        if !self.include_drafts {
+            return format!("- [{}]()", escape_title(title));
+        }
+        let mut page = self.drafts_dir().join(relative);
+        page.as_mut_os_string().push(".md");
+        let link = format!("- [{}](./{})", escape_title(title), page.to_string_lossy().replace('\\', "/"));
+        stats.drafts.push((title.to_string(), page));
+        link
+    }
+
+    /// Open a part named after its directory or generated page
+    fn begin_section(&self, name: &str, out: &mut dyn Write) -> io::Result<()> {
+        if self.section_markers {
+            writeln!(out, "<!-- mdbook-daily:section:{} -->", name)?;
+        }
+        Ok(())
+    }
+
+    /// Close a part opened by `begin_section`
+    fn end_section(&self, name: &str, out: &mut dyn Write) -> io::Result<()> {
+        if self.section_markers {
+            writeln!(out)?;
+            writeln!(out, "<!-- mdbook-daily:end-section:{} -->", name)?;
+        }
+        Ok(())
+    }
+
+    /// Date of the entry at `path`, a `/`-separated path relative to src
+    /// or its section
+    pub fn entry_date(&self, path: &str) -> Option<Date> {
+        self.path_date(path).or_else(|| self.created.get(Path::new(path.trim_start_matches("./"))).copied())
+    }
+
+    /// Date of the note at `relative` (relative to src, `/`-separated)
+    /// from the date sources of its section, in order, and every date its
+    /// sources give when two are more than `date_tolerance` days apart
+    pub fn dated_by_sources(&self, src_path: &Path, relative: &str, front: Option<&FrontMatter>) -> (Option<Date>, Vec<(DateSource, Date)>) {
+        let sources = Path::new(relative).components().next()
+            .and_then(|section| self.section_date_sources.get(section.as_os_str().to_string_lossy().as_ref()))
+            .unwrap_or(&self.date_sources);
+        let found: Vec<(DateSource, Date)> = sources.iter()
+            .filter_map(|&source| {
+                let date = match source {
+                    DateSource::FrontMatter => front.and_then(|f| f.date),
+                    DateSource::Filename => self.path_date(relative),
+                    DateSource::Git => self.created.get(Path::new(relative)).copied(),
+                    DateSource::Mtime => fs::metadata(src_path.join(relative)).and_then(|m| m.modified()).ok()
+                        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map(|since| Date::from_days(since.as_secs() as i64 / 86_400)),
+                };
+                Some((source, date?))
+            })
+            .collect();
+        let days = found.iter().map(|(_, date)| date.days());
+        let spread = days.clone().max().unwrap_or(0) - days.min().unwrap_or(0);
+        let disagreeing = self.date_tolerance.is_some_and(|tolerance| spread > tolerance as i64);
+        (found.first().map(|(_, date)| *date), if disagreeing { found } else { Vec::new() })
+    }
+
+    /// Date in the file or directory names of `path`, without falling
+    /// back to git
+    pub fn path_date(&self, path: &str) -> Option<Date> {
+        let stem = path.strip_suffix(".md").unwrap_or(path);
+        self.date_parsers.iter().find_map(|parser| parser.parse(stem)).or_else(|| date_from_path(path))
+    }
+
+    /// Read the dates notes were added to git when `git_dates` is set
+    pub fn read_git_dates(&mut self, src_path: &Path) {
+        if self.git_dates {
+            self.created = git::creation_dates(src_path);
+        }
+    }
+
+    /// Display title for a file stem or directory name at `relative`
+    pub fn title_for(&self, name: &str, relative: &Path) -> String {
+        let custom_date = || self.date_parsers.iter().any(|parser| parser.parse(name).is_some());
+        if self.plain_titles(relative) || (!self.title_transform.dates && custom_date()) {
+            name.to_string()
+        } else {
+            self.title_transform.apply(strip_numeric_prefix(name))
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            max_file_size: 16 * 1024 * 1024,
+            head_size: 8192,
+            jobs: None,
+            reads_per_second: None,
+            year_separators: false,
+            exclude_before: None,
+            generated_dir: PathBuf::from("_generated"),
+            archive_page: false,
+            acronym_index: false,
+            changes_since: None,
+            team_digest: false,
+            time_log: false,
+            tag_index: false,
+            tag_pages: false,
+            series_pages: false,
+            calendar_pages: false,
+            stats_page: false,
+            recent_entries: None,
+            on_this_day: false,
+            rollup: None,
+            feed_entries: None,
+            feed_html: false,
+            feed_sections: false,
+            read_only_sources: false,
+            strict: false,
+            hydrate_placeholders: false,
+            site_url: None,
+            include_drafts: false,
+            statuses: ["seed", "draft", "growing", "evergreen"].map(String::from).to_vec(),
+            hidden_statuses: Vec::new(),
+            line_ending: LineEnding::Lf,
+            section_markers: false,
+            missing_image_placeholders: false,
+            entry_navigation: false,
+            series_navigation: false,
+            related_notes: None,
+            related_by: vec![RelatedBy::Tags, RelatedBy::Links, RelatedBy::Titles],
+            wiki_links: None,
+            plain_title_dirs: Vec::new(),
+            pinned: Vec::new(),
+            pinned_unnumbered: false,
+            suffix: Vec::new(),
+            section_layout: HashMap::new(),
+            sort: SortOrder::Asc,
+            section_sort: HashMap::new(),
+            sorters: HashMap::new(),
+            filters: Vec::new(),
+            disambiguate: Disambiguator::None,
+            section_disambiguate: HashMap::new(),
+            exclude: Ignore::default(),
+            symlinks: SymlinkPolicy::Follow,
+            max_depth: None,
+            flatten_deep: false,
+            indent_width: 2,
+            section_titles: HashMap::new(),
+            strings: Strings::default(),
+            title_transform: TitleTransform::default(),
+            date_parsers: Vec::new(),
+            git_dates: false,
+            date_sources: vec![DateSource::FrontMatter, DateSource::Filename, DateSource::Git],
+            section_date_sources: HashMap::new(),
+            date_tolerance: Some(30),
+            created: HashMap::new(),
+            commit_message: "Update SUMMARY.md for {date}".to_string(),
+            head_cache: false,
+        }
+    }
+}
+
+/// Whether a directory name looks like a year (`2024`)
+fn is_year(name: &str) -> bool {
+    name.len() == 4 && name.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Counters collected while walking the src directory
+#[derive(Default)]
+pub struct ScanStats {
+    pub dirs: usize,
+    pub files: usize,
+    /// Files left out of the summary, with the reason
+    pub skipped: Vec<(PathBuf, String)>,
+    /// Entries left out for being older than the cutoff: date, title, link
+    pub excluded: Vec<(Date, String, String)>,
+    /// Dated entries listed in the summary: date, title, link
+    pub dated: Vec<(Date, String, String)>,
+    /// Draft chapters given placeholder pages: title, page path relative to src
+    pub drafts: Vec<(String, PathBuf)>,
+    /// Front matter of the listed entries that have it, by link
+    pub front_matter: HashMap<String, FrontMatter>,
+    /// Entries grouped by their `series` front matter
+    pub series: Vec<series::Series>,
+    /// Monthly calendar pages listed under their years
+    pub calendars: Vec<calendar::Month>,
+    /// Titles shared by entries of one section and left as they are:
+    /// section, title and count
+    pub duplicate_titles: Vec<(String, String, usize)>,
+    /// Entries whose date sources disagree by more than `date_tolerance`
+    /// days, with the date of each source
+    pub date_conflicts: Vec<(PathBuf, Vec<(DateSource, Date)>)>,
+    /// Undated entries named for a date that does not exist, such as
+    /// `0231.md`, with the date they are named for
+    pub impossible_dates: Vec<(PathBuf, Date)>,
+    /// Heads of notes read by earlier runs, when `head_cache` is set
+    pub heads: HeadCache,
+}
+
+impl ScanStats {
+    fn skip(&mut self, path: &Path, reason: String) {
+        self.skipped.push((path.to_path_buf(), reason));
+    }
+
+    /// With `strict`, fail when any path was skipped, listing them
+    pub fn check_strict(&self, strict: bool) -> io::Result<()> {
+        if !strict || self.skipped.is_empty() {
+            return Ok(());
+        }
+        let paths: Vec<String> = self.skipped.iter().map(|(path, reason)| format!("{} ({})", path.display(), reason)).collect();
+        Err(io::Error::other(format!("strict mode: skipped {} path(s): {}", self.skipped.len(), paths.join("; "))))
+    }
+
+    /// Print the skipped files and duplicate titles to stderr
+    pub fn print_warnings(&self) {
+        if !self.duplicate_titles.is_empty() {
+            eprintln!("Warning: {} title(s) are shared within a section; set `disambiguate` to tell them apart:", self.duplicate_titles.len());
+            for (section, title, count) in &self.duplicate_titles {
+                eprintln!("  {}: {:?} ({} entries)", section, title, count);
+            }
+        }
+        if !self.date_conflicts.is_empty() {
+            eprintln!("Warning: {} entries have dates that disagree, using the first of their `date-sources`:", self.date_conflicts.len());
+            for (path, dates) in &self.date_conflicts {
+                let dates: Vec<String> = dates.iter().map(|(source, date)| format!("{} {}", source.name(), date)).collect();
+                eprintln!("  {}: {}", path.display(), dates.join(", "));
+            }
+        }
+        if !self.impossible_dates.is_empty() {
+            eprintln!("Warning: {} entries are named for dates that do not exist, so they are listed undated:", self.impossible_dates.len());
+            for (path, date) in &self.impossible_dates {
+                eprintln!("  {}: {}", path.display(), date);
+            }
+        }
+        if self.skipped.is_empty() {
+            return;
+        }
+        eprintln!("Warning: skipped {} path(s):", self.skipped.len());
+        for (path, reason) in &self.skipped {
+            eprintln!("  {}: {}", path.display(), reason);
+        }
+    }
+}
+
+/// Whether a file is a OneDrive or Dropbox stub whose content lives only
+/// in the cloud and is fetched on first read
+#[cfg(windows)]
+fn is_cloud_placeholder(metadata: &fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_OFFLINE: u32 = 0x1000;
+    const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x40000;
+    const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x400000;
+    metadata.file_attributes()
+        & (FILE_ATTRIBUTE_OFFLINE | FILE_ATTRIBUTE_RECALL_ON_OPEN | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+        != 0
+}
+
+#[cfg(not(windows))]
+fn is_cloud_placeholder(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+/// Reason a `.md` file should not be listed, if any
+pub fn check_markdown_file(path: &Path, options: &Options) -> Option<String> {
+    read_head(path, options).err()
+}
+
+/// The first `options.head_size` bytes of a listable `.md` file, or the reason
+/// it should not be listed
+pub fn read_head(path: &Path, options: &Options) -> Result<Vec<u8>, String> {
+    let metadata = fs::metadata(path).map_err(|e| format!("cannot read metadata: {}", e))?;
+    // Sniffing the content would download a cloud-only file
+    if !options.hydrate_placeholders && is_cloud_placeholder(&metadata) {
+        return Err("cloud-only placeholder, not downloaded (use --hydrate-placeholders)".to_string());
+    }
+    let size = metadata.len();
+    if size > options.max_file_size {
+        return Err(format!("{} bytes exceeds the {} byte limit", size, options.max_file_size));
+    }
+    let mut head = Vec::new();
+    let read = fs::File::open(path)
+        .and_then(|file| file.take(options.head_size).read_to_end(&mut head));
+    match read {
+        Err(e) => Err(format!("cannot read file: {}", e)),
+        Ok(_) if head.contains(&0) => Err("looks like binary content".to_string()),
+        Ok(_) => Ok(head),
+    }
+}
+
+/// The first `limit` bytes of the text file at `path`, less a character
+/// cut off at the limit
+pub fn read_text(path: &Path, limit: u64) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    fs::File::open(path)?.take(limit).read_to_end(&mut bytes)?;
+    match String::from_utf8(bytes) {
+        Ok(text) => Ok(text),
+        Err(e) if e.utf8_error().error_len().is_none() => {
+            let valid = e.utf8_error().valid_up_to();
+            let mut bytes = e.into_bytes();
+            bytes.truncate(valid);
+            Ok(String::from_utf8(bytes).unwrap_or_default())
+        }
+        Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+    }
+}
+
+/// The part of a file's head [`head_metadata`] reads: the front matter
+/// block and the first heading
+pub fn head_summary(head: &[u8]) -> String {
+    let text = String::from_utf8_lossy(head);
+    let (_, body) = frontmatter::split(&text);
+    let front = &text[..text.len() - body.len()];
+    match first_heading(body) {
+        Some(heading) => format!("{}# {}\n", front, heading),
+        None => front.to_string(),
+    }
+}
+
+/// First H1 heading and front matter in the head of a file. Only the
+/// bytes already sniffed are searched, so anything past `head_size` is
+/// not found.
+pub fn head_metadata(head: &[u8]) -> (Option<String>, Option<FrontMatter>) {
+    let text = String::from_utf8_lossy(head);
+    let (front, body) = frontmatter::split(&text);
+    (first_heading(body), front)
+}
+
+/// Text of the first `# ` heading, outside fenced code
+fn first_heading(body: &str) -> Option<String> {
+    let mut in_fence = false;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some(text) = line.strip_prefix("# ") {
+                let text = text.trim().trim_end_matches('#').trim();
+                if !text.is_empty() {
+                    return Some(text.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Title of a file chapter: its front matter title, its first H1, or the
+/// name-derived title when it has neither
+fn file_title(
+    heading: Option<String>,
+    front: Option<&FrontMatter>,
+    stem: &str,
+    relative: &Path,
+    options: &Options,
+) -> String {
+    front.and_then(|f| f.title.clone())
+        .or(heading)
+        .unwrap_or_else(|| options.title_for(stem, relative))
+}
+
+/// Read a directory's entries sorted by name
+fn read_entries(dir_path: &Path, stats: &mut ScanStats) -> io::Result<Vec<fs::DirEntry>> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir_path)? {
+        match entry {
+            Ok(entry) => entries.push(entry),
+            Err(e) => stats.skip(dir_path, format!("cannot read a directory entry: {}", e)),
+        }
+    }
+    entries.sort_by_key(|e| e.file_name());
+    Ok(entries)
+}
+
+/// Order sibling chapters by the strategy of their section. `front`
+/// gives an item's front matter, if it has one.
+fn sort_chapters<T>(
+    items: &mut [T],
+    base_path: &Path,
+    options: &Options,
+    path: impl Fn(&T) -> &Path,
+    front: impl Fn(&T) -> Option<&FrontMatter>,
+) {
+    let Some(first) = items.first() else { return };
+    let relative = |item: &T| path(item).strip_prefix(base_path).unwrap_or(path(item)).to_path_buf();
+    let path_date = |item: &T| options.entry_date(&relative(item).to_string_lossy().replace('\\', "/"));
+    // An entry named for a date that does not exist keeps its place
+    let sort_date = |item: &T| path_date(item).or_else(|| named_date(&relative(item).to_string_lossy().replace('\\', "/")));
+    let front_date = |item: &T| front(item)?.date;
+    if let Some(sorter) = options.sorter_for(&relative(first)) {
+        items.sort_by(|a, b| {
+            let (relative_a, relative_b) = (relative(a), relative(b));
+            let candidate = |item, relative| Candidate {
+                relative,
+                is_dir: path(item).is_dir(),
+                date: front_date(item).or_else(|| path_date(item)),
+                front_matter: front(item),
+            };
+            sorter.compare(&candidate(a, relative_a.as_path()), &candidate(b, relative_b.as_path()))
+        });
+        return;
+    }
+    let name = |item: &T| path(item).file_name().unwrap_or_default().to_os_string();
+    // Numbered names keep their order in either direction
+    let number = |item: &T| prefix_order(&path(item).file_stem().unwrap_or_default().to_string_lossy());
+    // Undated chapters follow the dated ones in every date order
+    match options.sort_for(&relative(first)) {
+        SortOrder::Asc => items.sort_by_cached_key(|item| {
+            let date = sort_date(item);
+            (date.is_none(), date, number(item), name(item))
+        }),
+        SortOrder::Desc => items.sort_by_cached_key(|item| {
+            let date = sort_date(item);
+            (date.is_none(), Reverse(date), number(item), Reverse(name(item)))
+        }),
+        SortOrder::Mtime => items.sort_by_cached_key(|item| {
+            Reverse(fs::metadata(path(item)).and_then(|m| m.modified()).ok())
+        }),
+        SortOrder::FrontmatterDate => items.sort_by_cached_key(|item| {
+            let date = front_date(item).or_else(|| sort_date(item));
+            (date.is_none(), Reverse(date), number(item), Reverse(name(item)))
+        }),
+    }
+}
+
+/// The manifest of `dir`. One that cannot be read is skipped with the
+/// reason, leaving the directory in its usual order.
+fn read_manifest(dir: &Path, stats: &mut ScanStats) -> Manifest {
+    Manifest::read(dir).unwrap_or_else(|e| {
+        stats.skip(&dir.join(manifest::FILE_NAME), format!("invalid manifest: {}", e));
+        Manifest::default()
+    })
+}
+
+/// File or directory name of `path`
+fn file_name(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().into_owned()
+}
+
+/// Whether `dir` is a symlink back to a directory already being walked.
+/// `ancestors` holds the canonical paths of the directories above it.
+fn symlink_cycle(dir: &Path, ancestors: &[PathBuf]) -> Option<PathBuf> {
+    if !fs::symlink_metadata(dir).is_ok_and(|m| m.file_type().is_symlink()) {
+        return None;
+    }
+    let target = fs::canonicalize(dir).ok()?;
+    ancestors.contains(&target).then_some(target)
+}
+
+/// Link target for `path` relative to `base_path` with `/` separators, or
+/// None if it is outside the base or not valid UTF-8
+fn summary_link(base_path: &Path, path: &Path) -> Option<String> {
+    Some(path.strip_prefix(base_path).ok()?.to_str()?.replace('\\', "/"))
+}
+
+/// Recursively process directory and write its SUMMARY entries to `out`.
+/// `ancestors` holds the canonical paths of the directories being walked,
+/// so symlinked shared content cannot loop.
+fn process_directory(
+    base_path: &Path,
+    entries: Vec<fs::DirEntry>,
+    level: usize,
+    ancestors: &mut Vec<PathBuf>,
+    options: &Options,
+    stats: &mut ScanStats,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let indent = " ".repeat(level * options.indent_width);
+    
+    stats.dirs += 1;
+
+    // Separate files and directories
+    let mut notes = Vec::new();
+    let mut md_files = Vec::new();
+    let mut subdirs = Vec::new();
+    // Subdirectories here would be deeper than `max-depth`
+    let too_deep = options.max_depth.is_some_and(|max| level >= max);
+    // Directories past the depth limit whose notes are listed at this level
+    let mut flattened: Vec<PathBuf> = Vec::new();
+    
+    let manifest = read_manifest(ancestors.last().map_or(base_path, PathBuf::as_path), stats);
+    let mut pending: Vec<PathBuf> = entries.iter()
+        .filter(|entry| !manifest.hides(&entry.file_name().to_string_lossy()))
+        .map(fs::DirEntry::path)
+        .collect();
+    while let Some(path) = pending.pop() {
+        let file_name_str = path.file_name().unwrap_or_default().to_string_lossy();
+        let relative = path.strip_prefix(base_path).unwrap_or(&path);
+        let top_level = options.pinned.iter().chain(&options.suffix).any(|p| p == relative);
+        if options.is_excluded(relative, path.is_dir()) || top_level {
+            continue;
+        }
+        if path.is_symlink() {
+            if !path.exists() {
+                stats.skip(&path, "broken symlink".to_string());
+                continue;
+            }
+            if options.symlinks == SymlinkPolicy::Skip {
+                continue;
+            }
+        }
+        
+        if path.is_file() && file_name_str.ends_with(".md") && file_name_str != "SUMMARY.md" {
+            stats.files += 1;
+            notes.push(path);
+        } else if path.is_dir() && path.strip_prefix(base_path).ok() != Some(&options.generated_dir) {
+            if !too_deep {
+                let relative = path.strip_prefix(base_path).unwrap_or(&path);
+                let date = options.entry_date(&relative.to_string_lossy().replace('\\', "/"));
+                if options.passes_filters(&Candidate { relative, is_dir: true, date, front_matter: None }) {
+                    subdirs.push(path);
+                }
+                continue;
+            }
+            if !options.flatten_deep {
+                stats.skip(&path, format!("deeper than max-depth {}", options.max_depth.unwrap_or_default()));
+                continue;
+            }
+            let Ok(target) = fs::canonicalize(&path) else { continue };
+            if ancestors.contains(&target) || flattened.iter().any(|dir| fs::canonicalize(dir).ok() == Some(target.clone())) {
+                stats.skip(&path, format!("symlink loops back to {}", target.display()));
+                continue;
+            }
+            match read_entries(&path, stats) {
+                Ok(entries) => pending.extend(entries.iter().map(fs::DirEntry::path)),
+                Err(e) => stats.skip(&path, format!("cannot read directory: {}", e)),
+            }
+            stats.dirs += 1;
+            flattened.push(path);
+        }
+    }
+
+    // The notes of a directory are read together, so they can be read in parallel
+    let heads = cache::read_heads(base_path, &notes, options, &mut stats.heads);
+    for (path, head) in notes.into_iter().zip(heads) {
+        let head = match head {
+            Ok(head) => head,
+            Err(reason) => {
+                stats.skip(&path, reason);
+                continue;
+            }
+        };
+        let (heading, front) = head_metadata(&head);
+        // Drafts only show up in local previews
+        if front.as_ref().is_some_and(|f| f.draft) && !options.include_drafts {
+            continue;
+        }
+        if front.as_ref().is_some_and(FrontMatter::is_expired) {
+            continue;
+        }
+        if front.as_ref().and_then(|f| f.status.as_ref()).is_some_and(|status| options.hidden_statuses.contains(status)) {
+            continue;
+        }
+        if !options.filters.is_empty() {
+            let relative = path.strip_prefix(base_path).unwrap_or(&path);
+            let candidate = Candidate {
+                relative,
+                is_dir: false,
+                date: front.as_ref().and_then(|f| f.date)
+                    .or_else(|| options.entry_date(&relative.to_string_lossy().replace('\\', "/"))),
+                front_matter: front.as_ref(),
+            };
+            if !options.passes_filters(&candidate) {
+                continue;
+            }
+        }
+        md_files.push((path, heading, front));
+    }
+    
+    sort_chapters(&mut subdirs, base_path, options, |dir| dir, |_| None);
+    manifest.reorder(&mut subdirs, |dir| file_name(dir));
+
+    // Process subdirectories
+    let mut previous_was_year = false;
+    for subdir in subdirs {
+        let Some(dir_name) = subdir.file_name() else { continue };
+        let dir_name = dir_name.to_string_lossy();
+        let title = match manifest.title(&dir_name) {
+            Some(title) => title.to_string(),
+            None => options.title_for(&dir_name, subdir.strip_prefix(base_path).unwrap_or(&subdir)),
+        };
+
+        // Break up consecutive year groups at the top of a section
+        if level == 0 && options.year_separators {
+            let year = is_year(&dir_name);
+            if year && previous_was_year {
+                writeln!(out)?;
+                writeln!(out, "---")?;
+                writeln!(out)?;
+            }
+            previous_was_year = year;
+        }
+        let readme_path = subdir.join("README.md");
+
+        if let Some(target) = symlink_cycle(&subdir, ancestors) {
+            stats.skip(&subdir, format!("symlink loops back to {}", target.display()));
+            continue;
+        }
+
+        // Keep unreadable directories visible as draft chapters
+        let subdir_entries = match read_entries(&subdir, stats) {
+            Ok(entries) => entries,
+            Err(e) => {
+                stats.skip(&subdir, format!("cannot read directory: {}", e));
+                if options.include_drafts {
+                    let relative = subdir.strip_prefix(base_path).unwrap_or(&subdir);
+                    writeln!(out, "{}{}", indent, options.draft_entry(&title, relative, stats))?;
+                } else {
+                    writeln!(out, "{}- [{}]()", indent, escape_title(&title))?;
+                }
+                continue;
+            }
+        };
+        
+        let readme_link = if readme_path.exists() && check_markdown_file(&readme_path, options).is_none() {
+            let link = summary_link(base_path, &readme_path);
+            if link.is_none() {
+                stats.skip(&readme_path, "path is not valid UTF-8".to_string());
+            }
+            link
+        } else {
+            None
+        };
+        if let Some(link) = readme_link {
+            writeln!(out, "{}- [{}](./{})", indent, escape_title(&title), link)?;
+        } else {
+            // If no README, still process subdirectory
+            let relative = subdir.strip_prefix(base_path).unwrap_or(&subdir);
+            writeln!(out, "{}{}", indent, options.draft_entry(&title, relative, stats))?;
+        }
+        // Process files in subdirectory with increased indentation
+        let dated_before = stats.dated.len();
+        ancestors.push(fs::canonicalize(&subdir)?);
+        process_directory(base_path, subdir_entries, level + 1, ancestors, options, stats, out)?;
+        ancestors.pop();
+        // A year's calendar pages follow its entries
+        if options.calendar_pages && is_year(&dir_name) {
+            let relative = subdir.strip_prefix(base_path).unwrap_or(&subdir);
+            let child_indent = " ".repeat((level + 1) * options.indent_width);
+            for month in calendar::months(options, relative, &stats.dated[dated_before..]) {
+                writeln!(out, "{}- [{}](./{})", child_indent, escape_title(&month.title), month.page.to_string_lossy().replace('\\', "/"))?;
+                stats.calendars.push(month);
+            }
+        }
+    }
+    
+    // Process markdown files (excluding README.md as it's already processed)
+    sort_chapters(&mut md_files, base_path, options, |(path, _, _)| path, |(_, _, front)| front.as_ref());
+    // Notes with an `order` go first, the rest keep their sorted order
+    md_files.sort_by_key(|(_, _, front)| front.as_ref().and_then(|f| f.order).map_or((1, 0), |order| (0, order)));
+    manifest.reorder(&mut md_files, |(path, _, _)| file_name(path));
+    for (md_file, heading, front) in md_files {
+        let Some(file_name) = md_file.file_name() else { continue };
+        let file_name = file_name.to_string_lossy();
+        // This directory's README is its own chapter; flattened ones are notes
+        if file_name == "README.md" && !md_file.parent().is_some_and(|dir| flattened.iter().any(|f| f == dir)) {
+            continue;
+        }
+        
+        // A flattened README is named after its directory
+        let display_name = get_display_name(&file_name)
+            .or_else(|| Some(md_file.parent()?.file_name()?.to_string_lossy().into_owned()));
+        if let Some(display_name) = display_name {
+            let Some(relative_path_str) = summary_link(base_path, &md_file) else {
+                stats.skip(&md_file, "path is not valid UTF-8".to_string());
+                continue;
+            };
+            let display_name = match manifest.title(&file_name) {
+                Some(title) => title.to_string(),
+                None => file_title(heading, front.as_ref(), &display_name, Path::new(&relative_path_str), options),
+            };
+            let (date, conflict) = options.dated_by_sources(base_path, &relative_path_str, front.as_ref());
+            if !conflict.is_empty() {
+                stats.date_conflicts.push((md_file.clone(), conflict));
+            }
+            if let Some(named) = named_date(&relative_path_str).filter(|_| date.is_none()) {
+                stats.impossible_dates.push((md_file.clone(), named));
+            }
+            let week = front.as_ref().and_then(|f| f.week);
+            if let Some(front) = front {
+                stats.front_matter.insert(format!("./{}", relative_path_str), front);
+            }
+            if let Some(date) = date {
+                let link = (display_name.clone(), format!("./{}", relative_path_str));
+                if options.exclude_before.is_some_and(|cutoff| date < cutoff) {
+                    stats.excluded.push((date, link.0, link.1));
+                    continue;
+                }
+                stats.dated.push((date, link.0, link.1));
+            }
+            writeln!(out, "{}- [{}](./{})", indent, escape_title(&display_name), relative_path_str)?;
+            if let Some(monday) = week {
+                write_planned_days(base_path, Path::new(&relative_path_str), monday, &indent, options, stats, out)?;
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// Draft chapters under a planning page for the days of its week, from
+/// today on, that have no note yet
+fn write_planned_days(
+    base_path: &Path,
+    page: &Path,
+    monday: Date,
+    indent: &str,
+    options: &Options,
+    stats: &mut ScanStats,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let Some(section) = page.components().next() else { return Ok(()) };
+    let today = Date::today();
+    for offset in 0..7 {
+        let day = Date::from_days(monday.days() + offset);
+        let entry = Path::new(section.as_os_str()).join(day.entry_path());
+        if day < today || base_path.join(&entry).exists() {
+            continue;
+        }
+        let relative = entry.with_extension("");
+        let name = relative.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let title = options.title_for(&name, &relative);
+        writeln!(out, "{}{}{}", indent, " ".repeat(options.indent_width), options.draft_entry(&title, &relative, stats))?;
+    }
+    Ok(())
+}
+
+/// Write the entries below one directory of a section, as they appear
+/// in the full summary. `relative` is the directory relative to src.
+pub fn write_subtree(
+    src_path: &Path,
+    relative: &Path,
+    options: &Options,
+    stats: &mut ScanStats,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut ancestors = vec![fs::canonicalize(src_path)?];
+    let mut dir = src_path.to_path_buf();
+    for component in relative.components() {
+        dir.push(component);
+        ancestors.push(fs::canonicalize(&dir)?);
+    }
+    let level = relative.components().count() - 1;
+    let entries = read_entries(&dir, stats)?;
+    process_directory(src_path, entries, level, &mut ancestors, options, stats, out)
+}
+
+/// Write chapters listed by path (relative to src) outside the sections,
+/// as numbered list items or as unnumbered prefix or suffix chapters.
+/// Missing files are left out with a warning.
+fn write_top_level(
+    src_path: &Path,
+    paths: &[PathBuf],
+    numbered: bool,
+    options: &Options,
+    stats: &mut ScanStats,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    // The recent entries and on this day pages are written after the summary
+    let recent = |p: &Path| options.recent_entries.is_some() && p == Path::new(recent::RECENT_PAGE);
+    let on_this_day = |p: &Path| options.on_this_day && p == Path::new(onthisday::ON_THIS_DAY_PAGE);
+    let (paths, missing): (Vec<_>, Vec<_>) = paths.iter().partition(|p| src_path.join(p).is_file() || recent(p) || on_this_day(p));
+    for path in missing {
+        stats.skip(path, "configured chapter is missing".to_string());
+    }
+    if !paths.is_empty() {
+        writeln!(out)?;
+    }
+    for path in paths {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let (heading, front) = read_head(&src_path.join(path), options)
+            .map_or((None, None), |head| head_metadata(&head));
+        let link = path.to_string_lossy().replace('\\', "/");
+        let marker = if numbered { "- " } else { "" };
+        let title = match heading {
+            None if recent(path) && !src_path.join(path).is_file() => options.strings.recent_entries.clone(),
+            None if on_this_day(path) && !src_path.join(path).is_file() => options.strings.on_this_day.clone(),
+            _ => file_title(heading, front.as_ref(), &stem, path, options),
+        };
+        writeln!(out, "{}[{}](./{})", marker, escape_title(&title), link)?;
+    }
+    Ok(())
+}
+
+/// Generate SUMMARY.md content from src directory structure into `out`.
+/// This does not stream: each section is built whole before it is
+/// written, so titles shared within it can be told apart and its entries
+/// regrouped under rollup pages and series, and `stats` keeps the date,
+/// title and front matter of every entry for the generated pages. Memory
+/// grows with the book; only note reads are bounded, to `head_size`.
+pub fn write_summary_to(
+    src_path: &Path,
+    options: &Options,
+    stats: &mut ScanStats,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    writeln!(out, "# {}", options.strings.summary)?;
+    if options.head_cache {
+        stats.heads = HeadCache::load(&cache::cache_path(src_path));
+    }
+    
+    // Pinned chapters go at the top
+    let mut pinned = options.pinned.clone();
+    if options.recent_entries.is_some() {
+        pinned.push(PathBuf::from(recent::RECENT_PAGE));
+    }
+    if options.on_this_day {
+        pinned.push(PathBuf::from(onthisday::ON_THIS_DAY_PAGE));
+    }
+    write_top_level(src_path, &pinned, !options.pinned_unnumbered, options, stats, out)?;
+    
+    // Get all subdirectories
+    let mut subdirs: Vec<_> = read_entries(src_path, stats)?
+        .into_iter()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir() && *p != src_path.join(&options.generated_dir))
+        .filter(|p| !options.is_excluded(p.strip_prefix(src_path).unwrap_or(p), true))
+        .filter(|p| options.symlinks == SymlinkPolicy::Follow || !p.is_symlink())
+        .collect();
+    
+    // A manifest at the top of src orders and hides sections
+    let manifest = read_manifest(src_path, stats);
+    subdirs.retain(|dir| !manifest.hides(&file_name(dir)));
+    subdirs.sort_by_cached_key(|dir| (prefix_order(&dir.file_name().unwrap_or_default().to_string_lossy()), dir.clone()));
+    manifest.reorder(&mut subdirs, |dir| file_name(dir));
+    
+    for subdir in subdirs {
+        // Create section header
+        let Some(dir_name) = subdir.file_name() else { continue };
+        let dir_name = dir_name.to_string_lossy();
+        let section_name = section_title(options, &dir_name);
+        let layout = options.section_layout.get(dir_name.as_ref()).copied().unwrap_or_default();
+        writeln!(out)?;
+        options.begin_section(&dir_name, out)?;
+        if layout.separator {
+            writeln!(out, "---")?;
+            writeln!(out)?;
+        }
+        if layout.part {
+            writeln!(out, "# {}", section_name)?;
+            writeln!(out)?;
+        }
+
+        // Process the subdirectory. It is buffered so titles shared
+        // within the section can be told apart.
+        match read_entries(&subdir, stats) {
+            Ok(entries) => {
+                let mut ancestors = vec![fs::canonicalize(src_path)?, fs::canonicalize(&subdir)?];
+                let mut section = Vec::new();
+                let dated_before = stats.dated.len();
+                process_directory(src_path, entries, 0, &mut ancestors, options, stats, &mut section)?;
+                let section = String::from_utf8_lossy(&section);
+                let how = options.disambiguator_for(Path::new(dir_name.as_ref()));
+                let section = disambiguate(&section, &dir_name, how, dated_before, stats);
+                let section = rollup::nest_entries(&section, options, stats);
+                out.write_all(series::group_series(&section, options, stats).as_bytes())?;
+            }
+            Err(e) => {
+                stats.skip(&subdir, format!("cannot read directory: {}", e));
+                writeln!(out, "- [{}]()", escape_title(&section_name))?;
+            }
+        }
+        options.end_section(&dir_name, out)?;
+    }
+
+    for page in options.generated_pages() {
+        let path = options.generated_path(page);
+        let name = page.file_name().trim_end_matches(".md");
+        writeln!(out)?;
+        options.begin_section(name, out)?;
+        let title = page.title(&options.strings);
+        writeln!(out, "# {}", title)?;
+        writeln!(out)?;
+        writeln!(out, "- [{}](./{})", escape_title(title), path.to_string_lossy().replace('\\', "/"))?;
+        if matches!(page, GeneratedPage::Tags) && options.tag_pages {
+            for tag in tags::tag_names(stats) {
+                let path = tags::tag_path(options, &tag);
+                writeln!(out, "{}- [{}](./{})", " ".repeat(options.indent_width), escape_title(&tag), path.to_string_lossy().replace('\\', "/"))?;
+            }
+        }
+        options.end_section(name, out)?;
+    }
+
+    let dates = team::digest_dates(stats);
+    if options.team_digest && !dates.is_empty() {
+        writeln!(out)?;
+        options.begin_section("team-daily", out)?;
+        writeln!(out, "# {}", options.strings.team_daily)?;
+        writeln!(out)?;
+        for date in dates {
+            let path = team::digest_path(options, date);
+            writeln!(out, "- [{}](./{})", date, path.to_string_lossy().replace('\\', "/"))?;
+        }
+        options.end_section("team-daily", out)?;
+    }
+
+    // Suffix chapters have to come after every part
+    write_top_level(src_path, &options.suffix, false, options, stats, out)?;
+
+    stats.heads.save(src_path, &cache::cache_path(src_path))
+}
+
+/// A section's entries with titles that more than one entry uses given
+/// a suffix, or recorded in `stats` if `how` is `None`. Drafts keep
+/// their title.
+fn disambiguate(section: &str, dir_name: &str, how: Disambiguator, dated_before: usize, stats: &mut ScanStats) -> String {
+    let entries: Vec<Option<SummaryEntry>> = section.lines().enumerate().map(|(i, line)| parse_entry(i, line)).collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for entry in entries.iter().flatten().filter(|e| e.path.is_some()) {
+        *counts.entry(entry.title.as_str()).or_default() += 1;
+    }
+    let mut shared: Vec<(&str, usize)> = counts.into_iter().filter(|(_, count)| *count > 1).collect();
+    if shared.is_empty() {
+        return section.to_string();
+    }
+    shared.sort();
+    if matches!(how, Disambiguator::None) {
+        for (title, count) in shared {
+            stats.duplicate_titles.push((dir_name.to_string(), title.to_string(), count));
+        }
+        return section.to_string();
+    }
+
+    let dates: HashMap<&str, Date> = stats.dated[dated_before..].iter().map(|(date, _, link)| (link.as_str(), *date)).collect();
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    let mut out = String::with_capacity(section.len());
+    for (line, entry) in section.lines().zip(&entries) {
+        let suffix = entry.as_ref()
+            .filter(|e| shared.iter().any(|(title, _)| *title == e.title))
+            .and_then(|e| {
+                let link = e.path.as_deref()?;
+                let path = Path::new(link.strip_prefix("./").unwrap_or(link));
+                // A README stands for its directory, so its parent is one up
+                let dir = if path.file_name().is_some_and(|n| n == "README.md") { path.parent()?.parent()? } else { path.parent()? };
+                match how {
+                    Disambiguator::Date => dates.get(link).map(Date::to_string),
+                    _ => dir.file_name().map(|name| name.to_string_lossy().into_owned()),
+                }
+            });
+        match (entry, suffix) {
+            (Some(entry), Some(suffix)) => {
+                let title = format!("{} ({})", entry.title, suffix);
+                let link = entry.path.clone().unwrap_or_default();
+                out.push_str(&format!("{}- [{}]({})", entry.indent, escape_title(&title), link));
+                renamed.insert(link, title);
+            }
+            _ => out.push_str(line),
+        }
+        out.push('\n');
+    }
+    // Index pages built from the scan show the same titles
+    for (_, title, link) in &mut stats.dated[dated_before..] {
+        if let Some(renamed) = renamed.get(link.as_str()) {
+            title.clone_from(renamed);
+        }
+    }
+    out
+}
+
+/// Comments delimiting the part of a hand-maintained SUMMARY.md that the
+/// generator owns; everything outside them is kept verbatim
+pub const MANAGED_START: &str = "<!-- mdbook-daily:start -->";
+pub const MANAGED_END: &str = "<!-- mdbook-daily:end -->";
+
+/// Byte range between the managed-region markers of a summary, if it
+/// has both
+pub fn managed_region(existing: &str) -> Option<(usize, usize)> {
+    let start = existing.find(MANAGED_START)? + MANAGED_START.len();
+    let end = existing[start..].find(MANAGED_END)? + start;
+    Some((start, end))
+}
+
+/// The summary to write over `existing`: the generated one, or, when
+/// `existing` has a managed region, `existing` with just that region
+/// replaced by the generated parts (without their title heading)
+pub fn merge_managed(existing: Option<&str>, generated: String) -> String {
+    let Some((existing, (start, end))) = existing.and_then(|e| Some((e, managed_region(e)?))) else {
+        return generated;
+    };
+    let body = match generated.split_once('\n') {
+        Some((title, rest)) if title.starts_with("# ") => rest,
+        _ => &generated,
+    }.trim();
+    format!("{}\n\n{}\n\n{}", &existing[..start], body, &existing[end..])
+}
+
+/// Generate SUMMARY.md content from src directory structure
+pub fn generate_summary(src_path: &Path, options: &Options, stats: &mut ScanStats) -> io::Result<String> {
+    let mut content = Vec::new();
+    write_summary_to(src_path, options, stats, &mut content)?;
+    Ok(String::from_utf8_lossy(&content).into_owned())
+}
+
+/// Write the page listing entries excluded by age. The entries are not
+/// part of the book, so they are listed by title and source path only.
+pub fn write_archive_page(
+    strings: &Strings,
+    stats: &ScanStats,
+    authors: &HashMap<PathBuf, String>,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let mut excluded: Vec<_> = stats.excluded.iter().collect();
+    excluded.sort();
+    writeln!(out, "# {}", strings.archive)?;
+    writeln!(out)?;
+    writeln!(out, "{}", strings.archive_intro)?;
+    let mut year = None;
+    for (date, title, link) in excluded {
+        if year != Some(date.year) {
+            year = Some(date.year);
+            writeln!(out)?;
+            writeln!(out, "## {}", date.year)?;
+            writeln!(out)?;
+        }
+        let path = link.trim_start_matches("./");
+        match authors.get(Path::new(path)) {
+            Some(author) => writeln!(out, "- {} {} (`{}`) {}", date, title, path, strings.by_author.replace("{author}", author))?,
+            None => writeln!(out, "- {} {} (`{}`)", date, title, path)?,
+        }
+    }
+    Ok(())
+}
+
+/// Part title for a top-level section directory
+pub fn section_title(options: &Options, dir_name: &str) -> String {
+    if let Some(title) = options.section_titles.get(dir_name) {
+        title.clone()
+    } else if options.plain_titles(Path::new(dir_name)) {
+        capitalize_first(dir_name)
+    } else {
+        // Part titles have always started with a capital
+        capitalize_first(&options.title_transform.apply(strip_numeric_prefix(dir_name)))
+    }
+}
+
+/// The number and the rest of a `01_intro` or `10-projects` style name,
+/// which orders chapters by the number. Dates and years are not numbered.
+pub fn numeric_prefix(name: &str) -> Option<(u64, &str)> {
+    let digits = name.len() - name.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let rest = name[digits..].trim_start_matches(['_', '-']);
+    let separated = digits > 0 && rest.len() < name.len() - digits;
+    if !separated || !rest.starts_with(|c: char| !c.is_ascii_digit()) || date_from_path(name).is_some() {
+        return None;
+    }
+    Some((name[..digits].parse().ok()?, rest))
+}
+
+/// `name` without its numeric prefix
+fn strip_numeric_prefix(name: &str) -> &str {
+    numeric_prefix(name).map_or(name, |(_, rest)| rest)
+}
+
+/// Sort key putting numbered names first, by number
+fn prefix_order(name: &str) -> (u8, u64) {
+    numeric_prefix(name).map_or((1, 0), |(number, _)| (0, number))
+}
+
+/// Capitalize first letter of a string (simple title case). Letters
+/// without a single-character uppercase form, such as CJK, are left alone.
+pub fn capitalize_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) if first.is_lowercase() && first.to_uppercase().count() == 1 => {
+            let mut result = first.to_uppercase().to_string();
+            result.push_str(chars.as_str());
+            result
+        }
+        _ => s.to_string(),
+    }
+}
+
+/// Turn a file or directory name into a title: split camelCase and
+/// PascalCase into words (`aboutMe` -> `About Me`, `HTTPServer` ->
+/// `HTTP Server`) without changing the case of acronyms
+pub fn humanize(name: &str) -> String {
+    capitalize_first(&split_camel_case(name))
+}
+
+/// Put a space between the words of camelCase and PascalCase names
+pub fn split_camel_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || (prev.is_uppercase() && next_is_lower) {
+                words.push(' ');
+            }
+        }
+        words.push(c);
+    }
+    words
+}
+
+/// A chapter entry parsed back out of a SUMMARY.md file
+pub struct SummaryEntry {
+    pub line: usize,
+    pub indent: String,
+    pub title: String,
+    pub path: Option<String>,
+    /// A `- ` list item, rather than an unnumbered prefix or suffix chapter
+    pub numbered: bool,
+}
+
+impl SummaryEntry {
+    /// Key used to match the same entry across two summaries
+    pub fn key(&self) -> String {
+        match &self.path {
+            Some(path) => path.clone(),
+            None => format!("{}[{}]", self.indent, self.title),
+        }
+    }
+}
+
+/// `title` with the characters that end or escape link text in
+/// SUMMARY.md backslash-escaped
+pub fn escape_title(title: &str) -> String {
+    let mut escaped = String::with_capacity(title.len());
+    for c in title.chars() {
+        if matches!(c, '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Undo [`escape_title`] on link text read back from a summary. Other
+/// backslashes, as in a hand-written summary, are kept.
+fn unescape_title(text: &str) -> String {
+    let mut title = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(&next) = chars.peek().filter(|next| matches!(next, '[' | ']' | '\\')) {
+                title.push(next);
+                chars.next();
+                continue;
+            }
+        }
+        title.push(c);
+    }
+    title
+}
+
+/// Parse a `- [title](path)` or `- [title]` line
+pub fn parse_entry(line_no: usize, line: &str) -> Option<SummaryEntry> {
+    let trimmed = line.trim_start();
+    let indent = line[..line.len() - trimmed.len()].to_string();
+    // Unnumbered chapters are links on their own, outside any list
+    let (rest, numbered) = match trimmed.strip_prefix("- [") {
+        Some(rest) => (rest, true),
+        None if indent.is_empty() => (trimmed.strip_prefix('[')?, false),
+        None => return None,
+    };
+    if let Some(pos) = rest.rfind("](") {
+        let path = rest[pos + 2..].strip_suffix(')')?;
+        Some(SummaryEntry {
+            line: line_no,
+            indent,
+            title: unescape_title(&rest[..pos]),
+            path: if path.is_empty() { None } else { Some(path.to_string()) },
+            numbered,
+        })
+    } else {
+        Some(SummaryEntry {
+            line: line_no,
+            indent,
+            title: unescape_title(rest.strip_suffix(']')?),
+            path: None,
+            numbered,
+        })
+    }
+}
+
+pub fn parse_entries(content: &str) -> Vec<SummaryEntry> {
+    content.lines()
+        .enumerate()
+        .filter_map(|(i, line)| parse_entry(i, line))
+        .collect()
+}
+
+/// All entries of the summary the current tree would produce
+pub fn book_entries(src_path: &Path, options: &Options) -> io::Result<Vec<SummaryEntry>> {
+    let summary = generate_summary(src_path, options, &mut ScanStats::default())?;
+    Ok(parse_entries(&summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SummaryBuilder;
+
+    fn build(files: &[(&str, &str)]) -> (String, ScanStats) {
+        SummaryBuilder::from_files(files.iter().copied()).unwrap().build_with_stats().unwrap()
+    }
+
+    #[test]
+    fn dated_entries_sort_by_date_and_undated_by_file_name() {
+        let (summary, stats) = build(&[
+            ("journal/2026/README.md", "# 2026"),
+            ("journal/2026/0214.md", "# Valentine"),
+            ("journal/2026/0103.md", "# Zebra day"),
+            ("journal/2026/0105.md", "# Alpha day"),
+            ("notes/zeta.md", "# Aardvark"),
+            ("notes/ideas.md", "# Ideas"),
+        ]);
+        assert_eq!(summary, "# Summary\n\n# Journal\n\n\
+            - [2026](./journal/2026/README.md)\n  \
+              - [Zebra day](./journal/2026/0103.md)\n  \
+              - [Alpha day](./journal/2026/0105.md)\n  \
+              - [Valentine](./journal/2026/0214.md)\n\n\
+            # Notes\n\n\
+            - [Ideas](./notes/ideas.md)\n\
+            - [Aardvark](./notes/zeta.md)\n");
+        // Undated notes are listed but not counted as entries
+        let mut dated: Vec<&str> = stats.dated.iter().map(|(_, _, link)| link.as_str()).collect();
+        dated.sort();
+        assert_eq!(dated, ["./journal/2026/0103.md", "./journal/2026/0105.md", "./journal/2026/0214.md"]);
+    }
+
+    #[test]
+    fn readmes_are_the_pages_of_their_directories() {
+        // The book's and the sections' READMEs are not chapters; a
+        // subdirectory's is its chapter, named after the directory and
+        // listed before the notes beside it
+        let (summary, _) = build(&[
+            ("README.md", "# Home"),
+            ("journal/README.md", "# My journal"),
+            ("journal/2026/README.md", "# The year"),
+            ("journal/2026/0105.md", "# Day"),
+            ("journal/apples.md", "# Apples"),
+        ]);
+        assert_eq!(summary, "# Summary\n\n# Journal\n\n\
+            - [2026](./journal/2026/README.md)\n  \
+              - [Day](./journal/2026/0105.md)\n\
+            - [Apples](./journal/apples.md)\n");
+    }
+
+    #[test]
+    fn managed_regions_round_trip() {
+        let generated = "# Summary\n\n# Journal\n\n- [Day](./journal/0105.md)\n".to_string();
+        let existing = format!("# Summary\n\n[Intro](./intro.md)\n\n{}\nstale\n{}\n\n[Outro](./outro.md)\n", MANAGED_START, MANAGED_END);
+        let merged = merge_managed(Some(&existing), generated.clone());
+        assert_eq!(merged, format!(
+            "# Summary\n\n[Intro](./intro.md)\n\n{}\n\n# Journal\n\n- [Day](./journal/0105.md)\n\n{}\n\n[Outro](./outro.md)\n",
+            MANAGED_START, MANAGED_END,
+        ));
+        // Merging again changes nothing, and a summary without markers is replaced
+        assert_eq!(merge_managed(Some(&merged), generated.clone()), merged);
+        assert_eq!(merge_managed(Some("# Summary\n\n- [Old](./old.md)\n"), generated.clone()), generated);
+        assert_eq!(merge_managed(None, generated.clone()), generated);
+    }
+
+    #[test]
+    fn shared_titles_are_told_apart_per_section() {
+        let notes = [
+            ("journal/2026/0105.md", "# Standup"),
+            ("journal/2026/0212.md", "# Standup"),
+            ("work/2025/0301.md", "# Standup"),
+            ("work/2026/0301.md", "# Standup"),
+        ];
+        let mut options = Options { disambiguate: Disambiguator::Date, ..Options::default() };
+        options.section_disambiguate.insert("work".to_string(), Disambiguator::Parent);
+        let (summary, stats) = SummaryBuilder::from_files(notes).unwrap().options(options).build_with_stats().unwrap();
+        for title in ["[Standup (2026-01-05)]", "[Standup (2026-02-12)]", "[Standup (2025)]", "[Standup (2026)]"] {
+            assert!(summary.contains(title), "{} in {}", title, summary);
+        }
+        assert!(stats.duplicate_titles.is_empty());
+        // Without a disambiguator the titles stay and are reported
+        let (summary, stats) = build(&notes);
+        assert_eq!(summary.matches("[Standup](").count(), 4);
+        assert_eq!(stats.duplicate_titles.len(), 2);
+    }
+}
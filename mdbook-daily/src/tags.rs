@@ -0,0 +1,111 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::generated::{remove_stale, write_if_changed};
+use crate::link::relative_link;
+use crate::summary::{escape_title, Options, ScanStats, SummaryEntry};
+
+/// Directory (relative to src) holding the per-tag pages
+pub fn tags_dir(options: &Options) -> PathBuf {
+    options.generated_dir.join("tags")
+}
+
+/// `name` lowercased with anything but letters and digits turned into
+/// dashes, for file names of generated pages
+pub fn slug(name: &str) -> String {
+    let slug: String = name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    slug.trim_matches('-').to_string()
+}
+
+/// Path of the page for one tag, relative to src
+pub fn tag_path(options: &Options, tag: &str) -> PathBuf {
+    tags_dir(options).join(format!("{}.md", slug(tag)))
+}
+
+/// Every tag of the listed entries, sorted
+pub fn tag_names(stats: &ScanStats) -> Vec<String> {
+    let mut tags: Vec<String> = stats.front_matter.values()
+        .flat_map(|front| front.tags.iter().cloned())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    tags.sort();
+    tags
+}
+
+/// A tagged entry: its title, src-relative path and status
+type Tagged<'a> = (&'a str, &'a Path, Option<&'a str>);
+
+/// Entries by tag in SUMMARY order
+fn tagged_entries<'a>(stats: &'a ScanStats, entries: &'a [SummaryEntry]) -> BTreeMap<String, Vec<Tagged<'a>>> {
+    let mut tagged: BTreeMap<String, Vec<Tagged>> = BTreeMap::new();
+    for entry in entries {
+        let Some(link) = &entry.path else { continue };
+        let Some(front) = stats.front_matter.get(link) else { continue };
+        for tag in &front.tags {
+            let relative = Path::new(link.strip_prefix("./").unwrap_or(link));
+            tagged.entry(tag.clone()).or_default().push((&entry.title, relative, front.status.as_deref()));
+        }
+    }
+    tagged
+}
+
+/// The list item linking to a tagged entry from `page_dir`, with its
+/// status as a badge after the link
+fn tagged_item(page_dir: &Path, (title, path, status): &Tagged) -> String {
+    let badge = status.map(|status| format!(" `{}`", status)).unwrap_or_default();
+    format!("- [{}]({}){}", escape_title(title), relative_link(page_dir, path), badge)
+}
+
+/// Write the tag index: every tag with the entries carrying it. With
+/// per-tag pages, each tag heading links to its page. `page` is relative
+/// to src. Returns the number of tags.
+pub fn write_tag_index(
+    options: &Options,
+    stats: &ScanStats,
+    entries: &[SummaryEntry],
+    page: &Path,
+    out: &mut dyn Write,
+) -> io::Result<usize> {
+    let page_dir = page.parent().unwrap_or(Path::new(""));
+    let tagged = tagged_entries(stats, entries);
+    writeln!(out, "# {}", options.strings.tags)?;
+    for (tag, tag_entries) in &tagged {
+        writeln!(out)?;
+        if options.tag_pages {
+            writeln!(out, "## [{}]({})", escape_title(tag), relative_link(page_dir, &tag_path(options, tag)))?;
+        } else {
+            writeln!(out, "## {}", tag)?;
+        }
+        writeln!(out)?;
+        for entry in tag_entries {
+            writeln!(out, "{}", tagged_item(page_dir, entry))?;
+        }
+    }
+    Ok(tagged.len())
+}
+
+/// Write a page per tag listing its entries, removing pages of tags that
+/// are no longer used. Returns the number of pages.
+pub fn write_tag_pages(src_path: &Path, options: &Options, stats: &ScanStats, entries: &[SummaryEntry]) -> io::Result<usize> {
+    let tagged = tagged_entries(stats, entries);
+    let mut keep = HashSet::new();
+    for (tag, tag_entries) in &tagged {
+        let relative = tag_path(options, tag);
+        let page_dir = relative.parent().unwrap_or(Path::new(""));
+        let mut content = format!("# {}\n\n", tag);
+        for entry in tag_entries {
+            content.push_str(&tagged_item(page_dir, entry));
+            content.push('\n');
+        }
+        let path = src_path.join(&relative);
+        write_if_changed(&path, content.as_bytes(), options.line_ending)?;
+        keep.insert(path);
+    }
+    remove_stale(&src_path.join(tags_dir(options)), &keep)?;
+    Ok(tagged.len())
+}
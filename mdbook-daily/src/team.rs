@@ -0,0 +1,70 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::date::Date;
+use crate::generated;
+use crate::link::relative_link;
+use crate::summary::{escape_title, section_title, Options, ScanStats};
+
+/// Directory (relative to src) holding the per-date digest pages
+pub fn digest_dir(options: &Options) -> PathBuf {
+    options.generated_dir.join("daily")
+}
+
+/// Path of the digest page for one date, relative to src
+pub fn digest_path(options: &Options, date: Date) -> PathBuf {
+    digest_dir(options).join(format!("{}.md", date))
+}
+
+/// Distinct dates of the listed entries, oldest first
+pub fn digest_dates(stats: &ScanStats) -> Vec<Date> {
+    let mut dates: Vec<Date> = stats.dated.iter().map(|(date, _, _)| *date).collect();
+    dates.sort();
+    dates.dedup();
+    dates
+}
+
+/// Write one date's entries grouped by section (one section per person
+/// in a team journal), linking relative to `page_dir`
+pub fn write_day_digest(
+    date: Date,
+    stats: &ScanStats,
+    options: &Options,
+    page_dir: &Path,
+    out: &mut dyn Write,
+) -> io::Result<usize> {
+    let mut day: Vec<_> = stats.dated.iter().filter(|(d, _, _)| *d == date).collect();
+    day.sort_by(|a, b| a.2.cmp(&b.2));
+
+    writeln!(out, "# {}", date)?;
+    let mut section = None;
+    for (_, title, link) in &day {
+        let path = Path::new(link.trim_start_matches("./"));
+        let dir = path.components().next().map(|c| c.as_os_str().to_string_lossy().into_owned());
+        if dir != section {
+            writeln!(out)?;
+            writeln!(out, "## {}", section_title(options, dir.as_deref().unwrap_or_default()))?;
+            writeln!(out)?;
+            section = dir;
+        }
+        writeln!(out, "- [{}]({})", escape_title(title), relative_link(page_dir, path))?;
+    }
+    Ok(day.len())
+}
+
+/// Write a digest page for every date with listed entries
+pub fn write_digest_pages(src_path: &Path, options: &Options, stats: &ScanStats) -> io::Result<usize> {
+    let dir = digest_dir(options);
+    let dates = digest_dates(stats);
+    let mut keep = HashSet::new();
+    for &date in &dates {
+        let path = src_path.join(digest_path(options, date));
+        let mut content = Vec::new();
+        write_day_digest(date, stats, options, &dir, &mut content)?;
+        generated::write_if_changed(&path, &content, options.line_ending)?;
+        keep.insert(path);
+    }
+    generated::remove_stale(&src_path.join(&dir), &keep)?;
+    Ok(dates.len())
+}
@@ -0,0 +1,41 @@
+use std::time::{Duration, Instant};
+
+use crate::summary::ScanStats;
+
+/// Wall-clock time spent in each phase of a run
+pub struct Timings {
+    start: Instant,
+    last: Instant,
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl Default for Timings {
+    fn default() -> Timings {
+        Timings::new()
+    }
+}
+
+impl Timings {
+    pub fn new() -> Timings {
+        let now = Instant::now();
+        Timings { start: now, last: now, phases: Vec::new() }
+    }
+
+    /// Record the time since the previous phase ended
+    pub fn phase(&mut self, name: &'static str) {
+        let now = Instant::now();
+        self.phases.push((name, now - self.last));
+        self.last = now;
+    }
+
+    /// Print the collected metrics to stderr
+    pub fn report(&self, stats: &ScanStats) {
+        eprintln!("Directories scanned: {}", stats.dirs);
+        eprintln!("Files scanned:       {}", stats.files);
+        eprintln!("Files from cache:    {}", stats.heads.hits);
+        for (name, duration) in &self.phases {
+            eprintln!("{:<20} {:>8.2} ms", format!("{}:", name), duration.as_secs_f64() * 1000.0);
+        }
+        eprintln!("{:<20} {:>8.2} ms", "total:", self.start.elapsed().as_secs_f64() * 1000.0);
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+use crate::date::date_from_path;
+use crate::summary::{capitalize_first, split_camel_case};
+
+/// How the letters of a derived title are cased
+#[derive(Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TitleCase {
+    /// Capitalize the first letter only
+    First,
+    /// Capitalize the first letter of every word
+    Words,
+    /// Keep the name's own case, the default
+    None,
+}
+
+/// Steps turning a file or directory name into a title, set in the
+/// `title-transform` table. camelCase is always split into words.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct TitleTransform {
+    /// Turn `-` and `_` into spaces
+    pub separators: bool,
+    pub case: TitleCase,
+    /// Words always written as listed, whatever their case in the name,
+    /// e.g. `API` or `macOS`
+    pub acronyms: Vec<String>,
+    /// Transform date-style names such as `2024-01-05` or `0105` too
+    pub dates: bool,
+}
+
+impl Default for TitleTransform {
+    fn default() -> TitleTransform {
+        TitleTransform { separators: false, case: TitleCase::None, acronyms: Vec::new(), dates: false }
+    }
+}
+
+impl TitleTransform {
+    /// Title for a name, e.g. `rust-async-api` -> `Rust Async API` with
+    /// separators, word case and `API` listed
+    pub fn apply(&self, name: &str) -> String {
+        if !self.dates && (date_from_path(name).is_some() || name.bytes().all(|b| b.is_ascii_digit())) {
+            return name.to_string();
+        }
+        let mut title = split_camel_case(name);
+        if self.separators {
+            title = title.split(['-', '_']).filter(|w| !w.is_empty()).collect::<Vec<_>>().join(" ");
+        }
+        title = match self.case {
+            TitleCase::First => capitalize_first(&title),
+            TitleCase::Words => title.split(' ').map(capitalize_first).collect::<Vec<_>>().join(" "),
+            TitleCase::None => title,
+        };
+        if self.acronyms.is_empty() {
+            return title;
+        }
+        title.split(' ')
+            .map(|word| {
+                self.acronyms.iter()
+                    .find(|acronym| acronym.eq_ignore_ascii_case(word))
+                    .map_or(word, String::as_str)
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_default_keeps_the_name_s_case() {
+        let default = TitleTransform::default();
+        assert_eq!(default.apply("auth-4.0"), "auth-4.0");
+        assert_eq!(default.apply("notes"), "notes");
+        assert_eq!(default.apply("aboutMe"), "about Me");
+        let words = TitleTransform { separators: true, case: TitleCase::Words, acronyms: vec!["API".to_string()], dates: false };
+        assert_eq!(words.apply("rust-async-api"), "Rust Async API");
+    }
+}
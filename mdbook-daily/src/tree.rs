@@ -0,0 +1,286 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::date::Date;
+use crate::render;
+use crate::summary::{self, parse_entry, ScanStats};
+
+/// Version of the [`BookTree`] and [`Change`] models, written into every
+/// tree. It goes up when a field is renamed or removed or changes
+/// meaning; new fields that may be absent keep it.
+pub const MODEL_VERSION: u32 = 1;
+
+/// A chapter of the generated summary, with the date and tags of its
+/// entry once [`BookTree::annotated`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Chapter {
+    pub title: String,
+    /// Source file relative to src, `/`-separated; `None` for a draft
+    /// without a page
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<Date>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    pub children: Vec<Chapter>,
+}
+
+/// A `# Title` section of the summary and the chapters under it
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Part {
+    pub title: String,
+    pub chapters: Vec<Chapter>,
+}
+
+/// The structure of a generated SUMMARY.md: the data model `tree` prints
+/// and library users read, kept stable under [`MODEL_VERSION`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BookTree {
+    pub version: u32,
+    /// Chapters before the first part, such as the pinned ones
+    pub prefix: Vec<Chapter>,
+    #[serde(rename = "sections")]
+    pub parts: Vec<Part>,
+    /// Unnumbered chapters after the last part
+    pub suffix: Vec<Chapter>,
+}
+
+impl Default for BookTree {
+    fn default() -> BookTree {
+        BookTree { version: MODEL_VERSION, prefix: Vec::new(), parts: Vec::new(), suffix: Vec::new() }
+    }
+}
+
+impl BookTree {
+    /// Read the chapters and parts back out of SUMMARY.md content.
+    /// Nesting follows the indentation of the list items. Dates and tags
+    /// are not part of the summary; see [`BookTree::annotated`].
+    pub fn parse(summary: &str) -> BookTree {
+        let mut tree = BookTree::default();
+        // Indentation and chapter of each open level, outermost first
+        let mut open: Vec<(usize, Chapter)> = Vec::new();
+        for (i, line) in summary.lines().enumerate() {
+            if let Some(title) = line.strip_prefix("# ") {
+                tree.close(&mut open, 0);
+                // A heading on the first line is the summary's own title
+                if i > 0 {
+                    tree.parts.push(Part { title: title.trim().to_string(), chapters: Vec::new() });
+                }
+                continue;
+            }
+            let Some(entry) = parse_entry(i, line) else { continue };
+            tree.close(&mut open, entry.indent.len());
+            let path = entry.path.map(|p| p.strip_prefix("./").unwrap_or(&p).replace('\\', "/"));
+            let chapter = Chapter { title: entry.title, path, date: None, tags: Vec::new(), children: Vec::new() };
+            if !entry.numbered && !tree.parts.is_empty() {
+                tree.suffix.push(chapter);
+                continue;
+            }
+            open.push((entry.indent.len(), chapter));
+        }
+        tree.close(&mut open, 0);
+        tree
+    }
+
+    /// The tree with the dates and tags `stats` collected while
+    /// generating its summary
+    pub fn annotated(mut self, stats: &ScanStats) -> BookTree {
+        let dates: HashMap<&str, Date> = stats.dated.iter().map(|(date, _, link)| (link.as_str(), *date)).collect();
+        fn annotate(chapters: &mut [Chapter], dates: &HashMap<&str, Date>, stats: &ScanStats) {
+            for chapter in chapters {
+                let link = chapter.path.as_ref().map(|p| format!("./{}", p));
+                let link = link.as_deref().unwrap_or_default();
+                chapter.date = dates.get(link).copied();
+                chapter.tags = stats.front_matter.get(link).map(|front| front.tags.clone()).unwrap_or_default();
+                annotate(&mut chapter.children, dates, stats);
+            }
+        }
+        annotate(&mut self.prefix, &dates, stats);
+        for part in &mut self.parts {
+            annotate(&mut part.chapters, &dates, stats);
+        }
+        annotate(&mut self.suffix, &dates, stats);
+        self
+    }
+
+    /// Attach the open chapters indented at least `indent` to their
+    /// parents, or to the book when they are outermost
+    fn close(&mut self, open: &mut Vec<(usize, Chapter)>, indent: usize) {
+        while open.last().is_some_and(|(level, _)| *level >= indent) {
+            let Some((_, chapter)) = open.pop() else { break };
+            match open.last_mut() {
+                Some((_, parent)) => parent.children.push(chapter),
+                None => match self.parts.last_mut() {
+                    Some(part) => part.chapters.push(chapter),
+                    None => self.prefix.push(chapter),
+                },
+            }
+        }
+    }
+
+    /// Every chapter in summary order, parents before their children
+    pub fn chapters(&self) -> Vec<&Chapter> {
+        fn walk<'a>(chapters: &'a [Chapter], out: &mut Vec<&'a Chapter>) {
+            for chapter in chapters {
+                out.push(chapter);
+                walk(&chapter.children, out);
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.prefix, &mut out);
+        for part in &self.parts {
+            walk(&part.chapters, &mut out);
+        }
+        walk(&self.suffix, &mut out);
+        out
+    }
+}
+
+/// How a chapter differs between two [`BookTree`]s, from
+/// [`BookTree::diff`]. Chapters are matched by source path; drafts
+/// without one are not.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Change {
+    Added { path: String, title: String },
+    Removed { path: String, title: String },
+    /// Listed under other parents: part titles and parent chapter
+    /// paths, outermost first
+    Moved { path: String, from: Vec<String>, to: Vec<String> },
+    Retitled { path: String, from: String, to: String },
+}
+
+/// A chapter with a page, where the tree lists it
+struct Listed<'a> {
+    title: &'a str,
+    parents: Vec<String>,
+}
+
+impl BookTree {
+    /// What changed from `self` to `other`: removed chapters in the order
+    /// of `self`, then added, moved and retitled ones in the order of
+    /// `other`. A chapter both moved and retitled gives a change of each.
+    pub fn diff(&self, other: &BookTree) -> Vec<Change> {
+        let (old, old_order) = self.listed();
+        let (new, new_order) = other.listed();
+        let mut changes: Vec<Change> = old_order.iter()
+            .filter(|path| !new.contains_key(*path))
+            .map(|path| Change::Removed { path: path.to_string(), title: old[path].title.to_string() })
+            .collect();
+        for path in new_order {
+            let now = &new[path];
+            let Some(before) = old.get(path) else {
+                changes.push(Change::Added { path: path.to_string(), title: now.title.to_string() });
+                continue;
+            };
+            if before.parents != now.parents {
+                changes.push(Change::Moved { path: path.to_string(), from: before.parents.clone(), to: now.parents.clone() });
+            }
+            if before.title != now.title {
+                changes.push(Change::Retitled { path: path.to_string(), from: before.title.to_string(), to: now.title.to_string() });
+            }
+        }
+        changes
+    }
+
+    /// Every chapter with a page by path, and the paths in summary
+    /// order. A path listed twice counts where it is first listed.
+    fn listed(&self) -> (HashMap<&str, Listed<'_>>, Vec<&str>) {
+        fn walk<'a>(chapters: &'a [Chapter], parents: &mut Vec<String>, listed: &mut HashMap<&'a str, Listed<'a>>, order: &mut Vec<&'a str>) {
+            for chapter in chapters {
+                if let Some(path) = chapter.path.as_deref().filter(|path| !listed.contains_key(path)) {
+                    listed.insert(path, Listed { title: &chapter.title, parents: parents.clone() });
+                    order.push(path);
+                }
+                parents.push(chapter.path.clone().unwrap_or_else(|| chapter.title.clone()));
+                walk(&chapter.children, parents, listed, order);
+                parents.pop();
+            }
+        }
+        let (mut listed, mut order) = (HashMap::new(), Vec::new());
+        walk(&self.prefix, &mut Vec::new(), &mut listed, &mut order);
+        for part in &self.parts {
+            walk(&part.chapters, &mut vec![part.title.clone()], &mut listed, &mut order);
+        }
+        walk(&self.suffix, &mut Vec::new(), &mut listed, &mut order);
+        (listed, order)
+    }
+}
+
+/// `mdbook-daily tree [--format json|yaml|summary|mkdocs]`: print the
+/// structure the current tree would give SUMMARY.md, with the title,
+/// source path, date and tags of every chapter, for tools that should not
+/// parse the summary
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let format = match args {
+        [] => "json",
+        [flag, format] if flag == "--format" => format.as_str(),
+        _ => return Err(crate::usage_error("tree only accepts --format json|yaml|summary|mkdocs")),
+    };
+    let renderer = render::renderer(format)
+        .ok_or_else(|| crate::usage_error(&format!("unknown tree format {:?}", format)))?;
+    let options = config::book_options(Path::new("."))?;
+    let mut stats = ScanStats::default();
+    let summary = summary::generate_summary(src_path, &options, &mut stats)?;
+    let tree = BookTree::parse(&summary).annotated(&stats);
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+    renderer.render(&tree, &mut out)?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::SummaryBuilder;
+
+    fn entry(title: &str, path: &str, children: Vec<Chapter>) -> Chapter {
+        Chapter { title: title.to_string(), path: Some(path.to_string()), date: None, tags: Vec::new(), children }
+    }
+
+    fn book(chapters: Vec<Chapter>) -> BookTree {
+        BookTree { parts: vec![Part { title: "Journal".to_string(), chapters }], ..BookTree::default() }
+    }
+
+    #[test]
+    fn diff_reports_each_kind_of_change() {
+        let old = book(vec![
+            entry("2025", "2025/README.md", vec![entry("Old title", "a.md", vec![]), entry("Gone", "b.md", vec![])]),
+            entry("2026", "2026/README.md", vec![]),
+        ]);
+        let new = book(vec![
+            entry("2025", "2025/README.md", vec![]),
+            entry("2026", "2026/README.md", vec![entry("New title", "a.md", vec![]), entry("Fresh", "c.md", vec![])]),
+        ]);
+        let parents = |year: &str| vec!["Journal".to_string(), format!("{}/README.md", year)];
+        assert_eq!(old.diff(&new), [
+            Change::Removed { path: "b.md".to_string(), title: "Gone".to_string() },
+            Change::Moved { path: "a.md".to_string(), from: parents("2025"), to: parents("2026") },
+            Change::Retitled { path: "a.md".to_string(), from: "Old title".to_string(), to: "New title".to_string() },
+            Change::Added { path: "c.md".to_string(), title: "Fresh".to_string() },
+        ]);
+        assert!(new.diff(&new).is_empty());
+    }
+
+    #[test]
+    fn book_round_trips_through_json() {
+        let builder = SummaryBuilder::from_files([
+            ("journal/2026-01-15.md", "---\ntags: [rust]\n---\n# Started"),
+            ("journal/2026-01-16.md", "# Kept going"),
+        ])
+        .unwrap();
+        let book = builder.tree().unwrap();
+        assert_eq!(book.version, MODEL_VERSION);
+        let entry = &book.parts[0].chapters[0];
+        assert_eq!(entry.date, Date::new(2026, 1, 15));
+        assert_eq!(entry.tags, ["rust"]);
+        let json = serde_json::to_string(&book).unwrap();
+        assert!(json.contains("\"date\":\"2026-01-15\""), "{}", json);
+        assert!(json.contains("\"sections\":["), "{}", json);
+        assert_eq!(serde_json::from_str::<BookTree>(&json).unwrap(), book);
+    }
+}
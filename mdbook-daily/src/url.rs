@@ -0,0 +1,24 @@
+/// Absolute URL of the page mdBook builds from a chapter source path.
+///
+/// Follows mdBook's HTML output layout: `.md` becomes `.html` and a
+/// `README.md` chapter is rendered as its directory's `index.html`.
+/// Characters that are not safe in a URL path are percent-encoded.
+pub fn page_url(site_url: &str, link: &str) -> String {
+    let path = link.trim_start_matches("./");
+    let page = match path.strip_suffix("README.md") {
+        Some(dir) if dir.is_empty() || dir.ends_with('/') => format!("{}index.html", dir),
+        _ => format!("{}.html", path.strip_suffix(".md").unwrap_or(path)),
+    };
+    format!("{}/{}", site_url.trim_end_matches('/'), encode_path(&page))
+}
+
+fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,25 @@
+use std::fs;
+use std::path::Path;
+
+use mdbook::book::{self, BookItem};
+use mdbook::config::Config;
+
+/// Parse SUMMARY.md with mdBook's own parser and load every chapter it
+/// lists, as `mdbook build` would but without creating missing files.
+/// `root` holds book.toml. Returns the number of chapters loaded.
+pub fn validate_book(root: &Path, src_path: &Path) -> Result<usize, String> {
+    let summary_path = src_path.join("SUMMARY.md");
+    let summary = fs::read_to_string(&summary_path).map_err(|e| format!("{}: {}", summary_path.display(), e))?;
+    book::parse_summary(&summary).map_err(|e| format!("{}: {:#}", summary_path.display(), e))?;
+
+    let book_toml = root.join("book.toml");
+    let mut config = if book_toml.exists() {
+        Config::from_disk(&book_toml).map_err(|e| format!("{}: {:#}", book_toml.display(), e))?
+    } else {
+        Config::default()
+    };
+    // A missing chapter is a problem to report, not a file to create
+    config.build.create_missing = false;
+    let book = book::load_book(src_path, &config.build).map_err(|e| format!("{:#}", e))?;
+    Ok(book.iter().filter(|item| matches!(item, BookItem::Chapter(_))).count())
+}
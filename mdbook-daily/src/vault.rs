@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use toml::value::Value;
+
+use crate::config;
+use crate::date::{date_from_path, Date};
+use crate::frontmatter;
+use crate::link::relative_link;
+use crate::links::{markdown_files, markdown_links, resolve_link};
+use crate::summary::ScanStats;
+use crate::wikilinks::{resolve_wiki_links, Unresolved, WikiIndex};
+
+/// Section the daily notes of a vault go into unless `--section` says
+const DEFAULT_SECTION: &str = "daily";
+
+/// Directory under src the attachments are copied into unless `--assets`
+/// says otherwise
+const DEFAULT_ASSETS: &str = "assets";
+
+/// Note-taking apps whose vaults can be imported
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum App {
+    /// Daily notes named `YYYY-MM-DD.md` anywhere in the vault, YAML
+    /// front matter and attachments linked by file name
+    Obsidian,
+    /// Journals in `journals/YYYY_MM_DD.md`, `key:: value` page
+    /// properties and attachments in `assets/`
+    Logseq,
+}
+
+/// One daily note of the vault and where it goes
+struct Note {
+    source: PathBuf,
+    date: Date,
+    /// Destination relative to src
+    target: PathBuf,
+}
+
+/// The date of a daily note at `relative` (to the vault), if it is one
+fn daily_date(app: App, relative: &Path) -> Option<Date> {
+    let stem = relative.file_stem()?.to_string_lossy();
+    match app {
+        App::Obsidian => date_from_path(&stem).filter(|_| stem.len() == 10),
+        App::Logseq if relative.starts_with("journals") => date_from_path(&stem.replace('_', "-")).filter(|_| stem.len() == 10),
+        App::Logseq => None,
+    }
+}
+
+/// Names a wiki-link may use for a daily note: its file stem, its path in
+/// the vault and, for Logseq, the `Jan 5th, 2024` journal title
+fn link_names(app: App, relative: &Path, date: Date) -> Vec<String> {
+    let mut names = vec![
+        relative.file_stem().unwrap_or_default().to_string_lossy().into_owned(),
+        relative.with_extension("").to_string_lossy().replace('\\', "/"),
+        date.to_string(),
+    ];
+    if app == App::Logseq {
+        let suffix = match date.day {
+            1 | 21 | 31 => "st",
+            2 | 22 => "nd",
+            3 | 23 => "rd",
+            _ => "th",
+        };
+        names.push(format!("{} {}{}, {}", &date.month_name()[..3], date.day, suffix, date.year));
+    }
+    names
+}
+
+/// Logseq page properties (`key:: value` lines before the first block)
+/// as YAML front matter. `tags` and `alias` values become lists with the
+/// `[[...]]` around page names removed.
+fn logseq_properties(content: &str) -> String {
+    let mut keys: Vec<(String, Value)> = Vec::new();
+    let mut body = content;
+    for line in content.split_inclusive('\n') {
+        let Some((key, value)) = line.trim().split_once(":: ") else { break };
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            break;
+        }
+        let value = value.trim();
+        let value = match key {
+            "tags" | "alias" => Value::Array(
+                value.split(',')
+                    .map(|v| v.trim().trim_start_matches("[[").trim_end_matches("]]").trim_start_matches('#'))
+                    .filter(|v| !v.is_empty())
+                    .map(|v| Value::String(v.to_string()))
+                    .collect(),
+            ),
+            _ => Value::String(value.to_string()),
+        };
+        keys.push((key.to_string(), value));
+        body = &body[line.len()..];
+    }
+    if keys.is_empty() {
+        return content.to_string();
+    }
+    let keys: Vec<(&str, Value)> = keys.iter().map(|(k, v)| (k.as_str(), v.clone())).collect();
+    frontmatter::set_keys(body.trim_start_matches('\n'), &keys)
+}
+
+/// Obsidian front matter with `#` taken off the tags, which the tag
+/// pages would otherwise keep in their names
+fn obsidian_front_matter(content: &str) -> String {
+    let Some(front) = frontmatter::split(content).0 else { return content.to_string() };
+    if !front.tags.iter().any(|t| t.starts_with('#')) {
+        return content.to_string();
+    }
+    let tags = front.tags.iter().map(|t| Value::String(t.trim_start_matches('#').to_string())).collect();
+    frontmatter::set_keys(content, &[("tags", Value::Array(tags))])
+}
+
+/// Attachments in the vault by file name, for Obsidian's `![[name.png]]`
+/// embeds. The first file of a name wins.
+fn attachments_by_name(vault: &Path) -> HashMap<String, PathBuf> {
+    let mut files = HashMap::new();
+    let mut pending = vec![vault.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if path.is_dir() && !hidden {
+                pending.push(path);
+            } else if path.is_file() && path.extension().is_some_and(|e| e != "md") {
+                files.entry(entry.file_name().to_string_lossy().into_owned()).or_insert(path);
+            }
+        }
+    }
+    files
+}
+
+/// Copies attachments into the assets directory, once per source file
+struct Assets<'a> {
+    src_path: &'a Path,
+    /// Assets directory relative to src
+    dir: PathBuf,
+    copied: HashMap<PathBuf, PathBuf>,
+    dry_run: bool,
+}
+
+impl Assets<'_> {
+    /// The path (relative to src) `source` is copied to, keeping its file
+    /// name unless another attachment already took it
+    fn copy(&mut self, source: &Path) -> io::Result<PathBuf> {
+        if let Some(target) = self.copied.get(source) {
+            return Ok(target.clone());
+        }
+        let name = source.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let (stem, extension) = name.rsplit_once('.').unwrap_or((&name, ""));
+        let taken = |target: &PathBuf| self.copied.values().any(|t| t == target) || self.src_path.join(target).exists();
+        let mut target = self.dir.join(&name);
+        let mut n = 1;
+        while taken(&target) && fs::read(self.src_path.join(&target)).ok() != fs::read(source).ok() {
+            n += 1;
+            target = self.dir.join(format!("{}-{}.{}", stem, n, extension));
+        }
+        if !self.dry_run {
+            fs::create_dir_all(self.src_path.join(&self.dir))?;
+            fs::copy(source, self.src_path.join(&target))?;
+        }
+        self.copied.insert(source.to_path_buf(), target.clone());
+        Ok(target)
+    }
+}
+
+/// `note`'s content with embedded attachments copied and relinked, and
+/// links to the other imported notes turned into markdown links
+fn convert(
+    app: App,
+    vault: &Path,
+    note: &Note,
+    index: &WikiIndex,
+    by_name: &HashMap<String, PathBuf>,
+    assets: &mut Assets,
+) -> io::Result<(String, Vec<String>)> {
+    let content = fs::read_to_string(&note.source)?;
+    let mut content = match app {
+        App::Obsidian => obsidian_front_matter(&content),
+        App::Logseq => logseq_properties(&content),
+    };
+    let dir = note.target.parent().unwrap_or(Path::new(""));
+
+    // `![[name.png]]` embeds of attachments become images
+    let mut rest = content.as_str();
+    let mut embedded = String::with_capacity(content.len());
+    while let Some(start) = rest.find("![[") {
+        let Some(len) = rest[start + 3..].find("]]") else { break };
+        let inner = &rest[start + 3..start + 3 + len];
+        let name = inner.split('|').next().unwrap_or(inner).trim();
+        embedded.push_str(&rest[..start]);
+        match by_name.get(name) {
+            Some(source) => {
+                let target = assets.copy(source)?;
+                embedded.push_str(&format!("![{}]({})", name, relative_link(dir, &target).replace(' ', "%20")));
+            }
+            None => embedded.push_str(&rest[start..start + 3 + len + 2]),
+        }
+        rest = &rest[start + 3 + len + 2..];
+    }
+    embedded.push_str(rest);
+    content = embedded;
+
+    // Links to files next to the note or in the vault's attachment folders
+    for link in markdown_links(&content) {
+        let decoded = link.replace("%20", " ");
+        let relative = resolve_link(note.source.strip_prefix(vault).unwrap_or(&note.source), &decoded);
+        let source = vault.join(&relative);
+        if relative.starts_with("..") || !source.is_file() || source.extension().is_some_and(|e| e == "md") {
+            continue;
+        }
+        let target = assets.copy(&source)?;
+        let relinked = relative_link(dir, &target).replace(' ', "%20");
+        content = content.replace(&format!("]({}", link), &format!("]({}", relinked));
+    }
+
+    Ok(resolve_wiki_links(&content, &note.target, index, Unresolved::Keep))
+}
+
+/// `mdbook-daily import --from obsidian|logseq VAULT [--section NAME]
+/// [--assets DIR] [--dry-run]`: copy the daily notes of an Obsidian vault
+/// or a Logseq graph into src as `SECTION/YYYY/MMDD.md`, turning their
+/// front matter or page properties into this book's front matter, their
+/// wiki-links between daily notes into markdown links and their embedded
+/// attachments into copies under `src/DIR`, then regenerate SUMMARY.md.
+/// Links to pages that are not daily notes are left as wiki-links.
+/// `--dry-run` prints the plan without writing anything.
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut app = None;
+    let mut vault = None;
+    let mut section = DEFAULT_SECTION.to_string();
+    let mut assets_dir = PathBuf::from(DEFAULT_ASSETS);
+    let mut dry_run = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--from needs obsidian or logseq"))?;
+                app = Some(match value.as_str() {
+                    "obsidian" => App::Obsidian,
+                    "logseq" => App::Logseq,
+                    _ => return Err(crate::usage_error(&format!("cannot import from {:?}, expected obsidian or logseq", value))),
+                });
+            }
+            "--section" => {
+                section = args.next().ok_or_else(|| crate::usage_error("--section needs a name"))?.clone();
+            }
+            "--assets" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--assets needs a directory"))?;
+                assets_dir = PathBuf::from(value);
+            }
+            "--dry-run" => dry_run = true,
+            _ if arg.starts_with("--") => return Err(crate::usage_error(&format!("unknown import argument {:?}", arg))),
+            _ if vault.is_some() => return Err(crate::usage_error("import takes one vault")),
+            _ => vault = Some(PathBuf::from(arg)),
+        }
+    }
+    let Some(app) = app else { return Err(crate::usage_error("import needs --from obsidian or --from logseq")) };
+    let Some(vault) = vault else { return Err(crate::usage_error("import needs the path of a vault")) };
+    if !vault.is_dir() {
+        return Err(crate::usage_error(&format!("{:?} is not a directory", vault)));
+    }
+
+    let mut notes = Vec::new();
+    let mut names = Vec::new();
+    let mut conflicts = Vec::new();
+    for source in markdown_files(&vault) {
+        let relative = source.strip_prefix(&vault).unwrap_or(&source).to_path_buf();
+        if relative.components().any(|c| c.as_os_str().to_string_lossy().starts_with('.')) {
+            continue;
+        }
+        let Some(date) = daily_date(app, &relative) else { continue };
+        let target = Path::new(&section).join(date.year.to_string()).join(format!("{:02}{:02}.md", date.month, date.day));
+        if src_path.join(&target).exists() || notes.iter().any(|n: &Note| n.target == target) {
+            conflicts.push(source);
+            continue;
+        }
+        names.extend(link_names(app, &relative, date).into_iter().map(|name| (name, target.clone())));
+        notes.push(Note { source, date, target });
+    }
+    if notes.is_empty() {
+        println!("No daily notes found in {:?}", vault);
+        return Ok(());
+    }
+    notes.sort_by_key(|note| note.date);
+
+    let index = WikiIndex::from_names(names);
+    let by_name = if app == App::Obsidian { attachments_by_name(&vault) } else { HashMap::new() };
+    let mut assets = Assets { src_path, dir: assets_dir, copied: HashMap::new(), dry_run };
+    let mut unresolved = 0;
+    for note in &notes {
+        let (content, missing) = convert(app, &vault, note, &index, &by_name, &mut assets)?;
+        unresolved += missing.len();
+        let from = note.source.strip_prefix(&vault).unwrap_or(&note.source);
+        println!("  {} -> {}", from.display(), note.target.display());
+        if dry_run {
+            continue;
+        }
+        let destination = src_path.join(&note.target);
+        let parent = destination.parent().unwrap_or(src_path);
+        fs::create_dir_all(parent)?;
+        // Without a README a year directory is only a draft chapter
+        let readme = parent.join("README.md");
+        if !readme.exists() {
+            fs::write(&readme, format!("# {}\n", note.date.year))?;
+        }
+        fs::write(&destination, content)?;
+    }
+    for source in &conflicts {
+        eprintln!("Warning: {} would overwrite an existing note, leaving it out", source.display());
+    }
+    if unresolved > 0 {
+        println!("{} wiki-links point at pages that are not daily notes and were left as they are", unresolved);
+    }
+    if dry_run {
+        println!("Would import {} notes and {} attachments", notes.len(), assets.copied.len());
+        return Ok(());
+    }
+
+    let mut stats = ScanStats::default();
+    crate::write_summary(src_path, &config::book_options(Path::new("."))?, &mut stats)?;
+    stats.print_warnings();
+    println!(
+        "Imported {} notes and {} attachments, updated {:?}",
+        notes.len(),
+        assets.copied.len(),
+        src_path.join("SUMMARY.md")
+    );
+    Ok(())
+}
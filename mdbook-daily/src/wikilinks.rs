@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use mdbook::book::{Book, BookItem};
+use mdbook::utils::normalize_id;
+
+use crate::links::chapter_link;
+use crate::summary::ScanStats;
+
+/// What happens to a wiki-link whose page is not in the book
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Unresolved {
+    /// Leave the `[[...]]` text as written
+    Keep,
+    /// Replace it with its display text
+    Strip,
+    /// Leave it as written and print a warning
+    Warn,
+}
+
+impl Unresolved {
+    pub fn parse(value: &str) -> Option<Unresolved> {
+        match value {
+            "keep" => Some(Unresolved::Keep),
+            "strip" => Some(Unresolved::Strip),
+            "warn" => Some(Unresolved::Warn),
+            _ => None,
+        }
+    }
+}
+
+/// Chapters by the names a wiki-link may use for them, lowercased: the
+/// path relative to src without `.md`, the entry date, the file stem (or
+/// directory name for a README) and the chapter title, in that order of
+/// preference. The first chapter in the book wins a name.
+pub struct WikiIndex {
+    names: HashMap<String, PathBuf>,
+}
+
+impl WikiIndex {
+    pub fn new(book: &Book, stats: &ScanStats) -> WikiIndex {
+        let chapters: Vec<(&str, &Path)> = book.iter()
+            .filter_map(|item| match item {
+                BookItem::Chapter(chapter) => Some((chapter.name.as_str(), chapter.source_path.as_deref()?)),
+                _ => None,
+            })
+            .collect();
+        let mut names = HashMap::new();
+        let mut add = |name: String, path: &Path| {
+            names.entry(name.to_lowercase()).or_insert_with(|| path.to_path_buf());
+        };
+        for (_, path) in &chapters {
+            add(path.with_extension("").to_string_lossy().replace('\\', "/"), path);
+        }
+        for (date, _, link) in &stats.dated {
+            add(date.to_string(), Path::new(link.trim_start_matches("./")));
+        }
+        for (_, path) in &chapters {
+            let name = match path.file_name() {
+                Some(name) if name == "README.md" => path.parent().and_then(Path::file_name),
+                _ => path.file_stem(),
+            };
+            let Some(name) = name else { continue };
+            add(name.to_string_lossy().into_owned(), path);
+        }
+        for (title, path) in &chapters {
+            add(title.to_string(), path);
+        }
+        WikiIndex { names }
+    }
+
+    /// An index over notes that are not in a book yet, such as the ones
+    /// being imported, by the names given for them
+    pub fn from_names(names: impl IntoIterator<Item = (String, PathBuf)>) -> WikiIndex {
+        let mut index = HashMap::new();
+        for (name, path) in names {
+            index.entry(name.to_lowercase()).or_insert(path);
+        }
+        WikiIndex { names: index }
+    }
+
+    fn resolve(&self, page: &str) -> Option<&Path> {
+        let page = page.trim();
+        let page = page.strip_suffix(".md").unwrap_or(page);
+        self.names.get(&page.to_lowercase()).map(PathBuf::as_path)
+    }
+}
+
+/// `[[page]]`, `[[page|text]]`, `[[page#heading]]` and `[[#heading]]`
+/// links in `content` of the chapter at `source` turned into relative
+/// markdown links, and the targets that did not resolve. Embeds
+/// (`![[...]]`), fenced code and inline code are left alone.
+pub fn resolve_wiki_links(content: &str, source: &Path, index: &WikiIndex, unresolved: Unresolved) -> (String, Vec<String>) {
+    let dir = source.parent().unwrap_or(Path::new(""));
+    let mut out = String::with_capacity(content.len());
+    let mut missing = Vec::new();
+    let mut in_fence = false;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if in_fence || !line.contains("[[") {
+            out.push_str(line);
+            continue;
+        }
+        // Odd pieces between backticks are inline code
+        for (i, piece) in line.split('`').enumerate() {
+            if i > 0 {
+                out.push('`');
+            }
+            if i % 2 == 1 {
+                out.push_str(piece);
+                continue;
+            }
+            let mut rest = piece;
+            while let Some(start) = rest.find("[[") {
+                let Some(len) = rest[start + 2..].find("]]") else { break };
+                let inner = &rest[start + 2..start + 2 + len];
+                let embed = rest[..start].ends_with('!');
+                out.push_str(&rest[..start]);
+                rest = &rest[start + 2 + len + 2..];
+                if embed || inner.is_empty() {
+                    out.push_str(&format!("[[{}]]", inner));
+                    continue;
+                }
+                let (target, text) = inner.split_once('|').unwrap_or((inner, inner));
+                let (page, heading) = target.split_once('#').unwrap_or((target, ""));
+                let anchor = if heading.is_empty() { String::new() } else { format!("#{}", normalize_id(heading)) };
+                let link = if page.trim().is_empty() {
+                    Some(anchor)
+                } else {
+                    index.resolve(page).map(|path| format!("{}{}", chapter_link(dir, path), anchor))
+                };
+                match link {
+                    Some(link) => out.push_str(&format!("[{}]({})", text.trim(), link)),
+                    None => {
+                        missing.push(target.trim().to_string());
+                        match unresolved {
+                            Unresolved::Strip => out.push_str(text.trim()),
+                            Unresolved::Keep | Unresolved::Warn => out.push_str(&format!("[[{}]]", inner)),
+                        }
+                    }
+                }
+            }
+            out.push_str(rest);
+        }
+    }
+    (out, missing)
+}
+
+/// Resolve the wiki-links of every chapter in `book`, warning about
+/// unresolved ones when asked to
+pub fn add_wiki_links(book: &mut Book, stats: &ScanStats, unresolved: Unresolved) {
+    let index = WikiIndex::new(book, stats);
+    book.for_each_mut(|item| {
+        let BookItem::Chapter(chapter) = item else { return };
+        let Some(source) = &chapter.source_path else { return };
+        if !chapter.content.contains("[[") {
+            return;
+        }
+        let (content, missing) = resolve_wiki_links(&chapter.content, source, &index, unresolved);
+        if unresolved == Unresolved::Warn {
+            for target in missing {
+                eprintln!("Warning: {}: unresolved wiki-link [[{}]]", source.display(), target);
+            }
+        }
+        chapter.content = content;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::Date;
+    use mdbook::book::Chapter;
+
+    fn index() -> WikiIndex {
+        let mut book = Book::new();
+        for (title, path) in [("Morning pages", "journal/2026/1014.md"), ("Rust notes", "notes/rust.md"), ("Projects", "notes/projects/README.md")] {
+            book.push_item(BookItem::Chapter(Chapter::new(title, String::new(), path, Vec::new())));
+        }
+        let stats = ScanStats {
+            dated: vec![(Date::new(2026, 10, 14).unwrap(), "Morning pages".into(), "./journal/2026/1014.md".into())],
+            ..ScanStats::default()
+        };
+        WikiIndex::new(&book, &stats)
+    }
+
+    #[test]
+    fn links_resolve_by_path_date_name_and_title() {
+        let content = "[[notes/rust]] [[2026-10-14|that day]] [[projects]] [[morning pages#First thing]] [[#Below]]\n";
+        let (content, missing) = resolve_wiki_links(content, Path::new("notes/rust.md"), &index(), Unresolved::Warn);
+        assert_eq!(content, "[notes/rust](./rust.md) [that day](../journal/2026/1014.md) [projects](./projects/index.md) \
+            [morning pages#First thing](../journal/2026/1014.md#first-thing) [#Below](#below)\n");
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn missing_targets_are_kept_or_stripped() {
+        let content = "[[Nowhere|somewhere]] `[[code]]` ![[embed.png]]\n```\n[[fenced]]\n```\n";
+        let (kept, missing) = resolve_wiki_links(content, Path::new("notes/rust.md"), &index(), Unresolved::Keep);
+        assert_eq!(kept, content);
+        assert_eq!(missing, ["Nowhere"]);
+        let (stripped, _) = resolve_wiki_links(content, Path::new("notes/rust.md"), &index(), Unresolved::Strip);
+        assert_eq!(stripped, content.replacen("[[Nowhere|somewhere]]", "somewhere", 1));
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::config::{self, Config};
+use crate::date::{Date, DateTime};
+use crate::link::relative_link;
+use crate::locale::Strings;
+use crate::new::{create_entry, pick_section, template};
+use crate::summary::{escape_title, ScanStats};
+
+/// Heading the sessions are listed under in a day's note
+const HEADING: &str = "## Sessions";
+
+/// A work session logged in a note as `- 09:15-09:40 label (25 min)`,
+/// or `- 09:15- label` while it is still running. Times are UTC.
+pub struct Session {
+    pub start: u32,
+    pub end: Option<u32>,
+    pub label: String,
+}
+
+impl Session {
+    pub fn minutes(&self) -> Option<u32> {
+        // A session past midnight ends on the next day
+        self.end.map(|end| (end + 24 * 60 - self.start) % (24 * 60))
+    }
+
+    fn line(&self) -> String {
+        match (self.end, self.minutes()) {
+            (Some(end), Some(minutes)) => {
+                format!("- {}-{} {} ({} min)", clock(self.start), clock(end), self.label, minutes)
+            }
+            _ => format!("- {}- {}", clock(self.start), self.label),
+        }
+    }
+}
+
+fn clock(minutes: u32) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+fn parse_clock(text: &str) -> Option<u32> {
+    let (hours, minutes) = text.split_once(':')?;
+    let (hours, minutes): (u32, u32) = (hours.parse().ok()?, minutes.parse().ok()?);
+    (hours < 24 && minutes < 60 && text.len() == 5).then_some(hours * 60 + minutes)
+}
+
+/// Parse a session line, ignoring any other list item
+pub fn parse_session(line: &str) -> Option<Session> {
+    let rest = line.strip_prefix("- ")?;
+    let (times, label) = rest.split_once(' ')?;
+    let (start, end) = times.split_once('-')?;
+    let start = parse_clock(start)?;
+    let end = if end.is_empty() { None } else { Some(parse_clock(end)?) };
+    let label = match end {
+        Some(_) => label.rsplit_once(" (").filter(|(_, m)| m.ends_with(" min)")).map_or(label, |(l, _)| l),
+        None => label,
+    };
+    Some(Session { start, end, label: label.trim().to_string() })
+}
+
+/// Sessions in a note's content, in order
+pub fn sessions(content: &str) -> Vec<Session> {
+    content.lines().filter_map(parse_session).collect()
+}
+
+/// Content with the session started or stopped at `now`. Stopping picks
+/// the latest running session, or the latest one with `label`.
+fn update(content: &str, start: bool, label: Option<&str>, now: u32) -> Result<String, String> {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let running = |s: &Session| s.end.is_none() && label.is_none_or(|l| s.label == l);
+    let open = lines.iter().rposition(|line| parse_session(line).is_some_and(|s| running(&s)));
+    if start {
+        let label = label.unwrap_or_default();
+        if open.is_some() {
+            return Err(format!("session {:?} is already running", label));
+        }
+        let session = Session { start: now, end: None, label: label.to_string() };
+        let heading = match lines.iter().position(|line| line == HEADING) {
+            Some(i) => i,
+            None => {
+                if lines.last().is_some_and(|l| !l.is_empty()) {
+                    lines.push(String::new());
+                }
+                lines.push(HEADING.to_string());
+                lines.len() - 1
+            }
+        };
+        // Append after the last session under the heading
+        let last = (heading + 1..lines.len())
+            .take_while(|&i| lines[i].is_empty() || parse_session(&lines[i]).is_some())
+            .filter(|&i| !lines[i].is_empty())
+            .last();
+        match last {
+            Some(i) => lines.insert(i + 1, session.line()),
+            None => {
+                lines.insert(heading + 1, String::new());
+                lines.insert(heading + 2, session.line());
+            }
+        }
+    } else {
+        let Some(i) = open else {
+            return Err(match label {
+                Some(label) => format!("no running session {:?} in today's note", label),
+                None => "no running session in today's note".to_string(),
+            });
+        };
+        if let Some(mut session) = parse_session(&lines[i]) {
+            session.end = Some(now);
+            lines[i] = session.line();
+        }
+    }
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    Ok(updated)
+}
+
+/// `mdbook-daily log start LABEL | stop [LABEL] [--section NAME]`: log a
+/// work session in today's note under "## Sessions", creating the note
+/// from the template if there is none yet
+pub fn run(src_path: &Path, args: &[String]) -> io::Result<()> {
+    let mut start = None;
+    let mut label = None;
+    let mut section = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--section" => {
+                let value = args.next().ok_or_else(|| crate::usage_error("--section needs a directory"))?;
+                section = Some(value.clone());
+            }
+            "start" if start.is_none() => start = Some(true),
+            "stop" if start.is_none() => start = Some(false),
+            _ if start.is_some() && label.is_none() => label = Some(arg.clone()),
+            _ => return Err(crate::usage_error(&format!("unknown log argument {:?}", arg))),
+        }
+    }
+    let Some(start) = start else { return Err(crate::usage_error("log needs start or stop")) };
+    if start && label.is_none() {
+        return Err(crate::usage_error("log start needs a label"));
+    }
+
+    let config = Config::load(Path::new("."))?;
+    let options = config::book_options(Path::new("."))?;
+    let section = pick_section(src_path, &options, section, "log")?;
+    let template = template(&config, None, &section)?;
+
+    let now = DateTime::now();
+    let today = Date::today();
+    let path = src_path.join(&section).join(today.entry_path());
+    let created = !path.exists();
+    if created {
+        create_entry(&path, today, &template)?;
+    }
+    let content = fs::read_to_string(&path)?;
+    let updated = update(&content, start, label.as_deref(), now.hour * 60 + now.minute).map_err(io::Error::other)?;
+    fs::write(&path, updated)?;
+    if created {
+        crate::write_summary(src_path, &options, &mut ScanStats::default())?;
+    }
+    println!("{}", path.display());
+    Ok(())
+}
+
+/// Write the time log page: total time per label, then each day's
+/// sessions with a link to its note. Running sessions are not counted.
+/// `page` is relative to src. Returns the number of sessions.
+pub fn write_time_log_page(src_path: &Path, strings: &Strings, stats: &ScanStats, page: &Path, out: &mut dyn Write) -> io::Result<usize> {
+    let page_dir = page.parent().unwrap_or(Path::new(""));
+    let mut dated: Vec<_> = stats.dated.iter().collect();
+    dated.sort();
+    let mut totals: BTreeMap<String, (usize, u32)> = BTreeMap::new();
+    let mut days = Vec::new();
+    for (date, title, link) in dated {
+        let relative = Path::new(link.trim_start_matches("./"));
+        let Ok(content) = fs::read_to_string(src_path.join(relative)) else { continue };
+        let mut day: BTreeMap<String, u32> = BTreeMap::new();
+        for session in sessions(&content) {
+            let Some(minutes) = session.minutes() else { continue };
+            let total = totals.entry(session.label.clone()).or_default();
+            total.0 += 1;
+            total.1 += minutes;
+            *day.entry(session.label).or_default() += minutes;
+        }
+        if !day.is_empty() {
+            days.push((date, title, relative_link(page_dir, relative), day));
+        }
+    }
+
+    writeln!(out, "# {}", strings.time_log)?;
+    writeln!(out)?;
+    if totals.is_empty() {
+        writeln!(out, "{}", strings.no_sessions)?;
+        return Ok(0);
+    }
+    writeln!(out, "| {} | {} | {} |", strings.label, strings.sessions, strings.time)?;
+    writeln!(out, "| --- | --- | --- |")?;
+    for (label, (count, minutes)) in &totals {
+        writeln!(out, "| {} | {} | {} |", label, count, duration(*minutes))?;
+    }
+    for (date, title, link, day) in days.iter().rev() {
+        writeln!(out)?;
+        writeln!(out, "## {}", date)?;
+        writeln!(out)?;
+        writeln!(out, "[{}]({})", escape_title(title), link)?;
+        writeln!(out)?;
+        for (label, minutes) in day {
+            writeln!(out, "- {}: {}", label, duration(*minutes))?;
+        }
+    }
+    Ok(totals.values().map(|(count, _)| count).sum())
+}
+
+fn duration(minutes: u32) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {:02}m", h, m),
+    }
+}